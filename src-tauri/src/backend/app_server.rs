@@ -1,42 +1,39 @@
 use serde_json::{json, Value};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::env;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU64, Ordering};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock, RwLock as StdRwLock, Weak};
 use std::time::Duration;
 
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::process::{Child, ChildStdin, Command};
-use tokio::sync::{mpsc, oneshot, Mutex};
-use tokio::time::timeout;
+use tokio::process::{Child, ChildStderr, ChildStdin, ChildStdout, Command};
+use tokio::sync::{oneshot, Mutex};
+use tokio::task::JoinHandle;
+use tokio::time::{sleep, timeout, Instant};
 
+use crate::backend::background_callbacks::BackgroundThreadSender;
 use crate::backend::events::{AppServerEvent, EventSink};
+use crate::backend::protocol::{
+    thread_id_from_message, thread_id_from_response, AppServerCapabilities, ClientRequest,
+    ServerNotification, ServerRequest, TurnInterruptParams,
+};
 use crate::codex::args::parse_codex_args;
-use crate::shared::process_core::{kill_child_process_tree, tokio_command};
-use crate::types::WorkspaceEntry;
+use crate::shared::event_journal_core::{record_app_server_message, JournalDirection};
+use crate::shared::process_core::{
+    kill_child_process_tree, kill_child_process_tree_with, tokio_command,
+};
+use crate::shared::process_stats_core::descendant_pids;
+use crate::types::{AppSettings, WorkspaceEntry};
 
 #[cfg(target_os = "windows")]
 use crate::shared::process_core::{build_cmd_c_command, resolve_windows_executable};
 #[cfg(target_os = "windows")]
 use std::os::windows::process::CommandExt;
 
-fn extract_thread_id(value: &Value) -> Option<String> {
-    let params = value.get("params")?;
-
-    params
-        .get("threadId")
-        .or_else(|| params.get("thread_id"))
-        .and_then(|t| t.as_str())
-        .map(|s| s.to_string())
-        .or_else(|| {
-            params
-                .get("thread")
-                .and_then(|thread| thread.get("id"))
-                .and_then(|t| t.as_str())
-                .map(|s| s.to_string())
-        })
+pub(crate) fn extract_thread_id(value: &Value) -> Option<String> {
+    thread_id_from_message(value)
 }
 
 fn build_initialize_params(client_version: &str) -> Value {
@@ -52,34 +49,419 @@ fn build_initialize_params(client_version: &str) -> Value {
     })
 }
 
+fn extract_response_thread_id(value: &Value) -> Option<String> {
+    thread_id_from_response(value)
+}
+
+/// Everything needed to launch (and later relaunch) the app-server process.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct AppServerLaunchConfig {
+    pub(crate) codex_bin: Option<String>,
+    pub(crate) codex_args: Option<String>,
+    pub(crate) codex_home: Option<PathBuf>,
+    pub(crate) client_version: String,
+}
+
 pub(crate) struct WorkspaceSession {
     pub(crate) entry: WorkspaceEntry,
+    pub(crate) launch: AppServerLaunchConfig,
     pub(crate) child: Mutex<Child>,
-    pub(crate) stdin: Mutex<ChildStdin>,
+    /// `None` once closed during a graceful shutdown.
+    pub(crate) stdin: Mutex<Option<ChildStdin>>,
     pub(crate) pending: Mutex<HashMap<u64, oneshot::Sender<Value>>>,
     pub(crate) next_id: AtomicU64,
     /// Callbacks for background threads - events for these threadIds are sent through the channel
-    pub(crate) background_thread_callbacks: Mutex<HashMap<String, mpsc::Sender<Value>>>,
+    pub(crate) background_thread_callbacks: Mutex<HashMap<String, BackgroundThreadSender>>,
+    /// Threads started or resumed on this session; re-resumed after a supervised restart.
+    pub(crate) active_threads: Mutex<HashSet<String>>,
+    /// Set when the session is torn down on purpose so the supervisor does not respawn it.
+    pub(crate) shutdown_requested: AtomicBool,
+    pub(crate) restart_count: AtomicU32,
+    /// When the session was first spawned; supervised restarts keep it.
+    pub(crate) started_at: Instant,
+    /// Learned from `initialize`, refined as methods are rejected as unknown.
+    pub(crate) capabilities: StdRwLock<AppServerCapabilities>,
+    /// Set when the process may be shared with other workspaces; see
+    /// [`app_server_pool_key`].
+    pub(crate) pool_key: StdMutex<Option<String>>,
+    /// Workspaces attached to this process, keyed by id. Doubles as the
+    /// reference count: the process is terminated when the last one detaches.
+    pub(crate) members: StdMutex<HashMap<String, WorkspaceEntry>>,
+    /// Thread id -> owning workspace id, used to route events of a pooled
+    /// process back to the right workspace.
+    pub(crate) thread_routes: StdMutex<HashMap<String, String>>,
+    /// Thread id -> id of the turn currently running on it, so shutdown can
+    /// interrupt in-flight work instead of killing it mid-write.
+    pub(crate) running_turns: StdMutex<HashMap<String, String>>,
 }
 
 const APP_SERVER_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
-pub(crate) const BACKGROUND_THREAD_EVENT_BUFFER: usize = 128;
+const APP_SERVER_INITIALIZE_TIMEOUT: Duration = Duration::from_secs(15);
+
+const SUPERVISOR_RESTART_BACKOFF_BASE: Duration = Duration::from_secs(1);
+const SUPERVISOR_RESTART_BACKOFF_MAX: Duration = Duration::from_secs(60);
+const SUPERVISOR_CRASH_BUDGET: usize = 5;
+const SUPERVISOR_CRASH_WINDOW: Duration = Duration::from_secs(10 * 60);
+
+/// How long a graceful shutdown waits for interrupted turns to complete.
+const SHUTDOWN_TURN_TIMEOUT: Duration = Duration::from_secs(10);
+/// How long a graceful shutdown waits for the process to exit after its
+/// stdin is closed, before killing the tree.
+const SHUTDOWN_EXIT_TIMEOUT: Duration = Duration::from_secs(3);
+const SHUTDOWN_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Built-in per-method timeouts (seconds). Slow history calls get more room,
+/// cheap metadata calls fail fast. `AppSettings` overrides take precedence.
+const DEFAULT_METHOD_TIMEOUT_SECS: &[(&str, u64)] = &[
+    ("thread/list", 120),
+    ("thread/read", 90),
+    ("thread/resume", 90),
+    ("thread/compact/start", 120),
+    ("model/list", 10),
+    ("collaborationMode/list", 10),
+    ("experimentalFeature/list", 10),
+    ("account/read", 10),
+    ("account/rateLimits/read", 10),
+];
+
+static REQUEST_TIMEOUT_POLICY: OnceLock<StdRwLock<RequestTimeoutPolicy>> = OnceLock::new();
+
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct RequestTimeoutPolicy {
+    default: Duration,
+    per_method: HashMap<String, Duration>,
+}
+
+impl Default for RequestTimeoutPolicy {
+    fn default() -> Self {
+        Self {
+            default: APP_SERVER_REQUEST_TIMEOUT,
+            per_method: DEFAULT_METHOD_TIMEOUT_SECS
+                .iter()
+                .map(|(method, secs)| (method.to_string(), Duration::from_secs(*secs)))
+                .collect(),
+        }
+    }
+}
+
+impl RequestTimeoutPolicy {
+    pub(crate) fn from_settings(settings: &AppSettings) -> Self {
+        let mut policy = Self::default();
+        if let Some(secs) = settings
+            .app_server_request_timeout_secs
+            .filter(|secs| *secs > 0)
+        {
+            policy.default = Duration::from_secs(secs);
+        }
+        for (method, secs) in &settings.app_server_method_timeout_secs {
+            let method = method.trim();
+            if method.is_empty() || *secs == 0 {
+                continue;
+            }
+            policy
+                .per_method
+                .insert(method.to_string(), Duration::from_secs(*secs));
+        }
+        policy
+    }
+
+    pub(crate) fn timeout_for(&self, method: &str) -> Duration {
+        self.per_method.get(method).copied().unwrap_or(self.default)
+    }
+}
+
+fn request_timeout_policy() -> &'static StdRwLock<RequestTimeoutPolicy> {
+    REQUEST_TIMEOUT_POLICY.get_or_init(|| StdRwLock::new(RequestTimeoutPolicy::default()))
+}
+
+/// Apply the timeout table from settings to every session in this process.
+pub(crate) fn configure_request_timeouts(settings: &AppSettings) {
+    let policy = RequestTimeoutPolicy::from_settings(settings);
+    match request_timeout_policy().write() {
+        Ok(mut current) => *current = policy,
+        Err(poisoned) => *poisoned.into_inner() = policy,
+    }
+}
+
+fn request_timeout_for(method: &str) -> Duration {
+    match request_timeout_policy().read() {
+        Ok(policy) => policy.timeout_for(method),
+        Err(poisoned) => poisoned.into_inner().timeout_for(method),
+    }
+}
+
+static APP_SERVER_POOLING: AtomicBool = AtomicBool::new(false);
+static APP_SERVER_POOL: OnceLock<StdMutex<HashMap<String, Weak<WorkspaceSession>>>> =
+    OnceLock::new();
+static APP_SERVER_POOL_SPAWN_LOCKS: OnceLock<StdMutex<HashMap<String, Arc<Mutex<()>>>>> =
+    OnceLock::new();
+
+/// Apply the pooling opt-in from settings. Only affects sessions spawned
+/// afterwards; existing shared processes live until their last workspace
+/// disconnects.
+pub(crate) fn configure_app_server_pooling(settings: &AppSettings) {
+    APP_SERVER_POOLING.store(settings.app_server_pooling, Ordering::SeqCst);
+}
+
+/// Workspaces may share a process only when it would be launched identically.
+pub(crate) fn app_server_pool_key(
+    codex_bin: Option<&str>,
+    codex_args: Option<&str>,
+    codex_home: Option<&Path>,
+) -> String {
+    let codex_bin = codex_bin
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .unwrap_or("codex");
+    let codex_args = codex_args.map(str::trim).unwrap_or_default();
+    let codex_home = codex_home
+        .map(|path| path.to_string_lossy().to_string())
+        .unwrap_or_default();
+    json!([codex_bin, codex_args, codex_home]).to_string()
+}
+
+fn app_server_pool() -> &'static StdMutex<HashMap<String, Weak<WorkspaceSession>>> {
+    APP_SERVER_POOL.get_or_init(|| StdMutex::new(HashMap::new()))
+}
+
+/// Serializes find-or-spawn per pool key so two concurrent connects with the
+/// same launch share one process instead of both spawning.
+fn pool_spawn_lock(key: &str) -> Arc<Mutex<()>> {
+    let mut locks = lock_std(APP_SERVER_POOL_SPAWN_LOCKS.get_or_init(Default::default));
+    locks.retain(|_, lock| Arc::strong_count(lock) > 1);
+    Arc::clone(locks.entry(key.to_string()).or_default())
+}
+
+async fn find_pooled_session(key: &str) -> Option<Arc<WorkspaceSession>> {
+    let session = {
+        let mut pool = lock_std(app_server_pool());
+        pool.retain(|_, session| session.strong_count() > 0);
+        pool.get(key).and_then(Weak::upgrade)
+    }?;
+    session.is_running().await.then_some(session)
+}
+
+fn register_pooled_session(key: String, session: &Arc<WorkspaceSession>) {
+    *lock_std(&session.pool_key) = Some(key.clone());
+    lock_std(app_server_pool()).insert(key, Arc::downgrade(session));
+}
+
+fn unregister_pooled_session(key: &str, session: &WorkspaceSession) {
+    let mut pool = lock_std(app_server_pool());
+    let is_current = pool
+        .get(key)
+        .is_some_and(|pooled| std::ptr::eq(pooled.as_ptr(), session));
+    if is_current {
+        pool.remove(key);
+    }
+}
+
+/// An in-flight app-server request. Await it with
+/// [`WorkspaceSession::wait_request`] or abandon it with
+/// [`WorkspaceSession::cancel_request`].
+pub(crate) struct RequestHandle {
+    pub(crate) id: u64,
+    method: String,
+    tracking_params: Value,
+    timeout: Duration,
+    rx: oneshot::Receiver<Value>,
+}
 
 impl WorkspaceSession {
     async fn write_message(&self, value: Value) -> Result<(), String> {
         let mut stdin = self.stdin.lock().await;
+        let stdin = stdin
+            .as_mut()
+            .ok_or_else(|| "app-server stdin is closed".to_string())?;
         let mut line = serde_json::to_string(&value).map_err(|e| e.to_string())?;
         line.push('\n');
+        self.record_for_members(JournalDirection::Outbound, &value);
         stdin
             .write_all(line.as_bytes())
             .await
             .map_err(|e| e.to_string())
     }
 
+    pub(crate) fn capabilities(&self) -> AppServerCapabilities {
+        match self.capabilities.read() {
+            Ok(capabilities) => capabilities.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    pub(crate) fn mark_method_unsupported(&self, method: &str) {
+        match self.capabilities.write() {
+            Ok(mut capabilities) => capabilities.mark_unsupported(method),
+            Err(poisoned) => poisoned.into_inner().mark_unsupported(method),
+        }
+    }
+
+    pub(crate) fn pool_key(&self) -> Option<String> {
+        lock_std(&self.pool_key).clone()
+    }
+
+    /// Journal process-level traffic (requests we write, responses we read)
+    /// for every attached workspace, so replay works from any of them.
+    fn record_for_members(&self, direction: JournalDirection, value: &Value) {
+        for workspace_id in self.workspace_ids() {
+            record_app_server_message(&workspace_id, direction, value);
+        }
+    }
+
+    fn attach_workspace(&self, entry: WorkspaceEntry) {
+        lock_std(&self.members).insert(entry.id.clone(), entry);
+    }
+
+    /// Returns how many workspaces are still attached.
+    pub(crate) fn detach_workspace(&self, workspace_id: &str) -> usize {
+        lock_std(&self.thread_routes).retain(|_, owner| owner != workspace_id);
+        let mut members = lock_std(&self.members);
+        members.remove(workspace_id);
+        members.len()
+    }
+
+    /// Drop `workspace_id`'s reference and shut the process down gracefully
+    /// once no workspace uses it any more.
+    pub(crate) async fn release(&self, workspace_id: &str) {
+        // Read before detaching, which forgets the workspace's thread routes.
+        let turns = self.running_turns_for(workspace_id);
+        if self.detach_workspace(workspace_id) > 0 {
+            // The process stays up for the other workspaces, so stop the
+            // turns nobody is watching any more.
+            for (thread_id, turn_id) in turns {
+                let request = ClientRequest::TurnInterrupt(TurnInterruptParams {
+                    thread_id: thread_id.clone(),
+                    turn_id,
+                });
+                if let Err(error) = self.send_typed(request).await {
+                    eprintln!(
+                        "release: failed to interrupt turn: workspace_id={workspace_id}, thread_id={thread_id}, error={error}"
+                    );
+                }
+            }
+            return;
+        }
+        if let Some(key) = self.pool_key() {
+            unregister_pooled_session(&key, self);
+        }
+        self.shutdown_gracefully().await;
+    }
+
+    pub(crate) fn workspace_ids(&self) -> Vec<String> {
+        let mut ids: Vec<String> = lock_std(&self.members).keys().cloned().collect();
+        if ids.is_empty() {
+            ids.push(self.entry.id.clone());
+        }
+        ids.sort();
+        ids
+    }
+
+    /// The attached entry for `workspace_id`, falling back to the entry the
+    /// process was launched for.
+    pub(crate) fn workspace_entry(&self, workspace_id: &str) -> WorkspaceEntry {
+        lock_std(&self.members)
+            .get(workspace_id)
+            .cloned()
+            .unwrap_or_else(|| self.entry.clone())
+    }
+
+    pub(crate) fn workspace_path(&self, workspace_id: &str) -> String {
+        self.workspace_entry(workspace_id).path
+    }
+
+    /// Remember which workspace a thread belongs to. Only pooled processes
+    /// need this; a dedicated process routes everything to its workspace.
+    pub(crate) fn bind_thread(&self, workspace_id: &str, thread_id: &str) {
+        if lock_std(&self.pool_key).is_none() {
+            return;
+        }
+        lock_std(&self.thread_routes).insert(thread_id.to_string(), workspace_id.to_string());
+    }
+
+    /// `(thread id, turn id)` of the turns running for `workspace_id`. On a
+    /// pooled process, threads without a route count as the owner's.
+    pub(crate) fn running_turns_for(&self, workspace_id: &str) -> Vec<(String, String)> {
+        let pooled = lock_std(&self.pool_key).is_some();
+        let routes = lock_std(&self.thread_routes);
+        lock_std(&self.running_turns)
+            .iter()
+            .filter(|(thread_id, _)| {
+                !pooled
+                    || match routes.get(*thread_id) {
+                        Some(owner) => owner == workspace_id,
+                        None => self.entry.id == workspace_id,
+                    }
+            })
+            .map(|(thread_id, turn_id)| (thread_id.clone(), turn_id.clone()))
+            .collect()
+    }
+
+    /// Workspaces that should receive a server-initiated message.
+    fn route_workspace_ids(&self, thread_id: Option<&str>, message: &Value) -> Vec<String> {
+        if lock_std(&self.pool_key).is_none() {
+            return vec![self.entry.id.clone()];
+        }
+        let members = lock_std(&self.members);
+        let mut routes = lock_std(&self.thread_routes);
+        route_pooled_message(&members, &mut routes, &self.entry.id, thread_id, message)
+    }
+
+    /// Entry to launch a replacement process from. Prefer the original
+    /// workspace; it may have detached (and its worktree been deleted) since.
+    fn launch_entry(&self) -> WorkspaceEntry {
+        let members = lock_std(&self.members);
+        if members.is_empty() || members.contains_key(&self.entry.id) {
+            return self.entry.clone();
+        }
+        members
+            .values()
+            .min_by(|a, b| a.id.cmp(&b.id))
+            .cloned()
+            .unwrap_or_else(|| self.entry.clone())
+    }
+
+    /// `false` once the process has exited or was torn down on purpose.
+    pub(crate) async fn is_running(&self) -> bool {
+        if self.shutdown_requested.load(Ordering::SeqCst) {
+            return false;
+        }
+        matches!(self.child.lock().await.try_wait(), Ok(None))
+    }
+
+    fn set_capabilities(&self, capabilities: AppServerCapabilities) {
+        match self.capabilities.write() {
+            Ok(mut current) => *current = capabilities,
+            Err(poisoned) => *poisoned.into_inner() = capabilities,
+        }
+    }
+
+    pub(crate) async fn send_typed(&self, request: ClientRequest) -> Result<Value, String> {
+        let handle = self.begin_typed(request).await?;
+        self.wait_request(handle).await
+    }
+
+    pub(crate) async fn begin_typed(
+        &self,
+        request: ClientRequest,
+    ) -> Result<RequestHandle, String> {
+        let (method, params) = request.into_parts()?;
+        self.begin_request(&method, params).await
+    }
+
     pub(crate) async fn send_request(&self, method: &str, params: Value) -> Result<Value, String> {
+        let handle = self.begin_request(method, params).await?;
+        self.wait_request(handle).await
+    }
+
+    pub(crate) async fn begin_request(
+        &self,
+        method: &str,
+        params: Value,
+    ) -> Result<RequestHandle, String> {
         let id = self.next_id.fetch_add(1, Ordering::SeqCst);
         let (tx, rx) = oneshot::channel();
         self.pending.lock().await.insert(id, tx);
+        let tracking_params = thread_tracking_params(method, &params);
         if let Err(error) = self
             .write_message(json!({ "id": id, "method": method, "params": params }))
             .await
@@ -87,8 +469,29 @@ impl WorkspaceSession {
             self.pending.lock().await.remove(&id);
             return Err(error);
         }
-        match timeout(APP_SERVER_REQUEST_TIMEOUT, rx).await {
-            Ok(Ok(value)) => Ok(value),
+        Ok(RequestHandle {
+            id,
+            method: method.to_string(),
+            tracking_params,
+            timeout: request_timeout_for(method),
+            rx,
+        })
+    }
+
+    pub(crate) async fn wait_request(&self, handle: RequestHandle) -> Result<Value, String> {
+        let RequestHandle {
+            id,
+            method,
+            tracking_params,
+            timeout: request_timeout,
+            rx,
+        } = handle;
+        match timeout(request_timeout, rx).await {
+            Ok(Ok(value)) => {
+                self.track_thread_activity(&method, &tracking_params, &value)
+                    .await;
+                Ok(value)
+            }
             Ok(Err(_)) => {
                 self.pending.lock().await.remove(&id);
                 Err("request canceled".to_string())
@@ -96,13 +499,20 @@ impl WorkspaceSession {
             Err(_) => {
                 self.pending.lock().await.remove(&id);
                 Err(format!(
-                    "request timed out after {}s",
-                    APP_SERVER_REQUEST_TIMEOUT.as_secs()
+                    "{method} request timed out after {}s",
+                    request_timeout.as_secs()
                 ))
             }
         }
     }
 
+    /// Drop the waiter for `id`. The app-server protocol has no cancel
+    /// notification, so a late response is simply ignored by the reader.
+    /// Returns `false` when the request already completed.
+    pub(crate) async fn cancel_request(&self, id: u64) -> bool {
+        self.pending.lock().await.remove(&id).is_some()
+    }
+
     pub(crate) async fn send_notification(
         &self,
         method: &str,
@@ -120,6 +530,249 @@ impl WorkspaceSession {
         self.write_message(json!({ "id": id, "result": result }))
             .await
     }
+
+    /// Kill the app-server on purpose. The supervisor sees the flag and does
+    /// not attempt a restart.
+    pub(crate) async fn terminate(&self) {
+        self.shutdown_requested.store(true, Ordering::SeqCst);
+        let mut child = self.child.lock().await;
+        kill_child_process_tree(&mut child).await;
+    }
+
+    /// Stop the app-server without cutting off in-flight work: interrupt
+    /// running turns and wait for them to complete, close stdin, wait for the
+    /// process to exit, and only kill the tree when a deadline passes.
+    pub(crate) async fn shutdown_gracefully(&self) {
+        self.shutdown_with_timeouts(SHUTDOWN_TURN_TIMEOUT, SHUTDOWN_EXIT_TIMEOUT)
+            .await;
+    }
+
+    async fn shutdown_with_timeouts(&self, turn_timeout: Duration, exit_timeout: Duration) {
+        self.shutdown_requested.store(true, Ordering::SeqCst);
+
+        let turns: Vec<(String, String)> = lock_std(&self.running_turns)
+            .iter()
+            .map(|(thread_id, turn_id)| (thread_id.clone(), turn_id.clone()))
+            .collect();
+        if !turns.is_empty() {
+            let mut interrupt_ids = Vec::with_capacity(turns.len());
+            for (thread_id, turn_id) in turns {
+                let request = ClientRequest::TurnInterrupt(TurnInterruptParams {
+                    thread_id: thread_id.clone(),
+                    turn_id,
+                });
+                match self.begin_typed(request).await {
+                    Ok(handle) => interrupt_ids.push(handle.id),
+                    Err(error) => eprintln!(
+                        "shutdown: failed to interrupt turn: workspace_id={}, thread_id={thread_id}, error={error}",
+                        self.entry.id
+                    ),
+                }
+            }
+            let deadline = Instant::now() + turn_timeout;
+            while !lock_std(&self.running_turns).is_empty() && Instant::now() < deadline {
+                sleep(SHUTDOWN_POLL_INTERVAL).await;
+            }
+            let unfinished = lock_std(&self.running_turns).len();
+            if unfinished > 0 {
+                eprintln!(
+                    "shutdown: turns still running at deadline: workspace_id={}, count={unfinished}",
+                    self.entry.id
+                );
+            }
+            // Interrupt responses are not needed once the turns have ended.
+            let mut pending = self.pending.lock().await;
+            for id in interrupt_ids {
+                pending.remove(&id);
+            }
+        }
+
+        // Descendants are listed up front: once the app-server exits they are
+        // reparented and can no longer be found from its pid.
+        let descendants = self
+            .child
+            .lock()
+            .await
+            .id()
+            .map(descendant_pids)
+            .unwrap_or_default();
+        // Closing stdin is the app-server's cue to flush and exit.
+        drop(self.stdin.lock().await.take());
+        let mut child = self.child.lock().await;
+        match timeout(exit_timeout, child.wait()).await {
+            Ok(Ok(_)) => {}
+            Ok(Err(error)) => eprintln!(
+                "shutdown: failed to wait for app-server: workspace_id={}, error={error}",
+                self.entry.id
+            ),
+            Err(_) => eprintln!(
+                "shutdown: app-server did not exit in {}s, killing: workspace_id={}",
+                exit_timeout.as_secs(),
+                self.entry.id
+            ),
+        }
+        // Tools the app-server spawned can outlive even a clean exit.
+        kill_child_process_tree_with(&mut child, &descendants).await;
+    }
+
+    /// Keep `running_turns` in sync with turn lifecycle notifications.
+    fn track_turn_notification(&self, message: &Value) {
+        let is_turn_event = matches!(
+            message.get("method").and_then(Value::as_str),
+            Some("turn/started" | "turn/completed" | "turn/error" | "error")
+        );
+        if !is_turn_event {
+            return;
+        }
+        let mut running_turns = lock_std(&self.running_turns);
+        match ServerNotification::parse(message) {
+            Some(ServerNotification::TurnStarted { thread_id, turn }) => {
+                if let Some(turn_id) = turn.id {
+                    running_turns.insert(thread_id, turn_id);
+                }
+            }
+            Some(ServerNotification::TurnCompleted { thread_id, .. })
+            | Some(ServerNotification::TurnError {
+                thread_id: Some(thread_id),
+                will_retry: false,
+                ..
+            }) => {
+                running_turns.remove(&thread_id);
+            }
+            _ => {}
+        }
+    }
+
+    async fn track_thread_activity(&self, method: &str, params: &Value, response: &Value) {
+        if response.get("error").is_some() {
+            return;
+        }
+        match method {
+            "thread/start" | "thread/resume" | "thread/fork" => {
+                let thread_id = extract_response_thread_id(response).or_else(|| {
+                    params
+                        .get("threadId")
+                        .and_then(Value::as_str)
+                        .map(str::to_string)
+                });
+                if let Some(thread_id) = thread_id {
+                    self.active_threads.lock().await.insert(thread_id);
+                }
+            }
+            "thread/archive" => {
+                if let Some(thread_id) = params.get("threadId").and_then(Value::as_str) {
+                    self.active_threads.lock().await.remove(thread_id);
+                    lock_std(&self.thread_routes).remove(thread_id);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+fn lock_std<T>(mutex: &StdMutex<T>) -> std::sync::MutexGuard<'_, T> {
+    match mutex.lock() {
+        Ok(guard) => guard,
+        Err(poisoned) => poisoned.into_inner(),
+    }
+}
+
+fn normalize_workspace_path(path: &str) -> &str {
+    let trimmed = path.trim();
+    let stripped = trimmed.trim_end_matches(['/', '\\']);
+    if stripped.is_empty() {
+        trimmed
+    } else {
+        stripped
+    }
+}
+
+/// Routing for a pooled process: thread-scoped messages go to the thread's
+/// workspace (learned from `thread/started`'s cwd or an explicit bind),
+/// everything else is account-level and goes to every attached workspace.
+fn route_pooled_message(
+    members: &HashMap<String, WorkspaceEntry>,
+    routes: &mut HashMap<String, String>,
+    owner_id: &str,
+    thread_id: Option<&str>,
+    message: &Value,
+) -> Vec<String> {
+    let Some(thread_id) = thread_id else {
+        let mut ids: Vec<String> = members.keys().cloned().collect();
+        if ids.is_empty() {
+            ids.push(owner_id.to_string());
+        }
+        ids.sort();
+        return ids;
+    };
+    if let Some(ServerNotification::ThreadStarted { thread }) = ServerNotification::parse(message) {
+        let by_cwd = thread.cwd.as_deref().and_then(|cwd| {
+            let cwd = normalize_workspace_path(cwd);
+            members
+                .values()
+                .find(|entry| normalize_workspace_path(&entry.path) == cwd)
+        });
+        if let Some(entry) = by_cwd {
+            routes.insert(thread_id.to_string(), entry.id.clone());
+        }
+    }
+    if let Some(workspace_id) = routes.get(thread_id) {
+        if members.contains_key(workspace_id) {
+            return vec![workspace_id.clone()];
+        }
+    }
+    let fallback = if members.contains_key(owner_id) {
+        None
+    } else {
+        members.keys().min().cloned()
+    };
+    vec![fallback.unwrap_or_else(|| owner_id.to_string())]
+}
+
+fn thread_tracking_params(method: &str, params: &Value) -> Value {
+    match method {
+        "thread/start" | "thread/resume" | "thread/fork" | "thread/archive" => params.clone(),
+        _ => Value::Null,
+    }
+}
+
+/// Sliding-window restart budget: at most `max` restarts within `window`.
+struct CrashBudget {
+    max: usize,
+    window: Duration,
+    restarts: VecDeque<Instant>,
+}
+
+impl CrashBudget {
+    fn new(max: usize, window: Duration) -> Self {
+        Self {
+            max,
+            window,
+            restarts: VecDeque::new(),
+        }
+    }
+
+    fn try_consume(&mut self, now: Instant) -> bool {
+        while let Some(oldest) = self.restarts.front() {
+            if now.duration_since(*oldest) > self.window {
+                self.restarts.pop_front();
+            } else {
+                break;
+            }
+        }
+        if self.restarts.len() >= self.max {
+            return false;
+        }
+        self.restarts.push_back(now);
+        true
+    }
+}
+
+fn restart_backoff(attempt: u32) -> Duration {
+    let exponent = attempt.saturating_sub(1).min(16);
+    SUPERVISOR_RESTART_BACKOFF_BASE
+        .saturating_mul(1u32 << exponent)
+        .min(SUPERVISOR_RESTART_BACKOFF_MAX)
 }
 
 fn clear_pending_requests(pending: &mut HashMap<u64, oneshot::Sender<Value>>) {
@@ -132,29 +785,20 @@ async fn try_send_background_callback(
     thread_id: &str,
     value: Value,
 ) -> BackgroundCallbackDispatch {
-    let sender = {
-        let callbacks = session.background_thread_callbacks.lock().await;
-        callbacks.get(thread_id).cloned()
-    };
-
-    let Some(sender) = sender else {
+    let callbacks = session.background_thread_callbacks.lock().await;
+    let Some(sender) = callbacks.get(thread_id) else {
         return BackgroundCallbackDispatch::Missing;
     };
 
-    match sender.try_send(value) {
+    // The entry outlives its receiver so later events for the hidden thread
+    // are dropped too; the prompt that registered it removes it.
+    match sender.send(value) {
         Ok(()) => BackgroundCallbackDispatch::Sent,
-        Err(tokio::sync::mpsc::error::TrySendError::Full(_)) => {
-            eprintln!(
-                "background thread callback queue full, falling back to app-server-event path: workspace_id={workspace_id}, thread_id={thread_id}"
-            );
-            BackgroundCallbackDispatch::QueueFull
-        }
-        Err(tokio::sync::mpsc::error::TrySendError::Closed(_)) => {
+        Err(value) => {
             eprintln!(
-                "background thread callback channel closed: workspace_id={workspace_id}, thread_id={thread_id}"
+                "dropping event for background thread without receiver: workspace_id={workspace_id}, thread_id={thread_id}, method={}",
+                value.get("method").and_then(Value::as_str).unwrap_or_default()
             );
-            let mut callbacks = session.background_thread_callbacks.lock().await;
-            callbacks.remove(thread_id);
             BackgroundCallbackDispatch::Closed
         }
     }
@@ -164,7 +808,6 @@ async fn try_send_background_callback(
 enum BackgroundCallbackDispatch {
     Missing,
     Sent,
-    QueueFull,
     Closed,
 }
 
@@ -354,28 +997,24 @@ pub(crate) async fn check_codex_installation(
     })
 }
 
-pub(crate) async fn spawn_workspace_session<E: EventSink>(
-    entry: WorkspaceEntry,
-    default_codex_bin: Option<String>,
-    codex_args: Option<String>,
-    codex_home: Option<PathBuf>,
-    client_version: String,
-    event_sink: E,
-) -> Result<Arc<WorkspaceSession>, String> {
-    let codex_bin = entry
-        .codex_bin
-        .clone()
-        .filter(|value| !value.trim().is_empty())
-        .or(default_codex_bin);
-    let _ = check_codex_installation(codex_bin.clone()).await?;
+struct SpawnedAppServer {
+    child: Child,
+    stdin: ChildStdin,
+    stdout: ChildStdout,
+    stderr: ChildStderr,
+}
 
+fn launch_app_server(
+    entry: &WorkspaceEntry,
+    launch: &AppServerLaunchConfig,
+) -> Result<SpawnedAppServer, String> {
     let mut command = build_codex_command_with_bin(
-        codex_bin,
-        codex_args.as_deref(),
+        launch.codex_bin.clone(),
+        launch.codex_args.as_deref(),
         vec!["app-server".to_string()],
     )?;
     command.current_dir(&entry.path);
-    if let Some(codex_home) = codex_home {
+    if let Some(codex_home) = launch.codex_home.as_ref() {
         command.env("CODEX_HOME", codex_home);
     }
     command.stdin(std::process::Stdio::piped());
@@ -386,190 +1025,166 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
     let stdin = child.stdin.take().ok_or("missing stdin")?;
     let stdout = child.stdout.take().ok_or("missing stdout")?;
     let stderr = child.stderr.take().ok_or("missing stderr")?;
+    Ok(SpawnedAppServer {
+        child,
+        stdin,
+        stdout,
+        stderr,
+    })
+}
 
-    let session = Arc::new(WorkspaceSession {
-        entry: entry.clone(),
-        child: Mutex::new(child),
-        stdin: Mutex::new(stdin),
-        pending: Mutex::new(HashMap::new()),
-        next_id: AtomicU64::new(1),
-        background_thread_callbacks: Mutex::new(HashMap::new()),
-    });
+async fn dispatch_app_server_notification<E: EventSink>(
+    session: &Arc<WorkspaceSession>,
+    workspace_id: &str,
+    event_sink: &E,
+    thread_id: Option<String>,
+    value: Value,
+    seq: Option<u64>,
+) {
+    // Check for background thread callback
+    let mut is_background = false;
+    if let Some(ref tid) = thread_id {
+        match try_send_background_callback(session, workspace_id, tid, value.clone()).await {
+            BackgroundCallbackDispatch::Sent | BackgroundCallbackDispatch::Closed => {
+                is_background = true;
+            }
+            BackgroundCallbackDispatch::Missing => {}
+        }
+    }
+    // Background threads stay hidden, even once nothing collects their events
+    if !is_background {
+        let payload = AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message: value,
+            seq,
+        };
+        event_sink.emit_app_server_event(payload);
+    }
+}
 
-    let session_clone = Arc::clone(&session);
-    let workspace_id = entry.id.clone();
-    let event_sink_clone = event_sink.clone();
-    tokio::spawn(async move {
-        let mut lines = BufReader::new(stdout).lines();
-        loop {
-            let line = match lines.next_line().await {
-                Ok(Some(line)) => line,
-                Ok(None) => break,
-                Err(error) if error.kind() == ErrorKind::Interrupted => {
-                    // Retry interrupted reads instead of treating them as hard disconnects.
-                    continue;
-                }
-                Err(error) => {
+async fn read_app_server_stdout<E: EventSink>(
+    session: Arc<WorkspaceSession>,
+    stdout: ChildStdout,
+    event_sink: E,
+) {
+    let workspace_id = session.entry.id.clone();
+    let mut lines = BufReader::new(stdout).lines();
+    loop {
+        let line = match lines.next_line().await {
+            Ok(Some(line)) => line,
+            Ok(None) => break,
+            Err(error) if error.kind() == ErrorKind::Interrupted => {
+                // Retry interrupted reads instead of treating them as hard disconnects.
+                continue;
+            }
+            Err(error) => {
+                for workspace_id in session.workspace_ids() {
                     let payload = AppServerEvent {
                         workspace_id: workspace_id.clone(),
                         message: json!({
                             "method": "codex/stdoutReadError",
                             "params": {
-                                "workspaceId": workspace_id.clone(),
+                                "workspaceId": workspace_id,
                                 "kind": format!("{:?}", error.kind()),
                                 "error": error.to_string(),
                             },
                         }),
+                        seq: None,
                     };
-                    event_sink_clone.emit_app_server_event(payload);
-                    break;
+                    event_sink.emit_app_server_event(payload);
                 }
-            };
+                break;
+            }
+        };
 
-            if line.trim().is_empty() {
+        if line.trim().is_empty() {
+            continue;
+        }
+        let value: Value = match serde_json::from_str(&line) {
+            Ok(value) => value,
+            Err(err) => {
+                let message = json!({
+                    "method": "codex/parseError",
+                    "params": { "error": err.to_string(), "raw": line },
+                });
+                emit_process_event(&session, &event_sink, message);
                 continue;
             }
-            let value: Value = match serde_json::from_str(&line) {
-                Ok(value) => value,
-                Err(err) => {
-                    let payload = AppServerEvent {
-                        workspace_id: workspace_id.clone(),
-                        message: json!({
-                            "method": "codex/parseError",
-                            "params": { "error": err.to_string(), "raw": line },
-                        }),
-                    };
-                    event_sink_clone.emit_app_server_event(payload);
-                    continue;
-                }
-            };
+        };
 
-            let maybe_id = value.get("id").and_then(|id| id.as_u64());
-            let has_method = value.get("method").is_some();
-            let has_result_or_error = value.get("result").is_some() || value.get("error").is_some();
-
-            // Check if this event is for a background thread
-            let thread_id = extract_thread_id(&value);
-
-            if let Some(id) = maybe_id {
-                if has_result_or_error {
-                    if let Some(tx) = session_clone.pending.lock().await.remove(&id) {
-                        if tx.send(value).is_err() {
-                            eprintln!(
-                                "failed to deliver pending app-server response: workspace_id={workspace_id}, id={id}"
-                            );
-                        }
-                    }
-                } else if has_method {
-                    // Check for background thread callback
-                    let mut sent_to_background = false;
-                    if let Some(ref tid) = thread_id {
-                        match try_send_background_callback(
-                            &session_clone,
-                            &workspace_id,
-                            tid,
-                            value.clone(),
-                        )
-                        .await
-                        {
-                            BackgroundCallbackDispatch::Sent => {
-                                sent_to_background = true;
-                            }
-                            BackgroundCallbackDispatch::QueueFull => {
-                                let payload = AppServerEvent {
-                                    workspace_id: workspace_id.clone(),
-                                    message: json!({
-                                        "method": "codex/backgroundThreadQueueFallback",
-                                        "params": {
-                                            "workspaceId": workspace_id.clone(),
-                                            "threadId": tid,
-                                            "reason": "queue_full",
-                                            "bufferSize": BACKGROUND_THREAD_EVENT_BUFFER
-                                        },
-                                    }),
-                                };
-                                event_sink_clone.emit_app_server_event(payload);
-                            }
-                            BackgroundCallbackDispatch::Missing
-                            | BackgroundCallbackDispatch::Closed => {}
-                        }
-                    }
-                    // Don't emit to frontend if this is a background thread event
-                    if !sent_to_background {
-                        let payload = AppServerEvent {
-                            workspace_id: workspace_id.clone(),
-                            message: value,
-                        };
-                        event_sink_clone.emit_app_server_event(payload);
-                    }
-                } else if let Some(tx) = session_clone.pending.lock().await.remove(&id) {
+        let maybe_id = value.get("id").and_then(|id| id.as_u64());
+        let has_method = value.get("method").is_some();
+        let has_result_or_error = value.get("result").is_some() || value.get("error").is_some();
+
+        // Check if this event is for a background thread
+        let thread_id = extract_thread_id(&value);
+
+        if !has_method {
+            // Responses belong to the process, not to one workspace.
+            session.record_for_members(JournalDirection::Inbound, &value);
+        }
+
+        if let Some(id) = maybe_id {
+            if has_result_or_error {
+                if let Some(tx) = session.pending.lock().await.remove(&id) {
                     if tx.send(value).is_err() {
                         eprintln!(
-                            "failed to deliver fallback app-server response: workspace_id={workspace_id}, id={id}"
+                            "failed to deliver pending app-server response: workspace_id={workspace_id}, id={id}"
                         );
                     }
                 }
-            } else if has_method {
-                // Check for background thread callback
-                let mut sent_to_background = false;
-                if let Some(ref tid) = thread_id {
-                    match try_send_background_callback(
-                        &session_clone,
-                        &workspace_id,
-                        tid,
-                        value.clone(),
-                    )
-                    .await
-                    {
-                        BackgroundCallbackDispatch::Sent => {
-                            sent_to_background = true;
-                        }
-                        BackgroundCallbackDispatch::QueueFull => {
-                            let payload = AppServerEvent {
-                                workspace_id: workspace_id.clone(),
-                                message: json!({
-                                    "method": "codex/backgroundThreadQueueFallback",
-                                    "params": {
-                                        "workspaceId": workspace_id.clone(),
-                                        "threadId": tid,
-                                        "reason": "queue_full",
-                                        "bufferSize": BACKGROUND_THREAD_EVENT_BUFFER
-                                    },
-                                }),
-                            };
-                            event_sink_clone.emit_app_server_event(payload);
-                        }
-                        BackgroundCallbackDispatch::Missing
-                        | BackgroundCallbackDispatch::Closed => {}
-                    }
-                }
-                // Don't emit to frontend if this is a background thread event
-                if !sent_to_background {
-                    let payload = AppServerEvent {
-                        workspace_id: workspace_id.clone(),
-                        message: value,
-                    };
-                    event_sink_clone.emit_app_server_event(payload);
+            } else if ServerRequest::parse(&value).is_some() {
+                dispatch_routed_message(&session, &event_sink, thread_id, value).await;
+            } else if let Some(tx) = session.pending.lock().await.remove(&id) {
+                if tx.send(value).is_err() {
+                    eprintln!(
+                        "failed to deliver fallback app-server response: workspace_id={workspace_id}, id={id}"
+                    );
                 }
             }
+        } else if has_method {
+            session.track_turn_notification(&value);
+            dispatch_routed_message(&session, &event_sink, thread_id, value).await;
         }
+    }
+}
 
-        // Stdout reading loop exited — the codex app-server process has
-        // disconnected or crashed.  Notify the frontend so it can reset any
-        // processing state that would otherwise spin forever.
-        let payload = AppServerEvent {
-            workspace_id: workspace_id.clone(),
-            message: json!({
-                "method": "codex/disconnected",
-                "params": { "workspaceId": workspace_id.clone() },
-            }),
-        };
-        event_sink_clone.emit_app_server_event(payload);
-        let mut pending = session_clone.pending.lock().await;
-        clear_pending_requests(&mut pending);
-    });
+/// Journal and dispatch a server-initiated message once per workspace it is
+/// routed to. A dedicated process always routes to its own workspace.
+async fn dispatch_routed_message<E: EventSink>(
+    session: &Arc<WorkspaceSession>,
+    event_sink: &E,
+    thread_id: Option<String>,
+    value: Value,
+) {
+    let workspace_ids = session.route_workspace_ids(thread_id.as_deref(), &value);
+    for workspace_id in workspace_ids {
+        let seq = record_app_server_message(&workspace_id, JournalDirection::Inbound, &value);
+        dispatch_app_server_notification(
+            session,
+            &workspace_id,
+            event_sink,
+            thread_id.clone(),
+            value.clone(),
+            seq,
+        )
+        .await;
+    }
+}
+
+fn spawn_app_server_readers<E: EventSink>(
+    session: &Arc<WorkspaceSession>,
+    stdout: ChildStdout,
+    stderr: ChildStderr,
+    event_sink: &E,
+) -> JoinHandle<()> {
+    let stdout_task = tokio::spawn(read_app_server_stdout(
+        Arc::clone(session),
+        stdout,
+        event_sink.clone(),
+    ));
 
-    let workspace_id = entry.id.clone();
+    let session_clone = Arc::clone(session);
     let event_sink_clone = event_sink.clone();
     tokio::spawn(async move {
         let mut lines = BufReader::new(stderr).lines();
@@ -577,20 +1192,21 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
             if line.trim().is_empty() {
                 continue;
             }
-            let payload = AppServerEvent {
-                workspace_id: workspace_id.clone(),
-                message: json!({
-                    "method": "codex/stderr",
-                    "params": { "message": line },
-                }),
-            };
-            event_sink_clone.emit_app_server_event(payload);
+            let message = json!({
+                "method": "codex/stderr",
+                "params": { "message": line },
+            });
+            emit_process_event(&session_clone, &event_sink_clone, message);
         }
     });
 
-    let init_params = build_initialize_params(&client_version);
+    stdout_task
+}
+
+async fn initialize_session(session: &Arc<WorkspaceSession>) -> Result<(), String> {
+    let init_params = build_initialize_params(&session.launch.client_version);
     let init_result = timeout(
-        Duration::from_secs(15),
+        APP_SERVER_INITIALIZE_TIMEOUT,
         session.send_request("initialize", init_params),
     )
     .await;
@@ -605,17 +1221,311 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
             );
         }
     };
-    init_response?;
-    session.send_notification("initialized", None).await?;
-
-    let payload = AppServerEvent {
-        workspace_id: entry.id.clone(),
-        message: json!({
-            "method": "codex/connected",
-            "params": { "workspaceId": entry.id.clone() }
-        }),
+    let init_response = init_response?;
+    session.set_capabilities(AppServerCapabilities::from_initialize_response(
+        &init_response,
+    ));
+    session.send_notification("initialized", None).await
+}
+
+fn emit_supervisor_event<E: EventSink>(
+    event_sink: &E,
+    workspace_id: &str,
+    method: &str,
+    params: Value,
+) {
+    let message = json!({
+        "method": method,
+        "params": params,
+    });
+    let seq = record_app_server_message(workspace_id, JournalDirection::Local, &message);
+    event_sink.emit_app_server_event(AppServerEvent {
+        workspace_id: workspace_id.to_string(),
+        message,
+        seq,
+    });
+}
+
+/// Emit a supervisor event to every workspace using the session's process,
+/// with `workspaceId` filled in per recipient.
+fn emit_session_event<E: EventSink>(
+    session: &WorkspaceSession,
+    event_sink: &E,
+    method: &str,
+    params: Value,
+) {
+    for workspace_id in session.workspace_ids() {
+        let mut params = params.clone();
+        if let Some(params) = params.as_object_mut() {
+            params.insert("workspaceId".to_string(), json!(workspace_id));
+        }
+        emit_supervisor_event(event_sink, &workspace_id, method, params);
+    }
+}
+
+/// Unjournaled process diagnostics (stderr, parse errors) for every
+/// workspace using the session's process.
+fn emit_process_event<E: EventSink>(session: &WorkspaceSession, event_sink: &E, message: Value) {
+    for workspace_id in session.workspace_ids() {
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id,
+            message: message.clone(),
+            seq: None,
+        });
+    }
+}
+
+/// Relaunch the app-server in place: the `Arc<WorkspaceSession>` held by the
+/// sessions map stays the same, only the child process and stdin are swapped.
+async fn restart_session<E: EventSink>(
+    session: &Arc<WorkspaceSession>,
+    event_sink: &E,
+) -> Result<(JoinHandle<()>, Vec<String>), String> {
+    let SpawnedAppServer {
+        child,
+        stdin,
+        stdout,
+        stderr,
+    } = launch_app_server(&session.launch_entry(), &session.launch)?;
+    {
+        let mut current_child = session.child.lock().await;
+        *current_child = child;
+        *session.stdin.lock().await = Some(stdin);
+        // Turns of the previous process died with it.
+        lock_std(&session.running_turns).clear();
+        if session.shutdown_requested.load(Ordering::SeqCst) {
+            kill_child_process_tree(&mut current_child).await;
+            return Err("session shut down during restart".to_string());
+        }
+    }
+    let stdout_task = spawn_app_server_readers(session, stdout, stderr, event_sink);
+    if let Err(error) = initialize_session(session).await {
+        {
+            let mut child = session.child.lock().await;
+            kill_child_process_tree(&mut child).await;
+        }
+        let _ = stdout_task.await;
+        return Err(error);
+    }
+
+    let thread_ids = {
+        let threads = session.active_threads.lock().await;
+        let mut ids = threads.iter().cloned().collect::<Vec<_>>();
+        ids.sort();
+        ids
     };
-    event_sink.emit_app_server_event(payload);
+    let mut resumed = Vec::new();
+    for thread_id in thread_ids {
+        match session
+            .send_request("thread/resume", json!({ "threadId": thread_id }))
+            .await
+        {
+            Ok(response) if response.get("error").is_none() => resumed.push(thread_id),
+            Ok(response) => {
+                eprintln!(
+                    "supervisor: failed to resume thread after restart: workspace_id={}, thread_id={thread_id}, error={}",
+                    session.entry.id,
+                    response.get("error").cloned().unwrap_or(Value::Null)
+                );
+                session.active_threads.lock().await.remove(&thread_id);
+            }
+            Err(error) => {
+                eprintln!(
+                    "supervisor: failed to resume thread after restart: workspace_id={}, thread_id={thread_id}, error={error}",
+                    session.entry.id
+                );
+                session.active_threads.lock().await.remove(&thread_id);
+            }
+        }
+    }
+    Ok((stdout_task, resumed))
+}
+
+async fn supervise_session<E: EventSink>(
+    session: Arc<WorkspaceSession>,
+    mut stdout_task: JoinHandle<()>,
+    event_sink: E,
+) {
+    let workspace_id = session.entry.id.clone();
+    let mut budget = CrashBudget::new(SUPERVISOR_CRASH_BUDGET, SUPERVISOR_CRASH_WINDOW);
+    loop {
+        let _ = (&mut stdout_task).await;
+
+        // Stdout reading loop exited — the codex app-server process has
+        // disconnected or crashed.  Notify the frontend so it can reset any
+        // processing state that would otherwise spin forever.
+        emit_session_event(&session, &event_sink, "codex/disconnected", json!({}));
+        {
+            let mut pending = session.pending.lock().await;
+            clear_pending_requests(&mut pending);
+        }
+        if session.shutdown_requested.load(Ordering::SeqCst) {
+            return;
+        }
+        {
+            // Reap the exited child so it does not linger as a zombie.
+            let mut child = session.child.lock().await;
+            let _ = child.try_wait();
+        }
+
+        let mut attempt: u32 = 0;
+        let restarted = loop {
+            attempt += 1;
+            if !budget.try_consume(Instant::now()) {
+                emit_session_event(
+                    &session,
+                    &event_sink,
+                    "codex/restartBudgetExhausted",
+                    json!({
+                        "maxRestarts": SUPERVISOR_CRASH_BUDGET,
+                        "windowSecs": SUPERVISOR_CRASH_WINDOW.as_secs(),
+                    }),
+                );
+                return;
+            }
+            let delay = restart_backoff(attempt);
+            emit_session_event(
+                &session,
+                &event_sink,
+                "codex/reconnecting",
+                json!({
+                    "attempt": attempt,
+                    "delayMs": delay.as_millis() as u64,
+                }),
+            );
+            sleep(delay).await;
+            if session.shutdown_requested.load(Ordering::SeqCst) {
+                return;
+            }
+            match restart_session(&session, &event_sink).await {
+                Ok(restarted) => break restarted,
+                Err(error) => {
+                    eprintln!(
+                        "supervisor: app-server restart failed: workspace_id={workspace_id}, attempt={attempt}, error={error}"
+                    );
+                    if session.shutdown_requested.load(Ordering::SeqCst) {
+                        return;
+                    }
+                }
+            }
+        };
+
+        let (next_stdout_task, resumed_thread_ids) = restarted;
+        stdout_task = next_stdout_task;
+        let restart_count = session.restart_count.fetch_add(1, Ordering::SeqCst) + 1;
+        emit_session_event(
+            &session,
+            &event_sink,
+            "codex/connected",
+            json!({
+                "restarted": true,
+                "restartCount": restart_count,
+                "resumedThreadIds": resumed_thread_ids,
+                "capabilities": session.capabilities().to_json(),
+            }),
+        );
+    }
+}
+
+pub(crate) async fn spawn_workspace_session<E: EventSink>(
+    entry: WorkspaceEntry,
+    default_codex_bin: Option<String>,
+    codex_args: Option<String>,
+    codex_home: Option<PathBuf>,
+    client_version: String,
+    event_sink: E,
+) -> Result<Arc<WorkspaceSession>, String> {
+    let codex_bin = entry
+        .codex_bin
+        .clone()
+        .filter(|value| !value.trim().is_empty())
+        .or(default_codex_bin);
+    let pool_key = APP_SERVER_POOLING.load(Ordering::SeqCst).then(|| {
+        app_server_pool_key(
+            codex_bin.as_deref(),
+            codex_args.as_deref(),
+            codex_home.as_deref(),
+        )
+    });
+    let spawn_lock = pool_key.as_deref().map(pool_spawn_lock);
+    let _spawn_guard = match &spawn_lock {
+        Some(lock) => Some(lock.lock().await),
+        None => None,
+    };
+    if let Some(key) = pool_key.as_deref() {
+        if let Some(session) = find_pooled_session(key).await {
+            session.attach_workspace(entry.clone());
+            emit_supervisor_event(
+                &event_sink,
+                &entry.id,
+                "codex/connected",
+                json!({
+                    "workspaceId": entry.id.clone(),
+                    "pooled": true,
+                    "capabilities": session.capabilities().to_json(),
+                }),
+            );
+            return Ok(session);
+        }
+    }
+    let _ = check_codex_installation(codex_bin.clone()).await?;
+
+    let launch = AppServerLaunchConfig {
+        codex_bin,
+        codex_args,
+        codex_home,
+        client_version,
+    };
+    let SpawnedAppServer {
+        child,
+        stdin,
+        stdout,
+        stderr,
+    } = launch_app_server(&entry, &launch)?;
+
+    let session = Arc::new(WorkspaceSession {
+        entry: entry.clone(),
+        launch,
+        child: Mutex::new(child),
+        stdin: Mutex::new(Some(stdin)),
+        pending: Mutex::new(HashMap::new()),
+        next_id: AtomicU64::new(1),
+        background_thread_callbacks: Mutex::new(HashMap::new()),
+        active_threads: Mutex::new(HashSet::new()),
+        shutdown_requested: AtomicBool::new(false),
+        restart_count: AtomicU32::new(0),
+        started_at: Instant::now(),
+        capabilities: StdRwLock::new(AppServerCapabilities::default()),
+        pool_key: StdMutex::new(None),
+        members: StdMutex::new(HashMap::from([(entry.id.clone(), entry.clone())])),
+        thread_routes: StdMutex::new(HashMap::new()),
+        running_turns: StdMutex::new(HashMap::new()),
+    });
+
+    let stdout_task = spawn_app_server_readers(&session, stdout, stderr, &event_sink);
+    if let Err(error) = initialize_session(&session).await {
+        session.terminate().await;
+        return Err(error);
+    }
+    if let Some(key) = pool_key {
+        register_pooled_session(key, &session);
+    }
+
+    emit_supervisor_event(
+        &event_sink,
+        &entry.id,
+        "codex/connected",
+        json!({
+            "workspaceId": entry.id.clone(),
+            "capabilities": session.capabilities().to_json(),
+        }),
+    );
+
+    tokio::spawn(supervise_session(
+        Arc::clone(&session),
+        stdout_task,
+        event_sink,
+    ));
 
     Ok(session)
 }
@@ -623,16 +1533,55 @@ pub(crate) async fn spawn_workspace_session<E: EventSink>(
 #[cfg(test)]
 mod tests {
     use super::{
-        build_initialize_params, clear_pending_requests, extract_thread_id,
-        BackgroundCallbackDispatch,
+        app_server_pool_key, build_initialize_params, clear_pending_requests,
+        extract_response_thread_id, extract_thread_id, restart_backoff, route_pooled_message,
+        AppServerLaunchConfig, BackgroundCallbackDispatch, CrashBudget, RequestTimeoutPolicy,
+        WorkspaceSession, APP_SERVER_REQUEST_TIMEOUT,
     };
+    use crate::backend::background_callbacks::background_thread_channel;
+    use crate::types::{AppSettings, WorkspaceEntry, WorkspaceKind, WorkspaceSettings};
     use serde_json::json;
-    use std::collections::HashMap;
+    use std::collections::{HashMap, HashSet};
+    use std::sync::atomic::AtomicU64;
+    use std::sync::atomic::{AtomicBool, AtomicU32};
     use std::sync::Arc;
-    use tokio::process::Command;
-    use tokio::sync::{mpsc, Mutex};
+    use std::time::Duration;
+    use tokio::process::{Child, Command};
     use tokio::sync::oneshot;
-    use std::sync::atomic::AtomicU64;
+    use tokio::sync::Mutex;
+    use tokio::time::Instant;
+
+    /// Session wrapping `child` with no pending requests, routes or turns.
+    fn test_session(mut child: Child) -> Arc<WorkspaceSession> {
+        let stdin = child.stdin.take().expect("missing stdin");
+        Arc::new(WorkspaceSession {
+            entry: WorkspaceEntry {
+                id: "ws-1".to_string(),
+                name: "Workspace".to_string(),
+                path: ".".to_string(),
+                codex_bin: None,
+                kind: WorkspaceKind::Main,
+                parent_id: None,
+                worktree: None,
+                settings: WorkspaceSettings::default(),
+            },
+            launch: AppServerLaunchConfig::default(),
+            child: Mutex::new(child),
+            stdin: Mutex::new(Some(stdin)),
+            pending: Mutex::new(HashMap::new()),
+            next_id: AtomicU64::new(1),
+            background_thread_callbacks: Mutex::new(HashMap::new()),
+            active_threads: Mutex::new(HashSet::new()),
+            shutdown_requested: AtomicBool::new(false),
+            restart_count: AtomicU32::new(0),
+            started_at: Instant::now(),
+            capabilities: std::sync::RwLock::new(Default::default()),
+            pool_key: std::sync::Mutex::new(None),
+            members: std::sync::Mutex::new(HashMap::new()),
+            thread_routes: std::sync::Mutex::new(HashMap::new()),
+            running_turns: std::sync::Mutex::new(HashMap::new()),
+        })
+    }
 
     #[test]
     fn extract_thread_id_reads_camel_case() {
@@ -664,6 +1613,134 @@ mod tests {
         );
     }
 
+    #[test]
+    fn extract_response_thread_id_reads_nested_thread() {
+        let value = json!({ "id": 4, "result": { "thread": { "id": "thread-789" } } });
+        assert_eq!(
+            extract_response_thread_id(&value),
+            Some("thread-789".to_string())
+        );
+    }
+
+    fn pool_member(id: &str, path: &str) -> (String, crate::types::WorkspaceEntry) {
+        let entry = crate::types::WorkspaceEntry {
+            id: id.to_string(),
+            name: id.to_string(),
+            path: path.to_string(),
+            codex_bin: None,
+            kind: crate::types::WorkspaceKind::Main,
+            parent_id: None,
+            worktree: None,
+            settings: crate::types::WorkspaceSettings::default(),
+        };
+        (id.to_string(), entry)
+    }
+
+    #[test]
+    fn pool_key_matches_only_identical_launches() {
+        let home = std::path::Path::new("/tmp/codex-home");
+        let key = app_server_pool_key(None, Some(" --verbose "), Some(home));
+        assert_eq!(
+            key,
+            app_server_pool_key(Some("codex"), Some("--verbose"), Some(home))
+        );
+        assert_ne!(key, app_server_pool_key(None, Some("--verbose"), None));
+        assert_ne!(
+            key,
+            app_server_pool_key(Some("/opt/codex"), Some("--verbose"), Some(home))
+        );
+    }
+
+    #[test]
+    fn pool_spawn_lock_is_shared_per_key() {
+        let first = super::pool_spawn_lock("spawn-lock-a");
+        let second = super::pool_spawn_lock("spawn-lock-a");
+        assert!(Arc::ptr_eq(&first, &second));
+        assert!(!Arc::ptr_eq(
+            &first,
+            &super::pool_spawn_lock("spawn-lock-b")
+        ));
+    }
+
+    #[test]
+    fn pooled_messages_route_by_thread_and_broadcast_account_events() {
+        let members = HashMap::from([
+            pool_member("ws-a", "/repo/a"),
+            pool_member("ws-b", "/repo/b/"),
+        ]);
+        let mut routes = HashMap::new();
+
+        let started = json!({
+            "method": "thread/started",
+            "params": { "thread": { "id": "t-1", "cwd": "/repo/b" } }
+        });
+        assert_eq!(
+            route_pooled_message(&members, &mut routes, "ws-a", Some("t-1"), &started),
+            vec!["ws-b".to_string()]
+        );
+        let delta = json!({ "method": "item/agentMessage/delta", "params": { "threadId": "t-1" } });
+        assert_eq!(
+            route_pooled_message(&members, &mut routes, "ws-a", Some("t-1"), &delta),
+            vec!["ws-b".to_string()]
+        );
+
+        // Unknown threads fall back to the workspace that launched the process.
+        assert_eq!(
+            route_pooled_message(&members, &mut routes, "ws-a", Some("t-2"), &delta),
+            vec!["ws-a".to_string()]
+        );
+
+        let account = json!({ "method": "account/updated", "params": {} });
+        assert_eq!(
+            route_pooled_message(&members, &mut routes, "ws-a", None, &account),
+            vec!["ws-a".to_string(), "ws-b".to_string()]
+        );
+    }
+
+    #[test]
+    fn restart_backoff_doubles_and_caps() {
+        assert_eq!(restart_backoff(1), Duration::from_secs(1));
+        assert_eq!(restart_backoff(2), Duration::from_secs(2));
+        assert_eq!(restart_backoff(4), Duration::from_secs(8));
+        assert_eq!(restart_backoff(30), Duration::from_secs(60));
+    }
+
+    #[test]
+    fn request_timeout_policy_uses_builtin_method_defaults() {
+        let policy = RequestTimeoutPolicy::default();
+        assert_eq!(policy.timeout_for("thread/list"), Duration::from_secs(120));
+        assert_eq!(policy.timeout_for("model/list"), Duration::from_secs(10));
+        assert_eq!(policy.timeout_for("turn/start"), APP_SERVER_REQUEST_TIMEOUT);
+    }
+
+    #[test]
+    fn request_timeout_policy_applies_settings_overrides() {
+        let mut settings = AppSettings {
+            app_server_request_timeout_secs: Some(45),
+            ..AppSettings::default()
+        };
+        settings
+            .app_server_method_timeout_secs
+            .insert("thread/list".to_string(), 300);
+        settings
+            .app_server_method_timeout_secs
+            .insert("model/list".to_string(), 0);
+        let policy = RequestTimeoutPolicy::from_settings(&settings);
+        assert_eq!(policy.timeout_for("thread/list"), Duration::from_secs(300));
+        assert_eq!(policy.timeout_for("model/list"), Duration::from_secs(10));
+        assert_eq!(policy.timeout_for("turn/start"), Duration::from_secs(45));
+    }
+
+    #[test]
+    fn crash_budget_refuses_after_limit_and_recovers_after_window() {
+        let mut budget = CrashBudget::new(2, Duration::from_secs(60));
+        let start = Instant::now();
+        assert!(budget.try_consume(start));
+        assert!(budget.try_consume(start + Duration::from_secs(1)));
+        assert!(!budget.try_consume(start + Duration::from_secs(2)));
+        assert!(budget.try_consume(start + Duration::from_secs(62)));
+    }
+
     #[test]
     fn clear_pending_requests_drops_waiters() {
         let (tx, mut rx) = oneshot::channel();
@@ -680,10 +1757,43 @@ mod tests {
     }
 
     #[test]
-    fn try_send_background_callback_returns_queue_full_instead_of_silent_drop() {
+    fn timed_out_request_is_removed_from_pending() {
         let runtime = tokio::runtime::Runtime::new().expect("runtime should initialize");
         runtime.block_on(async {
-            let mut child = Command::new("sh")
+            let child = Command::new("sh")
+                .arg("-c")
+                .arg("sleep 5")
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .stderr(std::process::Stdio::piped())
+                .spawn()
+                .expect("must spawn child process");
+            let session = test_session(child);
+
+            let mut handle = session
+                .begin_request("model/list", json!({}))
+                .await
+                .expect("request should be written");
+            assert_eq!(session.pending.lock().await.len(), 1);
+            handle.timeout = Duration::from_millis(20);
+
+            let error = session
+                .wait_request(handle)
+                .await
+                .expect_err("request must time out");
+            assert!(error.contains("model/list request timed out"), "{error}");
+            assert!(session.pending.lock().await.is_empty());
+
+            let mut child = session.child.lock().await;
+            crate::shared::process_core::kill_child_process_tree(&mut child).await;
+        });
+    }
+
+    #[test]
+    fn try_send_background_callback_buffers_bursts_and_reports_dropped_receivers() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime should initialize");
+        runtime.block_on(async {
+            let child = Command::new("sh")
                 .arg("-c")
                 .arg("sleep 5")
                 .stdin(std::process::Stdio::piped())
@@ -691,46 +1801,147 @@ mod tests {
                 .stderr(std::process::Stdio::piped())
                 .spawn()
                 .expect("must spawn child process");
-            let stdin = child.stdin.take().expect("missing stdin");
-            let (tx, mut rx) = mpsc::channel::<serde_json::Value>(1);
+            let (tx, mut rx) = background_thread_channel();
             tx.send(json!({"first": true}))
+                .expect("must queue first event");
+            let session = test_session(child);
+            session
+                .background_thread_callbacks
+                .lock()
                 .await
-                .expect("must fill callback channel");
-            let mut callbacks = HashMap::new();
-            callbacks.insert("thread-1".to_string(), tx);
-
-            let session = Arc::new(crate::backend::app_server::WorkspaceSession {
-                entry: crate::types::WorkspaceEntry {
-                    id: "ws-1".to_string(),
-                    name: "Workspace".to_string(),
-                    path: ".".to_string(),
-                    codex_bin: None,
-                    kind: crate::types::WorkspaceKind::Main,
-                    parent_id: None,
-                    worktree: None,
-                    settings: crate::types::WorkspaceSettings::default(),
-                },
-                child: Mutex::new(child),
-                stdin: Mutex::new(stdin),
-                pending: Mutex::new(HashMap::new()),
-                next_id: AtomicU64::new(1),
-                background_thread_callbacks: Mutex::new(callbacks),
-            });
+                .insert("thread-1".to_string(), tx);
+
+            // Far more events than the old fixed-size channel held.
+            for _ in 0..512 {
+                let dispatch = super::try_send_background_callback(
+                    &session,
+                    "ws-1",
+                    "thread-1",
+                    json!({
+                        "method": "item/agentMessage/delta",
+                        "params": { "threadId": "thread-1", "itemId": "item-1", "delta": "x" }
+                    }),
+                )
+                .await;
+                assert_eq!(dispatch, BackgroundCallbackDispatch::Sent);
+            }
+            let first = rx.recv().await.expect("first event must remain queued");
+            assert_eq!(first.get("first").and_then(|v| v.as_bool()), Some(true));
+            let merged = rx.recv().await.expect("deltas must be coalesced");
+            assert_eq!(merged["params"]["delta"].as_str().map(str::len), Some(512));
 
+            drop(rx);
             let dispatch = super::try_send_background_callback(
                 &session,
                 "ws-1",
                 "thread-1",
-                json!({"second": true}),
+                json!({"late": true}),
             )
             .await;
-
-            assert_eq!(dispatch, BackgroundCallbackDispatch::QueueFull);
-            let first = rx.recv().await.expect("first event must remain queued");
-            assert_eq!(first.get("first").and_then(|v| v.as_bool()), Some(true));
+            assert_eq!(dispatch, BackgroundCallbackDispatch::Closed);
+            // The entry stays so later events for the thread are still hidden.
+            assert!(session
+                .background_thread_callbacks
+                .lock()
+                .await
+                .contains_key("thread-1"));
 
             let mut child = session.child.lock().await;
             crate::shared::process_core::kill_child_process_tree(&mut child).await;
         });
     }
+
+    #[test]
+    fn graceful_shutdown_interrupts_turns_and_lets_the_process_exit() {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        let runtime = tokio::runtime::Runtime::new().expect("runtime should initialize");
+        runtime.block_on(async {
+            // `cat` echoes requests back and exits cleanly once stdin closes.
+            let mut child = Command::new("cat")
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .spawn()
+                .expect("must spawn child process");
+            let stdout = child.stdout.take().expect("missing stdout");
+            let session = test_session(child);
+            session.track_turn_notification(&json!({
+                "method": "turn/started",
+                "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+            }));
+            assert_eq!(session.running_turns.lock().unwrap().len(), 1);
+
+            let completer = Arc::clone(&session);
+            let reader = tokio::spawn(async move {
+                let mut lines = BufReader::new(stdout).lines();
+                let line = lines.next_line().await.expect("read").expect("line");
+                completer.track_turn_notification(&json!({
+                    "method": "turn/completed",
+                    "params": { "threadId": "thread-1", "turn": { "id": "turn-1" } }
+                }));
+                serde_json::from_str::<serde_json::Value>(&line).expect("json")
+            });
+
+            let started = Instant::now();
+            session
+                .shutdown_with_timeouts(Duration::from_secs(5), Duration::from_secs(5))
+                .await;
+            assert!(started.elapsed() < Duration::from_secs(5));
+
+            let interrupt = reader.await.expect("reader");
+            assert_eq!(interrupt["method"], "turn/interrupt");
+            assert_eq!(interrupt["params"]["threadId"], "thread-1");
+            assert_eq!(interrupt["params"]["turnId"], "turn-1");
+            assert!(session.running_turns.lock().unwrap().is_empty());
+            assert!(session.pending.lock().await.is_empty());
+            let status = session.child.lock().await.try_wait().expect("status");
+            assert!(status.is_some_and(|status| status.success()));
+            assert!(session.write_message(json!({})).await.is_err());
+        });
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn graceful_shutdown_kills_descendants_left_after_a_clean_exit() {
+        use tokio::io::{AsyncBufReadExt, BufReader};
+
+        let runtime = tokio::runtime::Runtime::new().expect("runtime should initialize");
+        runtime.block_on(async {
+            // The shell exits cleanly once stdin closes but leaves `sleep` behind.
+            let mut child = Command::new("sh")
+                .arg("-c")
+                .arg("sleep 30 & echo $!; cat")
+                .stdin(std::process::Stdio::piped())
+                .stdout(std::process::Stdio::piped())
+                .spawn()
+                .expect("must spawn child process");
+            let stdout = child.stdout.take().expect("missing stdout");
+            let mut lines = BufReader::new(stdout).lines();
+            let sleep_pid: u32 = lines
+                .next_line()
+                .await
+                .expect("read")
+                .expect("line")
+                .parse()
+                .expect("pid");
+            let session = test_session(child);
+
+            session
+                .shutdown_with_timeouts(Duration::from_secs(1), Duration::from_secs(5))
+                .await;
+
+            let status = session.child.lock().await.try_wait().expect("status");
+            assert!(status.is_some_and(|status| status.success()));
+            // A killed orphan may linger as a zombie until init reaps it.
+            let deadline = Instant::now() + Duration::from_secs(5);
+            let is_running = || {
+                std::fs::read_to_string(format!("/proc/{sleep_pid}/stat"))
+                    .is_ok_and(|stat| !stat.contains(") Z "))
+            };
+            while is_running() && Instant::now() < deadline {
+                tokio::time::sleep(Duration::from_millis(20)).await;
+            }
+            assert!(!is_running(), "sleep {sleep_pid} must be killed");
+        });
+    }
 }
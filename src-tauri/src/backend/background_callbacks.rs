@@ -0,0 +1,330 @@
+//! Event buffers for hidden background threads (commit messages, run
+//! metadata). The app-server reader must never block on, or drop events for,
+//! a background thread, and those events must never reach the frontend. The
+//! buffer therefore accepts every event, keeping memory bounded by merging
+//! consecutive `item/agentMessage/delta` text and, once full, dropping only
+//! events the collector does not read. A second, hard limit caps the events
+//! the collector does read, so a stalled collector cannot grow it further.
+
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+
+use tokio::sync::Notify;
+
+use crate::backend::protocol::is_thread_boundary_method;
+
+/// Queued events per background thread before non-essential events are
+/// dropped. Deltas and turn boundaries are kept until the essential cap.
+pub(crate) const BACKGROUND_THREAD_EVENT_CAP: usize = 1024;
+
+/// Queued events per background thread, essential ones included. Past it a
+/// delta is merged into the last queued delta, whatever its item, and any
+/// other event is dropped. The collector concatenates every delta, so no
+/// collected text is lost.
+const BACKGROUND_THREAD_ESSENTIAL_CAP: usize = 1280;
+
+const AGENT_MESSAGE_DELTA_METHOD: &str = "item/agentMessage/delta";
+
+static EVENTS_QUEUED: AtomicU64 = AtomicU64::new(0);
+static DELTAS_COALESCED: AtomicU64 = AtomicU64::new(0);
+static EVENTS_DROPPED: AtomicU64 = AtomicU64::new(0);
+static PEAK_QUEUE_DEPTH: AtomicU64 = AtomicU64::new(0);
+
+/// Process-wide counters for background-thread buffering.
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct BackgroundCallbackMetrics {
+    pub(crate) events_queued: u64,
+    /// Deltas merged into an already queued delta instead of being queued.
+    pub(crate) deltas_coalesced: u64,
+    /// Events dropped because a buffer was at capacity.
+    pub(crate) events_dropped: u64,
+    pub(crate) peak_queue_depth: u64,
+}
+
+pub(crate) fn background_callback_metrics() -> BackgroundCallbackMetrics {
+    BackgroundCallbackMetrics {
+        events_queued: EVENTS_QUEUED.load(Ordering::Relaxed),
+        deltas_coalesced: DELTAS_COALESCED.load(Ordering::Relaxed),
+        events_dropped: EVENTS_DROPPED.load(Ordering::Relaxed),
+        peak_queue_depth: PEAK_QUEUE_DEPTH.load(Ordering::Relaxed),
+    }
+}
+
+#[derive(Default)]
+struct BufferState {
+    events: VecDeque<Value>,
+    receiver_closed: bool,
+    sender_closed: bool,
+}
+
+struct Shared {
+    state: StdMutex<BufferState>,
+    notify: Notify,
+    cap: usize,
+    essential_cap: usize,
+    senders: AtomicUsize,
+}
+
+impl Shared {
+    fn lock(&self) -> std::sync::MutexGuard<'_, BufferState> {
+        match self.state.lock() {
+            Ok(guard) => guard,
+            Err(poisoned) => poisoned.into_inner(),
+        }
+    }
+}
+
+/// Held by the session's callback map; sending never blocks or fails while
+/// the receiver is alive.
+pub(crate) struct BackgroundThreadSender {
+    shared: Arc<Shared>,
+}
+
+/// Held by the task collecting the background thread's output.
+pub(crate) struct BackgroundThreadReceiver {
+    shared: Arc<Shared>,
+}
+
+pub(crate) fn background_thread_channel() -> (BackgroundThreadSender, BackgroundThreadReceiver) {
+    background_thread_channel_with_caps(
+        BACKGROUND_THREAD_EVENT_CAP,
+        BACKGROUND_THREAD_ESSENTIAL_CAP,
+    )
+}
+
+fn background_thread_channel_with_caps(
+    cap: usize,
+    essential_cap: usize,
+) -> (BackgroundThreadSender, BackgroundThreadReceiver) {
+    let shared = Arc::new(Shared {
+        state: StdMutex::new(BufferState::default()),
+        notify: Notify::new(),
+        cap: cap.max(1),
+        essential_cap: essential_cap.max(cap).max(1),
+        senders: AtomicUsize::new(1),
+    });
+    (
+        BackgroundThreadSender {
+            shared: Arc::clone(&shared),
+        },
+        BackgroundThreadReceiver { shared },
+    )
+}
+
+impl BackgroundThreadSender {
+    /// Queue `value`, handing it back when the receiver has gone away.
+    pub(crate) fn send(&self, value: Value) -> Result<(), Value> {
+        let mut state = self.shared.lock();
+        if state.receiver_closed {
+            return Err(value);
+        }
+        let at_essential_cap = state.events.len() >= self.shared.essential_cap;
+        if coalesce_delta(&mut state.events, &value, at_essential_cap) {
+            DELTAS_COALESCED.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
+        if at_essential_cap || (state.events.len() >= self.shared.cap && !is_essential(&value)) {
+            EVENTS_DROPPED.fetch_add(1, Ordering::Relaxed);
+            return Ok(());
+        }
+        state.events.push_back(value);
+        EVENTS_QUEUED.fetch_add(1, Ordering::Relaxed);
+        PEAK_QUEUE_DEPTH.fetch_max(state.events.len() as u64, Ordering::Relaxed);
+        drop(state);
+        self.shared.notify.notify_one();
+        Ok(())
+    }
+}
+
+impl Clone for BackgroundThreadSender {
+    fn clone(&self) -> Self {
+        self.shared.senders.fetch_add(1, Ordering::SeqCst);
+        Self {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl Drop for BackgroundThreadSender {
+    fn drop(&mut self) {
+        if self.shared.senders.fetch_sub(1, Ordering::SeqCst) == 1 {
+            self.shared.lock().sender_closed = true;
+            self.shared.notify.notify_one();
+        }
+    }
+}
+
+impl BackgroundThreadReceiver {
+    /// Next queued event, or `None` once every sender is gone and the
+    /// buffer is drained.
+    pub(crate) async fn recv(&mut self) -> Option<Value> {
+        loop {
+            {
+                let mut state = self.shared.lock();
+                if let Some(value) = state.events.pop_front() {
+                    return Some(value);
+                }
+                if state.sender_closed {
+                    return None;
+                }
+            }
+            self.shared.notify.notified().await;
+        }
+    }
+}
+
+impl Drop for BackgroundThreadReceiver {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock();
+        state.receiver_closed = true;
+        state.events.clear();
+    }
+}
+
+fn method_of(value: &Value) -> Option<&str> {
+    value.get("method").and_then(Value::as_str)
+}
+
+/// Deltas carry the collected text and boundaries end the collection; both
+/// are kept regardless of capacity.
+fn is_essential(value: &Value) -> bool {
+    method_of(value).is_some_and(|method| {
+        method == AGENT_MESSAGE_DELTA_METHOD || is_thread_boundary_method(method)
+    })
+}
+
+/// Append `value`'s delta text to the queued delta it directly follows when
+/// both belong to the same item. With `any_item`, the last queued delta takes
+/// it wherever it sits and whatever its item.
+fn coalesce_delta(events: &mut VecDeque<Value>, value: &Value, any_item: bool) -> bool {
+    if method_of(value) != Some(AGENT_MESSAGE_DELTA_METHOD) {
+        return false;
+    }
+    let last = if any_item {
+        events
+            .iter_mut()
+            .rev()
+            .find(|event| method_of(event) == Some(AGENT_MESSAGE_DELTA_METHOD))
+    } else {
+        events
+            .back_mut()
+            .filter(|event| method_of(event) == Some(AGENT_MESSAGE_DELTA_METHOD))
+    };
+    let Some(last) = last else {
+        return false;
+    };
+    let item_id = |value: &Value| {
+        value
+            .get("params")
+            .and_then(|params| params.get("itemId").or_else(|| params.get("item_id")))
+            .cloned()
+    };
+    if !any_item && item_id(last) != item_id(value) {
+        return false;
+    }
+    let Some(delta) = value
+        .get("params")
+        .and_then(|params| params.get("delta"))
+        .and_then(Value::as_str)
+    else {
+        return false;
+    };
+    match last
+        .get_mut("params")
+        .and_then(|params| params.get_mut("delta"))
+    {
+        Some(Value::String(existing)) => {
+            existing.push_str(delta);
+            true
+        }
+        _ => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{background_thread_channel, background_thread_channel_with_caps};
+    use serde_json::json;
+
+    fn delta(item_id: &str, text: &str) -> serde_json::Value {
+        json!({
+            "method": "item/agentMessage/delta",
+            "params": { "threadId": "t-1", "itemId": item_id, "delta": text }
+        })
+    }
+
+    #[test]
+    fn deltas_coalesce_per_item_and_boundaries_survive_a_full_buffer() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime should initialize");
+        runtime.block_on(async {
+            let (tx, mut rx) = background_thread_channel_with_caps(2, 8);
+            tx.send(json!({ "method": "item/started", "params": { "threadId": "t-1" } }))
+                .expect("send");
+            for _ in 0..500 {
+                tx.send(delta("i-1", "ab")).expect("send");
+            }
+            tx.send(json!({ "method": "item/completed", "params": { "threadId": "t-1" } }))
+                .expect("send");
+            tx.send(delta("i-2", "c")).expect("send");
+            tx.send(json!({ "method": "turn/completed", "params": { "threadId": "t-1" } }))
+                .expect("send");
+            drop(tx);
+
+            let mut methods = Vec::new();
+            let mut text = String::new();
+            while let Some(event) = rx.recv().await {
+                if let Some(delta) = event["params"]["delta"].as_str() {
+                    text.push_str(delta);
+                }
+                methods.push(event["method"].as_str().unwrap_or_default().to_string());
+            }
+            assert_eq!(text.len(), 1001);
+            assert!(text.ends_with("abc"));
+            assert_eq!(
+                methods,
+                vec![
+                    "item/started",
+                    "item/agentMessage/delta",
+                    "item/agentMessage/delta",
+                    "turn/completed",
+                ]
+            );
+        });
+    }
+
+    #[test]
+    fn essential_cap_bounds_deltas_and_boundaries_without_losing_text() {
+        let runtime = tokio::runtime::Runtime::new().expect("runtime should initialize");
+        runtime.block_on(async {
+            let (tx, mut rx) = background_thread_channel_with_caps(2, 4);
+            for index in 0..100 {
+                tx.send(delta(&format!("i-{index}"), "x")).expect("send");
+                tx.send(json!({ "method": "turn/started", "params": { "threadId": "t-1" } }))
+                    .expect("send");
+            }
+            drop(tx);
+
+            let mut queued = 0;
+            let mut text = String::new();
+            while let Some(event) = rx.recv().await {
+                queued += 1;
+                if let Some(delta) = event["params"]["delta"].as_str() {
+                    text.push_str(delta);
+                }
+            }
+            assert_eq!(queued, 4);
+            assert_eq!(text.len(), 100);
+        });
+    }
+
+    #[test]
+    fn send_fails_after_receiver_is_dropped() {
+        let (tx, rx) = background_thread_channel();
+        drop(rx);
+        assert!(tx.send(delta("i-1", "x")).is_err());
+    }
+}
@@ -5,6 +5,9 @@ use serde_json::Value;
 pub(crate) struct AppServerEvent {
     pub(crate) workspace_id: String,
     pub(crate) message: Value,
+    /// Event journal sequence number, when the message was journaled.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) seq: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Clone)]
@@ -24,8 +27,17 @@ pub(crate) struct TerminalExit {
     pub(crate) terminal_id: String,
 }
 
+/// Fresh `get_git_status` payload for a workspace whose files changed.
+#[derive(Debug, Serialize, Clone)]
+pub(crate) struct GitStatusChanged {
+    #[serde(rename = "workspaceId")]
+    pub(crate) workspace_id: String,
+    pub(crate) status: Value,
+}
+
 pub(crate) trait EventSink: Clone + Send + Sync + 'static {
     fn emit_app_server_event(&self, event: AppServerEvent);
     fn emit_terminal_output(&self, event: TerminalOutput);
     fn emit_terminal_exit(&self, event: TerminalExit);
+    fn emit_git_status_changed(&self, event: GitStatusChanged);
 }
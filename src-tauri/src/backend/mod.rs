@@ -1,2 +1,4 @@
 pub(crate) mod app_server;
+pub(crate) mod background_callbacks;
 pub(crate) mod events;
+pub(crate) mod protocol;
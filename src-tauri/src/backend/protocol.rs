@@ -0,0 +1,689 @@
+//! Typed model of the Codex app-server JSON-RPC protocol.
+//!
+//! Deserialization is deliberately lenient: unknown fields are ignored,
+//! snake_case spellings are accepted next to camelCase, and unrecognised
+//! methods fall through to `Unknown` variants so a newer app-server degrades
+//! gracefully instead of breaking the monitor.
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Map, Value};
+use std::collections::HashSet;
+
+pub(crate) use crate::shared::thread_id_core::thread_id_from_message;
+use crate::shared::thread_id_core::thread_id_from_value;
+
+// ---------------------------------------------------------------------------
+// Shared payload types
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadInfo {
+    pub(crate) id: String,
+    #[serde(default)]
+    pub(crate) name: Option<String>,
+    #[serde(default)]
+    pub(crate) cwd: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnInfo {
+    #[serde(default)]
+    pub(crate) id: Option<String>,
+    #[serde(default)]
+    pub(crate) status: Option<String>,
+    #[serde(default)]
+    pub(crate) error: Option<Value>,
+}
+
+/// Thread id carried by a response, with or without the `result` envelope.
+pub(crate) fn thread_id_from_response(response: &Value) -> Option<String> {
+    response
+        .get("result")
+        .and_then(thread_id_from_value)
+        .or_else(|| thread_id_from_value(response))
+}
+
+/// `error` may be a bare string or a `{ message }` object depending on version.
+pub(crate) fn error_message(error: &Value) -> Option<String> {
+    match error {
+        Value::String(message) => Some(message.clone()),
+        Value::Object(map) => map
+            .get("message")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        _ => None,
+    }
+}
+
+/// The JSON-RPC error carried by a response, if any.
+pub(crate) fn response_error(response: &Value) -> Option<String> {
+    let error = response.get("error")?;
+    Some(error_message(error).unwrap_or_else(|| error.to_string()))
+}
+
+/// Deserialize a response's `result` (or the bare response) into `T`,
+/// surfacing JSON-RPC errors as `Err`.
+pub(crate) fn parse_response<T: DeserializeOwned>(response: &Value) -> Result<T, String> {
+    if let Some(error) = response_error(response) {
+        return Err(error);
+    }
+    let result = response.get("result").unwrap_or(response);
+    T::deserialize(result).map_err(|err| format!("unexpected app-server response: {err}"))
+}
+
+pub(crate) fn is_method_not_found_error(message: &str) -> bool {
+    let normalized = message.to_lowercase();
+    normalized.contains("method not found") || normalized.contains("unknown method")
+}
+
+// ---------------------------------------------------------------------------
+// Client -> server requests
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub(crate) struct EmptyParams {}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadIdParams {
+    pub(crate) thread_id: String,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadStartParams {
+    pub(crate) cwd: String,
+    /// `approvalPolicy` / `sandboxPolicy`, resolved by the caller.
+    #[serde(flatten)]
+    pub(crate) execution_policy: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ThreadListParams {
+    pub(crate) cursor: Option<String>,
+    pub(crate) limit: Option<u32>,
+    pub(crate) sort_key: Option<String>,
+    pub(crate) cwd: Option<String>,
+    pub(crate) source_kinds: Vec<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnStartParams {
+    pub(crate) thread_id: String,
+    pub(crate) input: Vec<Value>,
+    pub(crate) cwd: String,
+    pub(crate) model: Option<String>,
+    pub(crate) effort: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub(crate) collaboration_mode: Option<Value>,
+    /// `approvalPolicy` / `sandboxPolicy`, resolved by the caller.
+    #[serde(flatten)]
+    pub(crate) execution_policy: Map<String, Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnSteerParams {
+    pub(crate) thread_id: String,
+    pub(crate) expected_turn_id: String,
+    pub(crate) input: Vec<Value>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct TurnInterruptParams {
+    pub(crate) thread_id: String,
+    pub(crate) turn_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub(crate) struct LoginStartParams {
+    #[serde(rename = "type")]
+    pub(crate) kind: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LoginCancelParams {
+    pub(crate) login_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "method", content = "params")]
+pub(crate) enum ClientRequest {
+    #[serde(rename = "thread/start")]
+    ThreadStart(ThreadStartParams),
+    #[serde(rename = "thread/resume")]
+    ThreadResume(ThreadIdParams),
+    #[serde(rename = "thread/read")]
+    ThreadRead(ThreadIdParams),
+    #[serde(rename = "thread/fork")]
+    ThreadFork(ThreadIdParams),
+    #[serde(rename = "thread/archive")]
+    ThreadArchive(ThreadIdParams),
+    #[serde(rename = "thread/compact/start")]
+    ThreadCompactStart(ThreadIdParams),
+    #[serde(rename = "thread/list")]
+    ThreadList(ThreadListParams),
+    #[serde(rename = "turn/start")]
+    TurnStart(TurnStartParams),
+    #[serde(rename = "turn/steer")]
+    TurnSteer(TurnSteerParams),
+    #[serde(rename = "turn/interrupt")]
+    TurnInterrupt(TurnInterruptParams),
+    #[serde(rename = "account/login/start")]
+    AccountLoginStart(LoginStartParams),
+    #[serde(rename = "account/login/cancel")]
+    AccountLoginCancel(LoginCancelParams),
+    #[serde(rename = "model/list")]
+    ModelList(EmptyParams),
+}
+
+impl ClientRequest {
+    /// Split into the `(method, params)` pair `WorkspaceSession` sends.
+    pub(crate) fn into_parts(self) -> Result<(String, Value), String> {
+        let mut value = serde_json::to_value(self).map_err(|err| err.to_string())?;
+        let method = value
+            .get("method")
+            .and_then(Value::as_str)
+            .map(str::to_string)
+            .ok_or_else(|| "typed request is missing a method".to_string())?;
+        let params = value
+            .get_mut("params")
+            .map(Value::take)
+            .unwrap_or_else(|| json!({}));
+        Ok((method, params))
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Responses
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct LoginStartResponse {
+    #[serde(alias = "login_id")]
+    pub(crate) login_id: String,
+    #[serde(alias = "auth_url")]
+    pub(crate) auth_url: String,
+}
+
+// ---------------------------------------------------------------------------
+// Server -> client notifications
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Deserialize)]
+struct ThreadStartedParams {
+    thread: ThreadInfo,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadScopedParams {
+    #[serde(alias = "thread_id")]
+    thread_id: String,
+    #[serde(default)]
+    status: Option<Value>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TurnParams {
+    #[serde(alias = "thread_id")]
+    thread_id: String,
+    #[serde(default)]
+    turn: TurnInfo,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ItemParams {
+    #[serde(alias = "thread_id")]
+    thread_id: String,
+    #[serde(default, alias = "turn_id")]
+    turn_id: Option<String>,
+    #[serde(default)]
+    item: Value,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DeltaParams {
+    #[serde(alias = "thread_id")]
+    thread_id: String,
+    #[serde(default, alias = "item_id")]
+    item_id: Option<String>,
+    #[serde(default)]
+    delta: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ErrorParams {
+    #[serde(default, alias = "thread_id")]
+    thread_id: Option<String>,
+    #[serde(default)]
+    error: Option<Value>,
+    #[serde(default, alias = "will_retry")]
+    will_retry: bool,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct LoginCompletedParams {
+    #[serde(default, alias = "login_id")]
+    login_id: Option<String>,
+    #[serde(default)]
+    success: bool,
+    #[serde(default)]
+    error: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum ServerNotification {
+    ThreadStarted {
+        thread: ThreadInfo,
+    },
+    ThreadStatusChanged {
+        thread_id: String,
+        status: Option<Value>,
+    },
+    ThreadArchived {
+        thread_id: String,
+    },
+    TurnStarted {
+        thread_id: String,
+        turn: TurnInfo,
+    },
+    TurnCompleted {
+        thread_id: String,
+        turn: TurnInfo,
+    },
+    TurnError {
+        thread_id: Option<String>,
+        message: Option<String>,
+        /// The server is retrying; the turn has not ended.
+        will_retry: bool,
+    },
+    ItemStarted {
+        thread_id: String,
+        turn_id: Option<String>,
+        item: Value,
+    },
+    ItemCompleted {
+        thread_id: String,
+        turn_id: Option<String>,
+        item: Value,
+    },
+    AgentMessageDelta {
+        thread_id: String,
+        item_id: Option<String>,
+        delta: String,
+    },
+    AccountUpdated(Value),
+    AccountLoginCompleted {
+        login_id: Option<String>,
+        success: bool,
+        error: Option<String>,
+    },
+    RateLimitsUpdated(Value),
+    /// Unknown method, or a known method whose params did not match.
+    Unknown {
+        method: String,
+        params: Value,
+    },
+}
+
+fn parse_params<T: DeserializeOwned>(params: &Value) -> Option<T> {
+    T::deserialize(params).ok()
+}
+
+impl ServerNotification {
+    /// `None` when the message has no method (i.e. it is a response).
+    pub(crate) fn parse(message: &Value) -> Option<Self> {
+        let method = message.get("method")?.as_str()?;
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let parsed = match method {
+            "thread/started" => parse_params::<ThreadStartedParams>(&params)
+                .map(|p| Self::ThreadStarted { thread: p.thread }),
+            "thread/status/changed" => {
+                parse_params::<ThreadScopedParams>(&params).map(|p| Self::ThreadStatusChanged {
+                    thread_id: p.thread_id,
+                    status: p.status,
+                })
+            }
+            "thread/archived" => {
+                parse_params::<ThreadScopedParams>(&params).map(|p| Self::ThreadArchived {
+                    thread_id: p.thread_id,
+                })
+            }
+            "turn/started" => parse_params::<TurnParams>(&params).map(|p| Self::TurnStarted {
+                thread_id: p.thread_id,
+                turn: p.turn,
+            }),
+            "turn/completed" => parse_params::<TurnParams>(&params).map(|p| Self::TurnCompleted {
+                thread_id: p.thread_id,
+                turn: p.turn,
+            }),
+            "turn/error" | "error" => {
+                parse_params::<ErrorParams>(&params).map(|p| Self::TurnError {
+                    thread_id: p.thread_id,
+                    message: p.error.as_ref().and_then(error_message),
+                    will_retry: p.will_retry,
+                })
+            }
+            "item/started" => parse_params::<ItemParams>(&params).map(|p| Self::ItemStarted {
+                thread_id: p.thread_id,
+                turn_id: p.turn_id,
+                item: p.item,
+            }),
+            "item/completed" => parse_params::<ItemParams>(&params).map(|p| Self::ItemCompleted {
+                thread_id: p.thread_id,
+                turn_id: p.turn_id,
+                item: p.item,
+            }),
+            "item/agentMessage/delta" => {
+                parse_params::<DeltaParams>(&params).map(|p| Self::AgentMessageDelta {
+                    thread_id: p.thread_id,
+                    item_id: p.item_id,
+                    delta: p.delta,
+                })
+            }
+            "account/updated" => Some(Self::AccountUpdated(params.clone())),
+            "account/login/completed" => {
+                parse_params::<LoginCompletedParams>(&params).map(|p| Self::AccountLoginCompleted {
+                    login_id: p.login_id,
+                    success: p.success,
+                    error: p.error,
+                })
+            }
+            "account/rateLimits/updated" => Some(Self::RateLimitsUpdated(params.clone())),
+            _ => None,
+        };
+        Some(parsed.unwrap_or_else(|| Self::Unknown {
+            method: method.to_string(),
+            params,
+        }))
+    }
+}
+
+/// Turn boundaries and status changes, which unwatched clients still need to
+/// keep thread lists accurate.
+pub(crate) fn is_thread_boundary_method(method: &str) -> bool {
+    matches!(
+        method,
+        "turn/started" | "turn/completed" | "turn/error" | "error" | "thread/status/changed"
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Server -> client requests
+// ---------------------------------------------------------------------------
+
+/// Approvals and other prompts the app-server sends with an `id` it expects
+/// a client to answer.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct ServerRequest {
+    pub(crate) id: Value,
+    pub(crate) method: String,
+    pub(crate) params: Value,
+}
+
+impl ServerRequest {
+    /// `None` unless the message is a request (has both `id` and `method`
+    /// and is not a response).
+    pub(crate) fn parse(message: &Value) -> Option<Self> {
+        if message.get("result").is_some() || message.get("error").is_some() {
+            return None;
+        }
+        let id = message.get("id")?.clone();
+        let method = message.get("method")?.as_str()?.to_string();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        Some(Self { id, method, params })
+    }
+
+    pub(crate) fn thread_id(&self) -> Option<String> {
+        thread_id_from_value(&self.params)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Capability detection
+// ---------------------------------------------------------------------------
+
+/// What the connected app-server told us about itself in `initialize`, plus
+/// methods it has since rejected as unknown.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub(crate) struct AppServerCapabilities {
+    pub(crate) user_agent: Option<String>,
+    pub(crate) version: Option<(u64, u64, u64)>,
+    advertised: HashSet<String>,
+    unsupported_methods: HashSet<String>,
+}
+
+impl AppServerCapabilities {
+    pub(crate) fn from_initialize_response(response: &Value) -> Self {
+        let result = response.get("result").unwrap_or(response);
+        let user_agent = result
+            .get("userAgent")
+            .or_else(|| result.get("user_agent"))
+            .and_then(Value::as_str)
+            .map(str::to_string);
+        let version = user_agent.as_deref().and_then(parse_user_agent_version);
+        let advertised = result
+            .get("capabilities")
+            .and_then(Value::as_object)
+            .map(|capabilities| {
+                capabilities
+                    .iter()
+                    .filter(|(_, enabled)| enabled.as_bool() != Some(false))
+                    .map(|(name, _)| name.clone())
+                    .collect()
+            })
+            .unwrap_or_default();
+        Self {
+            user_agent,
+            version,
+            advertised,
+            unsupported_methods: HashSet::new(),
+        }
+    }
+
+    /// Optimistic: a method is assumed supported until the server rejects it.
+    pub(crate) fn supports_method(&self, method: &str) -> bool {
+        !self.unsupported_methods.contains(method)
+    }
+
+    pub(crate) fn mark_unsupported(&mut self, method: &str) {
+        self.unsupported_methods.insert(method.to_string());
+    }
+
+    pub(crate) fn to_json(&self) -> Value {
+        let mut advertised: Vec<&String> = self.advertised.iter().collect();
+        advertised.sort();
+        let mut unsupported: Vec<&String> = self.unsupported_methods.iter().collect();
+        unsupported.sort();
+        json!({
+            "userAgent": self.user_agent,
+            "version": self
+                .version
+                .map(|(major, minor, patch)| format!("{major}.{minor}.{patch}")),
+            "capabilities": advertised,
+            "unsupportedMethods": unsupported,
+        })
+    }
+}
+
+/// `codex_cli_rs/0.46.0 (Mac OS 15.0; arm64) ...` -> `(0, 46, 0)`.
+fn parse_user_agent_version(user_agent: &str) -> Option<(u64, u64, u64)> {
+    let product = user_agent.split_whitespace().next()?;
+    let (_, version) = product.split_once('/')?;
+    let core = version.split(['-', '+']).next()?;
+    let mut parts = core.split('.').map(|part| part.parse::<u64>().ok());
+    let major = parts.next()??;
+    let minor = parts.next().flatten().unwrap_or(0);
+    let patch = parts.next().flatten().unwrap_or(0);
+    Some((major, minor, patch))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        parse_response, thread_id_from_message, thread_id_from_response, AppServerCapabilities,
+        ClientRequest, LoginStartResponse, ServerNotification, ServerRequest, ThreadIdParams,
+        TurnStartParams,
+    };
+    use serde_json::{json, Map};
+
+    #[test]
+    fn thread_id_lookup_accepts_all_spellings() {
+        for params in [
+            json!({ "threadId": "t-1" }),
+            json!({ "thread_id": "t-1" }),
+            json!({ "thread": { "id": "t-1" } }),
+        ] {
+            let message = json!({ "method": "x", "params": params });
+            assert_eq!(thread_id_from_message(&message), Some("t-1".to_string()));
+        }
+        assert_eq!(thread_id_from_message(&json!({ "params": {} })), None);
+        assert_eq!(
+            thread_id_from_response(&json!({ "id": 1, "result": { "thread": { "id": "t-2" } } })),
+            Some("t-2".to_string())
+        );
+    }
+
+    #[test]
+    fn client_request_serializes_method_and_camel_case_params() {
+        let (method, params) = ClientRequest::ThreadResume(ThreadIdParams {
+            thread_id: "t-1".to_string(),
+        })
+        .into_parts()
+        .expect("parts");
+        assert_eq!(method, "thread/resume");
+        assert_eq!(params, json!({ "threadId": "t-1" }));
+
+        let mut execution_policy = Map::new();
+        execution_policy.insert("approvalPolicy".to_string(), json!("never"));
+        let (method, params) = ClientRequest::TurnStart(TurnStartParams {
+            thread_id: "t-1".to_string(),
+            input: vec![json!({ "type": "text", "text": "hi" })],
+            cwd: "/tmp".to_string(),
+            execution_policy,
+            ..TurnStartParams::default()
+        })
+        .into_parts()
+        .expect("parts");
+        assert_eq!(method, "turn/start");
+        assert_eq!(params["approvalPolicy"], "never");
+        assert!(params["model"].is_null());
+        assert!(params.get("collaborationMode").is_none());
+    }
+
+    #[test]
+    fn notifications_parse_leniently_and_fall_back_to_unknown() {
+        let delta = ServerNotification::parse(&json!({
+            "method": "item/agentMessage/delta",
+            "params": { "thread_id": "t-1", "itemId": "i-1", "delta": "hi", "extra": true }
+        }));
+        assert_eq!(
+            delta,
+            Some(ServerNotification::AgentMessageDelta {
+                thread_id: "t-1".to_string(),
+                item_id: Some("i-1".to_string()),
+                delta: "hi".to_string(),
+            })
+        );
+
+        let malformed = ServerNotification::parse(&json!({
+            "method": "turn/started",
+            "params": { "turn": {} }
+        }))
+        .expect("notification");
+        assert!(
+            matches!(malformed, ServerNotification::Unknown { ref method, .. } if method == "turn/started")
+        );
+
+        let error = ServerNotification::parse(&json!({
+            "method": "turn/error",
+            "params": { "threadId": "t-1", "error": { "message": "boom" } }
+        }))
+        .expect("notification");
+        assert_eq!(
+            error,
+            ServerNotification::TurnError {
+                thread_id: Some("t-1".to_string()),
+                message: Some("boom".to_string()),
+                will_retry: false,
+            }
+        );
+
+        assert!(ServerNotification::parse(&json!({ "id": 1, "result": {} })).is_none());
+    }
+
+    #[test]
+    fn server_requests_require_an_id() {
+        let approval = ServerRequest::parse(&json!({
+            "id": 3,
+            "method": "item/commandExecution/requestApproval",
+            "params": { "threadId": "t-1", "reason": "network" }
+        }))
+        .expect("request");
+        assert_eq!(approval.id, json!(3));
+        assert_eq!(approval.method, "item/commandExecution/requestApproval");
+        assert_eq!(approval.thread_id(), Some("t-1".to_string()));
+
+        assert!(ServerRequest::parse(&json!({
+            "method": "item/commandExecution/requestApproval",
+            "params": { "threadId": "t-1" }
+        }))
+        .is_none());
+        assert!(ServerRequest::parse(&json!({ "id": 3, "result": {} })).is_none());
+    }
+
+    #[test]
+    fn parse_response_reads_aliases_and_surfaces_errors() {
+        let response: LoginStartResponse = parse_response(&json!({
+            "id": 1,
+            "result": { "login_id": "l-1", "authUrl": "https://example.test" }
+        }))
+        .expect("login response");
+        assert_eq!(response.login_id, "l-1");
+        assert_eq!(response.auth_url, "https://example.test");
+
+        let error = parse_response::<LoginStartResponse>(&json!({
+            "id": 1,
+            "error": { "code": -32601, "message": "method not found" }
+        }))
+        .expect_err("error response");
+        assert_eq!(error, "method not found");
+    }
+
+    #[test]
+    fn capabilities_detect_version_and_learn_unsupported_methods() {
+        let mut capabilities = AppServerCapabilities::from_initialize_response(&json!({
+            "id": 0,
+            "result": {
+                "userAgent": "codex_cli_rs/0.46.2-alpha.1 (Mac OS 15.0; arm64) vscode/1.0",
+                "capabilities": { "experimentalApi": true, "legacy": false }
+            }
+        }));
+        assert_eq!(capabilities.version, Some((0, 46, 2)));
+        assert_eq!(
+            capabilities.to_json()["capabilities"],
+            json!(["experimentalApi"])
+        );
+        assert!(capabilities.supports_method("thread/resume"));
+        capabilities.mark_unsupported("thread/resume");
+        assert!(!capabilities.supports_method("thread/resume"));
+        assert_eq!(
+            capabilities.to_json()["unsupportedMethods"],
+            json!(["thread/resume"])
+        );
+    }
+}
@@ -0,0 +1,651 @@
+//! Headless client for the daemon's line-delimited JSON-RPC protocol, for CI
+//! jobs and SSH-only machines that need to drive agents without the GUI. It
+//! speaks the same wire format as `RemoteBackend::call`: an optional `auth`
+//! request, then one request per line.
+
+#[path = "../remote_backend/protocol.rs"]
+mod protocol;
+#[path = "../shared/thread_id_core.rs"]
+mod thread_id_core;
+#[path = "../remote_backend/tls.rs"]
+mod tls;
+#[path = "../shared/tls_core.rs"]
+mod tls_core;
+
+/// The slice of `shared` that `tls.rs` imports from.
+mod shared {
+    pub(crate) use super::tls_core;
+}
+
+use std::env;
+use std::io::Read;
+use std::path::PathBuf;
+
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader, Lines};
+use tokio::net::TcpStream;
+
+use protocol::{
+    build_request_line, parse_incoming_line, IncomingMessage, DEFAULT_REMOTE_HOST,
+    DISCONNECTED_MESSAGE,
+};
+use thread_id_core::thread_id_from_message;
+
+const TOKEN_ENV_VAR: &str = "CODEX_MONITOR_DAEMON_TOKEN";
+const HOST_ENV_VAR: &str = "CODEX_MONITOR_HOST";
+const THREAD_PREVIEW_CHARS: usize = 80;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Target {
+    Tcp {
+        host: String,
+        tls_fingerprint: Option<String>,
+    },
+    Unix(PathBuf),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum CliCommand {
+    WorkspacesList,
+    ThreadsList {
+        workspace_id: String,
+        limit: Option<u32>,
+    },
+    Send {
+        workspace_id: String,
+        thread_id: String,
+        text: String,
+    },
+    Tail {
+        workspace_id: Option<String>,
+        thread_id: Option<String>,
+    },
+    Approve {
+        workspace_id: String,
+        request_id: Value,
+        decline: bool,
+    },
+    GitStatus {
+        workspace_id: String,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct CliConfig {
+    target: Target,
+    token: Option<String>,
+    json: bool,
+    command: CliCommand,
+}
+
+fn usage() -> String {
+    format!(
+        "\
+USAGE:\n  codex-monitor-cli [connection options] <command>\n\n\
+COMMANDS:\n  workspaces list                          List workspaces\n  threads list <workspace> [--limit <n>]   List threads in a workspace\n  send <workspace> <thread> <text>         Send a message (`-` reads the text from stdin)\n  tail [--workspace <id>] [--thread <id>]  Stream app-server events until interrupted\n  approve <request-id> --workspace <id> [--decline]\n                                           Answer an approval request seen in `tail`\n  git status <workspace>                   Show staged and unstaged changes\n\n\
+OPTIONS:\n  --host <addr>            Daemon TCP address (default: ${HOST_ENV_VAR} or {DEFAULT_REMOTE_HOST})\n  --unix <path>            Connect to a daemon Unix socket instead of TCP\n  --token <token>          Daemon token (default: ${TOKEN_ENV_VAR})\n  --tls-fingerprint <fp>   Connect over TLS, trusting only this SHA-256 certificate fingerprint\n  --json                   Print raw JSON results (one event per line for `tail`)\n  -h, --help               Show this help\n"
+    )
+}
+
+fn parse_args(
+    args: Vec<String>,
+    env_token: Option<String>,
+    env_host: Option<String>,
+) -> Result<CliConfig, String> {
+    let mut host = env_host;
+    let mut unix_socket: Option<PathBuf> = None;
+    let mut token = env_token;
+    let mut tls_fingerprint: Option<String> = None;
+    let mut json = false;
+    let mut workspace_flag: Option<String> = None;
+    let mut thread_flag: Option<String> = None;
+    let mut limit: Option<u32> = None;
+    let mut decline = false;
+    let mut positional: Vec<String> = Vec::new();
+
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| -> Result<String, String> {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("{flag} requires a value"))?;
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                return Err(format!("{flag} requires a non-empty value"));
+            }
+            Ok(trimmed.to_string())
+        };
+        match arg.as_str() {
+            "-h" | "--help" => {
+                print!("{}", usage());
+                std::process::exit(0);
+            }
+            "--host" => host = Some(value("--host")?),
+            "--unix" => unix_socket = Some(PathBuf::from(value("--unix")?)),
+            "--token" => token = Some(value("--token")?),
+            "--tls-fingerprint" => tls_fingerprint = Some(value("--tls-fingerprint")?),
+            "--workspace" => workspace_flag = Some(value("--workspace")?),
+            "--thread" => thread_flag = Some(value("--thread")?),
+            "--limit" => {
+                let raw = value("--limit")?;
+                limit = Some(
+                    raw.parse::<u32>()
+                        .map_err(|_| format!("invalid --limit `{raw}`"))?,
+                );
+            }
+            "--json" => json = true,
+            "--decline" => decline = true,
+            _ if arg.starts_with("--") => return Err(format!("Unknown argument: {arg}")),
+            _ => positional.push(arg),
+        }
+    }
+
+    let target = match unix_socket {
+        Some(path) => {
+            if tls_fingerprint.is_some() {
+                return Err("--tls-fingerprint only applies to TCP connections".to_string());
+            }
+            Target::Unix(path)
+        }
+        None => Target::Tcp {
+            host: host
+                .filter(|value| !value.trim().is_empty())
+                .unwrap_or_else(|| DEFAULT_REMOTE_HOST.to_string()),
+            tls_fingerprint: tls_fingerprint
+                .as_deref()
+                .map(tls::normalize_fingerprint)
+                .transpose()?,
+        },
+    };
+
+    let positional: Vec<&str> = positional.iter().map(String::as_str).collect();
+    let command = match positional.as_slice() {
+        ["workspaces", "list"] => CliCommand::WorkspacesList,
+        ["threads", "list", workspace_id] => CliCommand::ThreadsList {
+            workspace_id: workspace_id.to_string(),
+            limit,
+        },
+        ["send", workspace_id, thread_id, text @ ..] if !text.is_empty() => CliCommand::Send {
+            workspace_id: workspace_id.to_string(),
+            thread_id: thread_id.to_string(),
+            text: text.join(" "),
+        },
+        ["tail"] => CliCommand::Tail {
+            workspace_id: workspace_flag,
+            thread_id: thread_flag,
+        },
+        ["approve", request_id] => CliCommand::Approve {
+            workspace_id: workspace_flag
+                .ok_or_else(|| "approve requires --workspace <id>".to_string())?,
+            request_id: parse_request_id(request_id),
+            decline,
+        },
+        ["git", "status", workspace_id] => CliCommand::GitStatus {
+            workspace_id: workspace_id.to_string(),
+        },
+        [] => return Err("Missing command".to_string()),
+        _ => return Err(format!("Unknown command: {}", positional.join(" "))),
+    };
+
+    Ok(CliConfig {
+        target,
+        token: token.filter(|value| !value.trim().is_empty()),
+        json,
+        command,
+    })
+}
+
+/// App-server request ids are numbers or strings; `tail` prints them as-is.
+fn parse_request_id(raw: &str) -> Value {
+    match raw.parse::<u64>() {
+        Ok(number) => json!(number),
+        Err(_) => json!(raw),
+    }
+}
+
+type BoxedReader = Box<dyn AsyncRead + Unpin + Send>;
+type BoxedWriter = Box<dyn AsyncWrite + Unpin + Send>;
+
+struct Connection {
+    writer: BoxedWriter,
+    lines: Lines<BufReader<BoxedReader>>,
+    next_id: u64,
+}
+
+impl Connection {
+    fn new(reader: BoxedReader, writer: BoxedWriter) -> Self {
+        Self {
+            writer,
+            lines: BufReader::new(reader).lines(),
+            next_id: 0,
+        }
+    }
+
+    async fn open(target: &Target) -> Result<Self, String> {
+        match target {
+            Target::Tcp {
+                host,
+                tls_fingerprint,
+            } => {
+                let stream = TcpStream::connect(host)
+                    .await
+                    .map_err(|err| format!("Failed to connect to {host}: {err}"))?;
+                match tls_fingerprint {
+                    Some(fingerprint) => {
+                        let stream = tls::pinned_connector(fingerprint)?
+                            .connect(tls::server_name_for_host(host), stream)
+                            .await
+                            .map_err(|err| format!("TLS handshake with {host} failed: {err}"))?;
+                        let (reader, writer) = tokio::io::split(stream);
+                        Ok(Self::new(Box::new(reader), Box::new(writer)))
+                    }
+                    None => {
+                        let (reader, writer) = stream.into_split();
+                        Ok(Self::new(Box::new(reader), Box::new(writer)))
+                    }
+                }
+            }
+            #[cfg(unix)]
+            Target::Unix(path) => {
+                let stream = tokio::net::UnixStream::connect(path)
+                    .await
+                    .map_err(|err| format!("Failed to connect to {}: {err}", path.display()))?;
+                let (reader, writer) = stream.into_split();
+                Ok(Self::new(Box::new(reader), Box::new(writer)))
+            }
+            #[cfg(not(unix))]
+            Target::Unix(_) => Err("Unix sockets are not supported on this platform".to_string()),
+        }
+    }
+
+    /// Sends one request and waits for its response. Notifications that
+    /// arrive in the meantime are dropped.
+    async fn call(&mut self, method: &str, params: Value) -> Result<Value, String> {
+        self.next_id += 1;
+        let id = self.next_id;
+        let mut line = build_request_line(id, method, params)?;
+        line.push('\n');
+        self.writer
+            .write_all(line.as_bytes())
+            .await
+            .map_err(|err| err.to_string())?;
+        loop {
+            if let IncomingMessage::Response {
+                id: response_id,
+                payload,
+            } = self.next_message().await?
+            {
+                if response_id == id {
+                    return payload;
+                }
+            }
+        }
+    }
+
+    async fn next_message(&mut self) -> Result<IncomingMessage, String> {
+        loop {
+            let line = self
+                .lines
+                .next_line()
+                .await
+                .map_err(|err| err.to_string())?
+                .ok_or_else(|| DISCONNECTED_MESSAGE.to_string())?;
+            if let Some(message) = parse_incoming_line(line.trim()) {
+                return Ok(message);
+            }
+        }
+    }
+}
+
+/// Client-side `tail` filter. Server requests carry the thread they belong
+/// to, so approvals for other threads are filtered too.
+fn event_matches(params: &Value, workspace_id: Option<&str>, thread_id: Option<&str>) -> bool {
+    let event_workspace = params.get("workspace_id").and_then(Value::as_str);
+    if workspace_id.is_some_and(|expected| event_workspace != Some(expected)) {
+        return false;
+    }
+    let message = params.get("message").unwrap_or(&Value::Null);
+    thread_id.is_none_or(|expected| thread_id_from_message(message).as_deref() == Some(expected))
+}
+
+fn format_event(params: &Value) -> String {
+    let workspace_id = params
+        .get("workspace_id")
+        .and_then(Value::as_str)
+        .unwrap_or("?");
+    let message = params.get("message").unwrap_or(&Value::Null);
+    let method = message.get("method").and_then(Value::as_str).unwrap_or("?");
+    let event_params = message.get("params").cloned().unwrap_or(Value::Null);
+    match message.get("id") {
+        Some(request_id) => {
+            format!("[{workspace_id}] {method} (request {request_id}) {event_params}")
+        }
+        None => format!("[{workspace_id}] {method} {event_params}"),
+    }
+}
+
+fn print_workspaces(result: &Value) {
+    for workspace in result.as_array().into_iter().flatten() {
+        let field = |key: &str| workspace.get(key).and_then(Value::as_str).unwrap_or("");
+        let connected = workspace
+            .get("connected")
+            .and_then(Value::as_bool)
+            .unwrap_or(false);
+        println!(
+            "{}\t{}\t{}\t{}",
+            field("id"),
+            field("name"),
+            field("path"),
+            if connected {
+                "connected"
+            } else {
+                "disconnected"
+            }
+        );
+    }
+}
+
+fn print_threads(response: &Value) {
+    let result = response.get("result").unwrap_or(response);
+    for thread in result
+        .get("data")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let id = thread.get("id").and_then(Value::as_str).unwrap_or("");
+        let preview: String = thread
+            .get("preview")
+            .and_then(Value::as_str)
+            .unwrap_or("")
+            .split_whitespace()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .chars()
+            .take(THREAD_PREVIEW_CHARS)
+            .collect();
+        println!("{id}\t{preview}");
+    }
+    let next_cursor = result
+        .get("nextCursor")
+        .or_else(|| result.get("next_cursor"))
+        .and_then(Value::as_str);
+    if let Some(cursor) = next_cursor {
+        println!("(more threads; next cursor {cursor})");
+    }
+}
+
+fn print_git_status(result: &Value) {
+    let branch = result
+        .get("branchName")
+        .and_then(Value::as_str)
+        .unwrap_or("(detached)");
+    println!("On branch {branch}");
+    for (label, key) in [("Staged", "stagedFiles"), ("Unstaged", "unstagedFiles")] {
+        let files = result
+            .get(key)
+            .and_then(Value::as_array)
+            .cloned()
+            .unwrap_or_default();
+        if files.is_empty() {
+            continue;
+        }
+        println!("{label}:");
+        for file in files {
+            let status = file.get("status").and_then(Value::as_str).unwrap_or("?");
+            let path = file.get("path").and_then(Value::as_str).unwrap_or("");
+            println!("  {status}\t{path}");
+        }
+    }
+    let additions = result
+        .get("totalAdditions")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+    let deletions = result
+        .get("totalDeletions")
+        .and_then(Value::as_i64)
+        .unwrap_or(0);
+    println!("+{additions} -{deletions}");
+}
+
+fn print_json(value: &Value) {
+    println!(
+        "{}",
+        serde_json::to_string_pretty(value).unwrap_or_else(|_| value.to_string())
+    );
+}
+
+fn read_message_text(text: String) -> Result<String, String> {
+    if text != "-" {
+        return Ok(text);
+    }
+    let mut buffer = String::new();
+    std::io::stdin()
+        .read_to_string(&mut buffer)
+        .map_err(|err| format!("Failed to read message from stdin: {err}"))?;
+    let trimmed = buffer.trim();
+    if trimmed.is_empty() {
+        return Err("Message text from stdin is empty".to_string());
+    }
+    Ok(trimmed.to_string())
+}
+
+async fn run(config: CliConfig) -> Result<(), String> {
+    let mut connection = Connection::open(&config.target).await?;
+    if let Some(token) = config.token.as_deref() {
+        connection.call("auth", json!({ "token": token })).await?;
+    }
+
+    match config.command {
+        CliCommand::WorkspacesList => {
+            let result = connection.call("list_workspaces", json!({})).await?;
+            if config.json {
+                print_json(&result);
+            } else {
+                print_workspaces(&result);
+            }
+        }
+        CliCommand::ThreadsList {
+            workspace_id,
+            limit,
+        } => {
+            let result = connection
+                .call(
+                    "list_threads",
+                    json!({ "workspaceId": workspace_id, "limit": limit }),
+                )
+                .await?;
+            if config.json {
+                print_json(&result);
+            } else {
+                print_threads(&result);
+            }
+        }
+        CliCommand::Send {
+            workspace_id,
+            thread_id,
+            text,
+        } => {
+            let text = read_message_text(text)?;
+            let result = connection
+                .call(
+                    "send_user_message",
+                    json!({ "workspaceId": workspace_id, "threadId": thread_id, "text": text }),
+                )
+                .await?;
+            print_json(&result);
+        }
+        CliCommand::Tail {
+            workspace_id,
+            thread_id,
+        } => loop {
+            let IncomingMessage::Notification { method, params } =
+                connection.next_message().await?
+            else {
+                continue;
+            };
+            if method != "app-server-event"
+                || !event_matches(&params, workspace_id.as_deref(), thread_id.as_deref())
+            {
+                continue;
+            }
+            if config.json {
+                println!("{params}");
+            } else {
+                println!("{}", format_event(&params));
+            }
+        },
+        CliCommand::Approve {
+            workspace_id,
+            request_id,
+            decline,
+        } => {
+            let (decision, outcome) = if decline {
+                ("decline", "Declined")
+            } else {
+                ("accept", "Approved")
+            };
+            let result = connection
+                .call(
+                    "respond_to_server_request",
+                    json!({
+                        "workspaceId": workspace_id,
+                        "requestId": request_id,
+                        "requestWorkspaceId": workspace_id,
+                        "requestIdEcho": request_id,
+                        "result": { "decision": decision },
+                    }),
+                )
+                .await?;
+            if config.json {
+                print_json(&result);
+            } else {
+                println!("{outcome} request {request_id}");
+            }
+        }
+        CliCommand::GitStatus { workspace_id } => {
+            let result = connection
+                .call("get_git_status", json!({ "workspaceId": workspace_id }))
+                .await?;
+            if config.json {
+                print_json(&result);
+            } else {
+                print_git_status(&result);
+            }
+        }
+    }
+    Ok(())
+}
+
+fn main() {
+    let config = match parse_args(
+        env::args().skip(1).collect(),
+        env::var(TOKEN_ENV_VAR).ok(),
+        env::var(HOST_ENV_VAR).ok(),
+    ) {
+        Ok(config) => config,
+        Err(err) => {
+            eprintln!("{err}\n\n{}", usage());
+            std::process::exit(2);
+        }
+    };
+
+    let runtime = tokio::runtime::Builder::new_current_thread()
+        .enable_all()
+        .build()
+        .expect("failed to build tokio runtime");
+
+    if let Err(err) = runtime.block_on(run(config)) {
+        eprintln!("{err}");
+        std::process::exit(1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{event_matches, format_event, parse_args, CliCommand, Target, DEFAULT_REMOTE_HOST};
+    use serde_json::json;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_commands_and_connection_options() {
+        let config = parse_args(args(&["workspaces", "list"]), Some("secret".into()), None)
+            .expect("workspaces list");
+        assert_eq!(config.command, CliCommand::WorkspacesList);
+        assert_eq!(config.token.as_deref(), Some("secret"));
+        assert_eq!(
+            config.target,
+            Target::Tcp {
+                host: DEFAULT_REMOTE_HOST.to_string(),
+                tls_fingerprint: None,
+            }
+        );
+
+        let config = parse_args(
+            args(&[
+                "--unix",
+                "/tmp/cm.sock",
+                "send",
+                "ws-1",
+                "th-1",
+                "fix",
+                "tests",
+            ]),
+            None,
+            Some("10.0.0.2:4732".into()),
+        )
+        .expect("send");
+        assert_eq!(config.target, Target::Unix("/tmp/cm.sock".into()));
+        assert_eq!(
+            config.command,
+            CliCommand::Send {
+                workspace_id: "ws-1".to_string(),
+                thread_id: "th-1".to_string(),
+                text: "fix tests".to_string(),
+            }
+        );
+
+        let config = parse_args(
+            args(&["approve", "42", "--workspace", "ws-1", "--decline"]),
+            None,
+            None,
+        )
+        .expect("approve");
+        assert_eq!(
+            config.command,
+            CliCommand::Approve {
+                workspace_id: "ws-1".to_string(),
+                request_id: json!(42),
+                decline: true,
+            }
+        );
+
+        assert!(parse_args(args(&["approve", "42"]), None, None).is_err());
+        assert!(parse_args(args(&["send", "ws-1", "th-1"]), None, None).is_err());
+        assert!(parse_args(args(&["git", "log", "ws-1"]), None, None).is_err());
+        assert!(parse_args(args(&["tail", "--tls-fingerprint", "nope"]), None, None).is_err());
+    }
+
+    #[test]
+    fn tail_filters_by_workspace_and_thread() {
+        let event = json!({
+            "workspace_id": "ws-1",
+            "message": {
+                "method": "item/commandExecution/requestApproval",
+                "id": 7,
+                "params": { "threadId": "th-1" }
+            }
+        });
+        assert!(event_matches(&event, None, None));
+        assert!(event_matches(&event, Some("ws-1"), Some("th-1")));
+        assert!(!event_matches(&event, Some("ws-2"), None));
+        assert!(!event_matches(&event, None, Some("th-2")));
+        assert_eq!(
+            format_event(&event),
+            "[ws-1] item/commandExecution/requestApproval (request 7) {\"threadId\":\"th-1\"}"
+        );
+    }
+}
@@ -1,3 +1,5 @@
+#[path = "codex_monitor_daemon/audit.rs"]
+mod audit;
 #[allow(dead_code)]
 #[path = "../backend/mod.rs"]
 mod backend;
@@ -7,6 +9,8 @@ mod codex_args;
 mod codex_config;
 #[path = "../codex/home.rs"]
 mod codex_home;
+#[path = "codex_monitor_daemon/config_file.rs"]
+mod config_file;
 #[path = "../files/io.rs"]
 mod file_io;
 #[path = "../files/ops.rs"]
@@ -19,12 +23,22 @@ mod git_utils;
 mod rpc;
 #[path = "codex_monitor_daemon/meta.rs"]
 mod meta;
+#[path = "codex_monitor_daemon/metrics.rs"]
+mod metrics;
 #[path = "../rules.rs"]
 mod rules;
+#[path = "codex_monitor_daemon/service.rs"]
+mod service;
 #[path = "../shared/mod.rs"]
 mod shared;
+#[path = "codex_monitor_daemon/snapshot.rs"]
+mod snapshot;
 #[path = "../storage.rs"]
 mod storage;
+#[path = "codex_monitor_daemon/tls.rs"]
+mod tls;
+#[path = "codex_monitor_daemon/tokens.rs"]
+mod tokens;
 #[path = "codex_monitor_daemon/transport.rs"]
 mod transport;
 #[allow(dead_code)]
@@ -69,33 +83,49 @@ use std::env;
 use std::fs::File;
 use std::io::Read;
 use std::net::SocketAddr;
-use std::path::PathBuf;
-use std::sync::Arc;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex, RwLock as StdRwLock};
 use std::time::Duration;
 
 use futures_util::{SinkExt, StreamExt};
 use ignore::WalkBuilder;
 use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::net::{TcpListener, TcpStream};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::net::TcpListener;
 use tokio::sync::{broadcast, mpsc, Mutex, Semaphore};
 use tokio::time::sleep;
 use tokio_tungstenite::connect_async;
 use tokio_tungstenite::tungstenite::Message;
 
-use backend::app_server::{spawn_workspace_session, WorkspaceSession};
-use backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use audit::{AuditLog, AuditQuery};
+use backend::app_server::{
+    configure_app_server_pooling, configure_request_timeouts, spawn_workspace_session,
+    WorkspaceSession,
+};
+use backend::events::{AppServerEvent, EventSink, GitStatusChanged, TerminalExit, TerminalOutput};
+use config_file::{ConfigFile, RuntimeConfig};
+use metrics::DaemonMetrics;
 use shared::codex_core::CodexLoginCancelState;
+use shared::event_journal_core::{self, configure_event_journal};
+use shared::git_status_watch_core::{self, GitStatusWatchSet, GitStatusWatchers};
 use shared::prompts_core::{self, CustomPromptEntry};
+use shared::terminal_core::{self, TerminalSessionInfo, TerminalSessions};
 use shared::{
     agents_config_core, codex_aux_core, codex_core, files_core, git_core, git_ui_core,
-    local_usage_core, settings_core, workspaces_core, worktree_core,
+    local_usage_core, process_stats_core, settings_core, workspaces_core, worktree_core,
+};
+use snapshot::{
+    ActiveTurn, DaemonStateSnapshot, PendingServerRequests, RunningTerminal, WorkspaceConnection,
 };
 use storage::{read_settings, read_workspaces};
+use tokens::{Principal, TokenScope, TokenStore};
 use types::{
-    AppSettings, GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse, LocalUsageSnapshot,
-    OrbitConnectTestResult, OrbitDeviceCodeStart, OrbitSignInPollResult, OrbitSignInStatus,
-    OrbitSignOutResult, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus,
+    AppSettings, GitCommitDiff, GitConflictResolution, GitConflictStatus, GitFileDiff,
+    GitHubIssuesResponse, GitHubPullRequestComment, GitHubPullRequestDiff,
+    GitHubPullRequestsResponse, GitHunkSelection, GitLogFilter, GitLogResponse, GitStashEntry,
+    LocalUsageSnapshot, OrbitConnectTestResult, OrbitDeviceCodeStart, OrbitSignInPollResult,
+    OrbitSignInStatus, OrbitSignOutResult, WorkspaceEntry, WorkspaceInfo, WorkspaceSettings,
+    WorktreeSetupStatus,
 };
 use workspace_settings::apply_workspace_settings_update;
 
@@ -123,19 +153,46 @@ fn spawn_with_client(
 #[derive(Clone)]
 struct DaemonEventSink {
     tx: broadcast::Sender<DaemonEvent>,
+    /// Server requests seen on the way out, so a client that missed them can
+    /// still answer them after a lag or reconnect.
+    pending_requests: Arc<StdMutex<PendingServerRequests>>,
+}
+
+impl DaemonEventSink {
+    fn new(tx: broadcast::Sender<DaemonEvent>) -> Self {
+        Self {
+            tx,
+            pending_requests: Arc::new(StdMutex::new(PendingServerRequests::default())),
+        }
+    }
+
+    fn resolve_server_request(&self, workspace_id: &str, request_id: &Value) {
+        if let Ok(mut pending) = self.pending_requests.lock() {
+            pending.resolve(workspace_id, request_id);
+        }
+    }
+
+    fn pending_server_requests(&self) -> Vec<snapshot::PendingServerRequest> {
+        self.pending_requests
+            .lock()
+            .map(|pending| pending.list())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Clone)]
 enum DaemonEvent {
     AppServer(AppServerEvent),
-    #[allow(dead_code)]
     TerminalOutput(TerminalOutput),
-    #[allow(dead_code)]
     TerminalExit(TerminalExit),
+    GitStatusChanged(GitStatusChanged),
 }
 
 impl EventSink for DaemonEventSink {
     fn emit_app_server_event(&self, event: AppServerEvent) {
+        if let Ok(mut pending) = self.pending_requests.lock() {
+            pending.observe(&event);
+        }
         if let Err(err) = self.tx.send(DaemonEvent::AppServer(event)) {
             eprintln!("[daemon] failed to broadcast app-server event: {err}");
         }
@@ -152,16 +209,46 @@ impl EventSink for DaemonEventSink {
             eprintln!("[daemon] failed to broadcast terminal-exit event: {err}");
         }
     }
+
+    fn emit_git_status_changed(&self, event: GitStatusChanged) {
+        if let Err(err) = self.tx.send(DaemonEvent::GitStatusChanged(event)) {
+            eprintln!("[daemon] failed to broadcast git-status-changed event: {err}");
+        }
+    }
+}
+
+/// Values given as flags or `CODEX_MONITOR_*` environment variables. They
+/// win over the config file, including when it is reloaded.
+#[derive(Clone, Default)]
+struct DaemonFlags {
+    config_path: Option<PathBuf>,
+    listen: Option<SocketAddr>,
+    listen_unix: Option<PathBuf>,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+    metrics_listen: Option<SocketAddr>,
+    token: Option<String>,
+    insecure_no_auth: bool,
+    data_dir: Option<PathBuf>,
+    orbit_url: Option<String>,
+    orbit_token: Option<String>,
+    orbit_auth_url: Option<String>,
+    orbit_runner_name: Option<String>,
 }
 
 struct DaemonConfig {
     listen: SocketAddr,
-    token: Option<String>,
+    listen_unix: Option<PathBuf>,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+    metrics_listen: Option<SocketAddr>,
     data_dir: PathBuf,
     orbit_url: Option<String>,
-    orbit_token: Option<String>,
     orbit_auth_url: Option<String>,
     orbit_runner_name: Option<String>,
+    /// Initial value of [`DaemonState::runtime`].
+    runtime: RuntimeConfig,
+    flags: DaemonFlags,
 }
 
 struct DaemonState {
@@ -173,6 +260,13 @@ struct DaemonState {
     app_settings: Mutex<AppSettings>,
     event_sink: DaemonEventSink,
     codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
+    terminal_sessions: TerminalSessions,
+    git_status_watchers: GitStatusWatchers,
+    tokens: Mutex<TokenStore>,
+    audit_log: AuditLog,
+    metrics: Arc<DaemonMetrics>,
+    /// Shared token, limits and workspace roots; replaced on config reload.
+    runtime: StdRwLock<RuntimeConfig>,
     daemon_mode: String,
     daemon_binary_path: Option<String>,
 }
@@ -189,14 +283,27 @@ impl DaemonState {
         let settings_path = config.data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
+        configure_request_timeouts(&app_settings);
+        configure_app_server_pooling(&app_settings);
+        configure_event_journal(&config.data_dir, workspaces.keys().cloned().collect());
         let daemon_mode = if config.orbit_url.is_some() {
             "orbit".to_string()
+        } else if config.listen_unix.is_some() {
+            "unix".to_string()
         } else {
             "tcp".to_string()
         };
         let daemon_binary_path = std::env::current_exe()
             .ok()
             .and_then(|path| path.to_str().map(str::to_string));
+        // An unreadable token file must not fall back to an open daemon.
+        let tokens = match TokenStore::load(tokens::tokens_path(&config.data_dir)) {
+            Ok(tokens) => tokens,
+            Err(err) => {
+                eprintln!("failed to load scoped tokens: {err}");
+                std::process::exit(2);
+            }
+        };
         Self {
             data_dir: config.data_dir.clone(),
             workspaces: Mutex::new(workspaces),
@@ -206,19 +313,162 @@ impl DaemonState {
             app_settings: Mutex::new(app_settings),
             event_sink,
             codex_login_cancels: Mutex::new(HashMap::new()),
+            terminal_sessions: Mutex::new(HashMap::new()),
+            git_status_watchers: GitStatusWatchers::default(),
+            tokens: Mutex::new(tokens),
+            audit_log: AuditLog::new(&config.data_dir),
+            metrics: Arc::new(DaemonMetrics::new(
+                config.runtime.max_in_flight_rpc_per_connection,
+            )),
+            runtime: StdRwLock::new(config.runtime.clone()),
             daemon_mode,
             daemon_binary_path,
         }
     }
 
+    fn runtime_config(&self) -> RuntimeConfig {
+        // A poisoned lock still holds the last applied settings; never fall
+        // back to defaults, which would drop the shared token.
+        match self.runtime.read() {
+            Ok(runtime) => runtime.clone(),
+            Err(poisoned) => poisoned.into_inner().clone(),
+        }
+    }
+
+    fn apply_runtime_config(&self, runtime: RuntimeConfig) {
+        self.metrics
+            .set_max_in_flight_per_connection(runtime.max_in_flight_rpc_per_connection);
+        match self.runtime.write() {
+            Ok(mut current) => *current = runtime,
+            Err(poisoned) => *poisoned.into_inner() = runtime,
+        }
+    }
+
+    async fn authorize(&self, principal: Option<&Principal>, method: &str) -> Result<(), String> {
+        let required = tokens::required_scope(method);
+        match principal {
+            Some(Principal::Root) => Ok(()),
+            Some(Principal::Token(name)) => match self.tokens.lock().await.scope_of(name) {
+                Some(scope) if scope >= required => Ok(()),
+                Some(_) => Err(tokens::forbidden_message(method, required)),
+                None => Err("unauthorized: token has been revoked".to_string()),
+            },
+            None => Err("unauthorized".to_string()),
+        }
+    }
+
+    async fn token_list(&self) -> Value {
+        let tokens = self.tokens.lock().await;
+        let tokens: Vec<Value> = tokens
+            .list()
+            .iter()
+            .map(|token| {
+                json!({
+                    "name": token.name,
+                    "scope": token.scope,
+                    "createdAtMs": token.created_at_ms,
+                })
+            })
+            .collect();
+        json!({ "tokens": tokens })
+    }
+
+    async fn token_mint(&self, name: String, scope: TokenScope) -> Result<Value, String> {
+        let token = self.tokens.lock().await.mint(&name, scope)?;
+        Ok(json!({ "name": name.trim(), "scope": scope, "token": token }))
+    }
+
+    async fn token_revoke(&self, name: String) -> Result<Value, String> {
+        let revoked = self.tokens.lock().await.revoke(&name)?;
+        Ok(json!({ "revoked": revoked }))
+    }
+
+    async fn audit_log_query(&self, query: AuditQuery) -> Result<Value, String> {
+        let audit_log = self.audit_log.clone();
+        tokio::task::spawn_blocking(move || audit_log.query(&query))
+            .await
+            .map_err(|err| format!("failed to join audit query task: {err}"))?
+    }
+
     fn daemon_info(&self) -> Value {
         meta::daemon_info(&self.daemon_mode, self.daemon_binary_path.as_deref())
     }
 
+    async fn daemon_metrics(&self) -> metrics::MetricsSnapshot {
+        let sessions = self
+            .sessions
+            .lock()
+            .await
+            .iter()
+            .map(|(workspace_id, session)| metrics::SessionSnapshot {
+                workspace_id: workspace_id.clone(),
+                uptime_seconds: session.started_at.elapsed().as_secs(),
+                restart_count: session
+                    .restart_count
+                    .load(std::sync::atomic::Ordering::Relaxed),
+            })
+            .collect();
+        self.metrics.snapshot(
+            sessions,
+            backend::background_callbacks::background_callback_metrics(),
+        )
+    }
+
     async fn list_workspaces(&self) -> Vec<WorkspaceInfo> {
         workspaces_core::list_workspaces_core(&self.workspaces, &self.sessions).await
     }
 
+    async fn daemon_state_snapshot(&self) -> DaemonStateSnapshot {
+        let workspaces: Vec<WorkspaceConnection> = self
+            .list_workspaces()
+            .await
+            .into_iter()
+            .map(|workspace| WorkspaceConnection {
+                id: workspace.id,
+                name: workspace.name,
+                connected: workspace.connected,
+            })
+            .collect();
+        let sessions: Vec<(String, Arc<WorkspaceSession>)> = self
+            .sessions
+            .lock()
+            .await
+            .iter()
+            .map(|(workspace_id, session)| (workspace_id.clone(), Arc::clone(session)))
+            .collect();
+        let active_turns = sessions
+            .iter()
+            .flat_map(|(workspace_id, session)| {
+                session
+                    .running_turns_for(workspace_id)
+                    .into_iter()
+                    .map(|(thread_id, turn_id)| ActiveTurn {
+                        workspace_id: workspace_id.clone(),
+                        thread_id,
+                        turn_id,
+                    })
+            })
+            .collect();
+        // Terminal sessions are keyed `workspace_id:terminal_id`.
+        let terminals = self
+            .terminal_sessions
+            .lock()
+            .await
+            .keys()
+            .filter_map(|key| key.split_once(':'))
+            .map(|(workspace_id, terminal_id)| RunningTerminal {
+                workspace_id: workspace_id.to_string(),
+                terminal_id: terminal_id.to_string(),
+            })
+            .collect();
+        DaemonStateSnapshot::new(
+            workspaces,
+            active_turns,
+            self.event_sink.pending_server_requests(),
+            terminals,
+        )
+    }
+
     async fn is_workspace_path_dir(&self, path: String) -> bool {
         workspaces_core::is_workspace_path_dir_core(&path)
     }
@@ -229,6 +479,7 @@ impl DaemonState {
         codex_bin: Option<String>,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
+        self.runtime_config().check_workspace_path(&path)?;
         let client_version = client_version.clone();
         workspaces_core::add_workspace_core(
             path,
@@ -259,6 +510,16 @@ impl DaemonState {
         codex_bin: Option<String>,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
+        let runtime = self.runtime_config();
+        runtime.check_workspace_path(&destination_path)?;
+        // The folder inside the destination may already exist, e.g. as a
+        // symlink leading outside the allowed roots.
+        let clone_path = workspaces_core::git_url_clone_path(
+            &url,
+            Path::new(destination_path.trim()),
+            target_folder_name.as_deref(),
+        )?;
+        runtime.check_workspace_path(&clone_path.to_string_lossy())?;
         let client_version = client_version.clone();
         workspaces_core::add_workspace_from_git_url_core(
             url,
@@ -548,6 +809,10 @@ impl DaemonState {
         .await
     }
 
+    async fn shutdown_sessions(&self) {
+        workspaces_core::shutdown_sessions_core(&self.sessions).await;
+    }
+
     async fn get_app_settings(&self) -> AppSettings {
         settings_core::get_app_settings_core(&self.app_settings).await
     }
@@ -626,6 +891,82 @@ impl DaemonState {
         })
     }
 
+    async fn workspace_process_stats(&self, workspace_id: Option<String>) -> Result<Value, String> {
+        process_stats_core::workspace_process_stats_core(
+            &self.workspaces,
+            &self.sessions,
+            workspace_id,
+            &self.event_sink,
+        )
+        .await
+    }
+
+    async fn watch_process_limits(&self) {
+        loop {
+            tokio::time::sleep(process_stats_core::PROCESS_LIMIT_POLL_INTERVAL).await;
+            process_stats_core::check_process_limits_core(
+                &self.workspaces,
+                &self.sessions,
+                &self.event_sink,
+            )
+            .await;
+        }
+    }
+
+    async fn terminal_open(
+        &self,
+        workspace_id: String,
+        terminal_id: String,
+        cols: u16,
+        rows: u16,
+    ) -> Result<TerminalSessionInfo, String> {
+        terminal_core::terminal_open_core(
+            &self.terminal_sessions,
+            &self.workspaces,
+            workspace_id,
+            terminal_id,
+            cols,
+            rows,
+            self.event_sink.clone(),
+        )
+        .await
+    }
+
+    async fn terminal_write(
+        &self,
+        workspace_id: String,
+        terminal_id: String,
+        data: String,
+    ) -> Result<(), String> {
+        terminal_core::terminal_write_core(&self.terminal_sessions, workspace_id, terminal_id, data)
+            .await
+    }
+
+    async fn terminal_resize(
+        &self,
+        workspace_id: String,
+        terminal_id: String,
+        cols: u16,
+        rows: u16,
+    ) -> Result<(), String> {
+        terminal_core::terminal_resize_core(
+            &self.terminal_sessions,
+            workspace_id,
+            terminal_id,
+            cols,
+            rows,
+        )
+        .await
+    }
+
+    async fn terminal_close(
+        &self,
+        workspace_id: String,
+        terminal_id: String,
+    ) -> Result<(), String> {
+        terminal_core::terminal_close_core(&self.terminal_sessions, workspace_id, terminal_id).await
+    }
+
     async fn list_workspace_files(&self, workspace_id: String) -> Result<Vec<String>, String> {
         workspaces_core::list_workspace_files_core(&self.workspaces, &workspace_id, |root| {
             list_workspace_files_inner(root, 20000)
@@ -729,6 +1070,7 @@ impl DaemonState {
 
     async fn thread_live_subscribe(
         &self,
+        subscriptions: &Mutex<rpc::ThreadLiveSubscriptions>,
         workspace_id: String,
         thread_id: String,
     ) -> Result<Value, String> {
@@ -738,6 +1080,10 @@ impl DaemonState {
             thread_id.clone(),
         )
         .await?;
+        subscriptions
+            .lock()
+            .await
+            .subscribe(&workspace_id, &thread_id);
         let subscription_id = format!("{}:{}", workspace_id, thread_id);
         self.event_sink.emit_app_server_event(AppServerEvent {
             workspace_id: workspace_id.clone(),
@@ -749,6 +1095,7 @@ impl DaemonState {
                     "subscriptionId": subscription_id,
                 }
             }),
+            seq: None,
         });
         Ok(json!({
             "subscriptionId": subscription_id,
@@ -758,9 +1105,15 @@ impl DaemonState {
 
     async fn thread_live_unsubscribe(
         &self,
+        subscriptions: &Mutex<rpc::ThreadLiveSubscriptions>,
         workspace_id: String,
         thread_id: String,
     ) -> Result<Value, String> {
+        // Drop the subscription even if the workspace has since disconnected.
+        subscriptions
+            .lock()
+            .await
+            .unsubscribe(&workspace_id, &thread_id);
         codex_core::thread_live_unsubscribe_core(
             &self.sessions,
             workspace_id.clone(),
@@ -777,10 +1130,20 @@ impl DaemonState {
                     "reason": "manual",
                 }
             }),
+            seq: None,
         });
         Ok(json!({ "ok": true }))
     }
 
+    async fn replay_events(
+        &self,
+        workspace_id: String,
+        since_seq: u64,
+        limit: Option<u32>,
+    ) -> Result<Value, String> {
+        event_journal_core::replay_events_core(workspace_id, since_seq, limit).await
+    }
+
     async fn set_thread_name(
         &self,
         workspace_id: String,
@@ -805,6 +1168,7 @@ impl DaemonState {
     ) -> Result<Value, String> {
         codex_core::send_user_message_core(
             &self.sessions,
+            &self.workspaces,
             workspace_id,
             thread_id,
             text,
@@ -971,11 +1335,13 @@ impl DaemonState {
     ) -> Result<Value, String> {
         codex_core::respond_to_server_request_core(
             &self.sessions,
-            workspace_id,
-            request_id,
+            workspace_id.clone(),
+            request_id.clone(),
             result,
         )
         .await?;
+        self.event_sink
+            .resolve_server_request(&workspace_id, &request_id);
         Ok(json!({ "ok": true }))
     }
 
@@ -998,6 +1364,7 @@ impl DaemonState {
         copy_name: String,
         client_version: String,
     ) -> Result<WorkspaceInfo, String> {
+        self.runtime_config().check_workspace_path(&copies_folder)?;
         workspaces_core::add_clone_core(
             source_workspace_id,
             copy_name,
@@ -1053,6 +1420,29 @@ impl DaemonState {
         git_ui_core::get_git_status_core(&self.workspaces, workspace_id).await
     }
 
+    async fn watch_git_status(
+        &self,
+        watches: &Mutex<GitStatusWatchSet>,
+        workspace_id: String,
+    ) -> Result<(), String> {
+        git_status_watch_core::watch_git_status_core(
+            &self.git_status_watchers,
+            watches,
+            &self.workspaces,
+            workspace_id,
+            self.event_sink.clone(),
+        )
+        .await
+    }
+
+    async fn unwatch_git_status(
+        &self,
+        watches: &Mutex<GitStatusWatchSet>,
+        workspace_id: String,
+    ) -> Result<(), String> {
+        git_status_watch_core::unwatch_git_status_core(watches, workspace_id).await
+    }
+
     async fn list_git_roots(
         &self,
         workspace_id: String,
@@ -1069,8 +1459,10 @@ impl DaemonState {
         &self,
         workspace_id: String,
         limit: Option<usize>,
+        cursor: Option<String>,
+        filter: Option<GitLogFilter>,
     ) -> Result<GitLogResponse, String> {
-        git_ui_core::get_git_log_core(&self.workspaces, workspace_id, limit).await
+        git_ui_core::get_git_log_core(&self.workspaces, workspace_id, limit, cursor, filter).await
     }
 
     async fn get_git_commit_diff(
@@ -1111,6 +1503,33 @@ impl DaemonState {
         git_ui_core::revert_git_all_core(&self.workspaces, workspace_id).await
     }
 
+    async fn stage_git_hunks(
+        &self,
+        workspace_id: String,
+        path: String,
+        selection: GitHunkSelection,
+    ) -> Result<(), String> {
+        git_ui_core::stage_git_hunks_core(&self.workspaces, workspace_id, path, selection).await
+    }
+
+    async fn unstage_git_hunks(
+        &self,
+        workspace_id: String,
+        path: String,
+        selection: GitHunkSelection,
+    ) -> Result<(), String> {
+        git_ui_core::unstage_git_hunks_core(&self.workspaces, workspace_id, path, selection).await
+    }
+
+    async fn revert_git_hunks(
+        &self,
+        workspace_id: String,
+        path: String,
+        selection: GitHunkSelection,
+    ) -> Result<(), String> {
+        git_ui_core::revert_git_hunks_core(&self.workspaces, workspace_id, path, selection).await
+    }
+
     async fn commit_git(&self, workspace_id: String, message: String) -> Result<(), String> {
         git_ui_core::commit_git_core(&self.workspaces, workspace_id, message).await
     }
@@ -1179,6 +1598,84 @@ impl DaemonState {
         git_ui_core::create_git_branch_core(&self.workspaces, workspace_id, name).await
     }
 
+    async fn list_git_stashes(&self, workspace_id: String) -> Result<Vec<GitStashEntry>, String> {
+        git_ui_core::list_git_stashes_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn create_git_stash(
+        &self,
+        workspace_id: String,
+        message: Option<String>,
+        include_untracked: bool,
+    ) -> Result<GitStashEntry, String> {
+        git_ui_core::create_git_stash_core(
+            &self.workspaces,
+            workspace_id,
+            message,
+            include_untracked,
+        )
+        .await
+    }
+
+    async fn apply_git_stash(&self, workspace_id: String, sha: String) -> Result<(), String> {
+        git_ui_core::apply_git_stash_core(&self.workspaces, workspace_id, sha).await
+    }
+
+    async fn pop_git_stash(&self, workspace_id: String, sha: String) -> Result<(), String> {
+        git_ui_core::pop_git_stash_core(&self.workspaces, workspace_id, sha).await
+    }
+
+    async fn drop_git_stash(&self, workspace_id: String, sha: String) -> Result<(), String> {
+        git_ui_core::drop_git_stash_core(&self.workspaces, workspace_id, sha).await
+    }
+
+    async fn get_git_stash_diff(
+        &self,
+        workspace_id: String,
+        sha: String,
+    ) -> Result<Vec<GitFileDiff>, String> {
+        git_ui_core::get_git_stash_diff_core(
+            &self.workspaces,
+            &self.app_settings,
+            workspace_id,
+            sha,
+        )
+        .await
+    }
+
+    async fn get_git_conflicts(&self, workspace_id: String) -> Result<GitConflictStatus, String> {
+        git_ui_core::get_git_conflicts_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn resolve_git_conflict(
+        &self,
+        workspace_id: String,
+        path: String,
+        resolution: GitConflictResolution,
+        content: Option<String>,
+    ) -> Result<(), String> {
+        git_ui_core::resolve_git_conflict_core(
+            &self.workspaces,
+            workspace_id,
+            path,
+            resolution,
+            content,
+        )
+        .await
+    }
+
+    async fn abort_merge(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::abort_merge_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn continue_merge(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::continue_merge_core(&self.workspaces, workspace_id).await
+    }
+
+    async fn abort_rebase(&self, workspace_id: String) -> Result<(), String> {
+        git_ui_core::abort_rebase_core(&self.workspaces, workspace_id).await
+    }
+
     async fn prompts_list(&self, workspace_id: String) -> Result<Vec<CustomPromptEntry>, String> {
         prompts_core::prompts_list_core(&self.workspaces, &self.settings_path, workspace_id).await
     }
@@ -1371,6 +1868,7 @@ fn emit_background_thread_hide(event_sink: &DaemonEventSink, workspace_id: &str,
                 "action": "hide"
             }
         }),
+        seq: None,
     });
 }
 
@@ -1498,34 +1996,27 @@ fn default_data_dir() -> PathBuf {
 fn usage() -> String {
     format!(
         "\
-USAGE:\n  codex-monitor-daemon [--listen <addr>] [--data-dir <path>] [--token <token> | --insecure-no-auth]\n  codex-monitor-daemon --orbit-url <ws-url> [--orbit-token <token>] [--orbit-auth-url <url>] [--orbit-runner-name <name>] [--data-dir <path>]\n\n\
-OPTIONS:\n  --listen <addr>          Bind address (default: {DEFAULT_LISTEN_ADDR})\n  --data-dir <path>        Data dir holding workspaces.json/settings.json\n  --token <token>          Shared token required by TCP clients\n  --insecure-no-auth       Disable TCP auth (dev only)\n  --orbit-url <ws-url>     Run in Orbit runner mode and connect outbound to this WS URL\n  --orbit-token <token>    Orbit auth token (optional if URL already includes token)\n  --orbit-auth-url <url>   Orbit auth base URL (metadata only, optional)\n  --orbit-runner-name <n>  Runner display name (metadata only, optional)\n  -h, --help               Show this help\n"
+USAGE:\n  codex-monitor-daemon [--listen <addr>] [--data-dir <path>] [--token <token> | --insecure-no-auth] [--tls-cert <path> --tls-key <path>]\n  codex-monitor-daemon --listen-unix <path> [--data-dir <path>] [--token <token>]\n  codex-monitor-daemon --orbit-url <ws-url> [--orbit-token <token>] [--orbit-auth-url <url>] [--orbit-runner-name <name>] [--data-dir <path>]\n{}\n\
+OPTIONS:\n  --config <path>          TOML config file; flags and env vars override it, SIGHUP reloads it\n  --listen <addr>          Bind address (default: {DEFAULT_LISTEN_ADDR})\n  --listen-unix <path>     Listen on a Unix socket (mode 0600) instead of TCP; token optional\n  --tls-cert <path>        PEM certificate chain; serves TCP clients over TLS\n  --tls-key <path>         PEM private key for --tls-cert\n  --metrics-listen <addr>  Serve Prometheus metrics at http://<addr>/metrics (no auth; keep on loopback)\n  --data-dir <path>        Data dir holding workspaces.json/settings.json\n  --token <token>          Shared admin token required by TCP clients (scoped tokens live in <data-dir>/tokens.json)\n  --insecure-no-auth       Disable TCP auth (dev only)\n  --orbit-url <ws-url>     Run in Orbit runner mode and connect outbound to this WS URL\n  --orbit-token <token>    Orbit auth token (optional if URL already includes token)\n  --orbit-auth-url <url>   Orbit auth base URL (metadata only, optional)\n  --orbit-runner-name <n>  Runner display name (metadata only, optional)\n  -h, --help               Show this help\n",
+        service::SERVICE_USAGE
     )
 }
 
-fn parse_args() -> Result<DaemonConfig, String> {
-    let mut listen = DEFAULT_LISTEN_ADDR
-        .parse::<SocketAddr>()
-        .map_err(|err| err.to_string())?;
-    let mut token = env::var("CODEX_MONITOR_DAEMON_TOKEN")
+fn env_value(key: &str) -> Option<String> {
+    env::var(key)
         .ok()
         .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-    let mut insecure_no_auth = false;
-    let mut data_dir: Option<PathBuf> = None;
-    let mut orbit_url: Option<String> = None;
-    let mut orbit_token: Option<String> = env::var("CODEX_MONITOR_ORBIT_TOKEN")
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-    let mut orbit_auth_url: Option<String> = env::var("CODEX_MONITOR_ORBIT_AUTH_URL")
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-    let mut orbit_runner_name: Option<String> = env::var("CODEX_MONITOR_ORBIT_RUNNER_NAME")
-        .ok()
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
+        .filter(|value| !value.is_empty())
+}
+
+fn parse_args() -> Result<DaemonConfig, String> {
+    let mut flags = DaemonFlags {
+        token: env_value("CODEX_MONITOR_DAEMON_TOKEN"),
+        orbit_token: env_value("CODEX_MONITOR_ORBIT_TOKEN"),
+        orbit_auth_url: env_value("CODEX_MONITOR_ORBIT_AUTH_URL"),
+        orbit_runner_name: env_value("CODEX_MONITOR_ORBIT_RUNNER_NAME"),
+        ..DaemonFlags::default()
+    };
 
     let mut args = env::args().skip(1);
     while let Some(arg) = args.next() {
@@ -1534,9 +2025,50 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 print!("{}", usage());
                 std::process::exit(0);
             }
+            "--config" => {
+                let value = args.next().ok_or("--config requires a value")?;
+                let trimmed = value.trim();
+                if trimmed.is_empty() {
+                    return Err("--config requires a non-empty value".to_string());
+                }
+                flags.config_path = Some(PathBuf::from(trimmed));
+            }
             "--listen" => {
                 let value = args.next().ok_or("--listen requires a value")?;
-                listen = value.parse::<SocketAddr>().map_err(|err| err.to_string())?;
+                flags.listen = Some(value.parse::<SocketAddr>().map_err(|err| err.to_string())?);
+            }
+            "--listen-unix" => {
+                let value = args.next().ok_or("--listen-unix requires a value")?;
+                let trimmed = value.trim();
+                if trimmed.is_empty() {
+                    return Err("--listen-unix requires a non-empty value".to_string());
+                }
+                flags.listen_unix = Some(PathBuf::from(trimmed));
+            }
+            "--tls-cert" => {
+                let value = args.next().ok_or("--tls-cert requires a value")?;
+                let trimmed = value.trim();
+                if trimmed.is_empty() {
+                    return Err("--tls-cert requires a non-empty value".to_string());
+                }
+                flags.tls_cert = Some(PathBuf::from(trimmed));
+            }
+            "--tls-key" => {
+                let value = args.next().ok_or("--tls-key requires a value")?;
+                let trimmed = value.trim();
+                if trimmed.is_empty() {
+                    return Err("--tls-key requires a non-empty value".to_string());
+                }
+                flags.tls_key = Some(PathBuf::from(trimmed));
+            }
+            "--metrics-listen" => {
+                let value = args.next().ok_or("--metrics-listen requires a value")?;
+                flags.metrics_listen = Some(
+                    value
+                        .trim()
+                        .parse::<SocketAddr>()
+                        .map_err(|err| format!("invalid --metrics-listen: {err}"))?,
+                );
             }
             "--token" => {
                 let value = args.next().ok_or("--token requires a value")?;
@@ -1544,7 +2076,7 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 if trimmed.is_empty() {
                     return Err("--token requires a non-empty value".to_string());
                 }
-                token = Some(trimmed.to_string());
+                flags.token = Some(trimmed.to_string());
             }
             "--data-dir" => {
                 let value = args.next().ok_or("--data-dir requires a value")?;
@@ -1552,11 +2084,11 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 if trimmed.is_empty() {
                     return Err("--data-dir requires a non-empty value".to_string());
                 }
-                data_dir = Some(PathBuf::from(trimmed));
+                flags.data_dir = Some(PathBuf::from(trimmed));
             }
             "--insecure-no-auth" => {
-                insecure_no_auth = true;
-                token = None;
+                flags.insecure_no_auth = true;
+                flags.token = None;
             }
             "--orbit-url" => {
                 let value = args.next().ok_or("--orbit-url requires a value")?;
@@ -1564,7 +2096,7 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 if trimmed.is_empty() {
                     return Err("--orbit-url requires a non-empty value".to_string());
                 }
-                orbit_url = Some(trimmed.to_string());
+                flags.orbit_url = Some(trimmed.to_string());
             }
             "--orbit-token" => {
                 let value = args.next().ok_or("--orbit-token requires a value")?;
@@ -1572,7 +2104,7 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 if trimmed.is_empty() {
                     return Err("--orbit-token requires a non-empty value".to_string());
                 }
-                orbit_token = Some(trimmed.to_string());
+                flags.orbit_token = Some(trimmed.to_string());
             }
             "--orbit-auth-url" => {
                 let value = args.next().ok_or("--orbit-auth-url requires a value")?;
@@ -1580,7 +2112,7 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 if trimmed.is_empty() {
                     return Err("--orbit-auth-url requires a non-empty value".to_string());
                 }
-                orbit_auth_url = Some(trimmed.to_string());
+                flags.orbit_auth_url = Some(trimmed.to_string());
             }
             "--orbit-runner-name" => {
                 let value = args.next().ok_or("--orbit-runner-name requires a value")?;
@@ -1588,14 +2120,73 @@ fn parse_args() -> Result<DaemonConfig, String> {
                 if trimmed.is_empty() {
                     return Err("--orbit-runner-name requires a non-empty value".to_string());
                 }
-                orbit_runner_name = Some(trimmed.to_string());
+                flags.orbit_runner_name = Some(trimmed.to_string());
             }
             _ => return Err(format!("Unknown argument: {arg}")),
         }
     }
 
+    let file = match flags.config_path.as_deref() {
+        Some(path) => config_file::load(path)?,
+        None => ConfigFile::default(),
+    };
+    resolve_config(flags, file)
+}
+
+/// Merges flags over the config file and validates the result. Also used to
+/// re-validate the file on reload.
+fn resolve_config(flags: DaemonFlags, file: ConfigFile) -> Result<DaemonConfig, String> {
+    let listen = match flags.listen.or(file.listen) {
+        Some(listen) => listen,
+        None => DEFAULT_LISTEN_ADDR
+            .parse::<SocketAddr>()
+            .map_err(|err| err.to_string())?,
+    };
+    let listen_unix = flags.listen_unix.clone().or(file.listen_unix);
+    let (file_tls_cert, file_tls_key) = match file.tls {
+        Some(tls) => (Some(tls.cert), Some(tls.key)),
+        None => (None, None),
+    };
+    let tls_cert = flags.tls_cert.clone().or(file_tls_cert);
+    let tls_key = flags.tls_key.clone().or(file_tls_key);
+    let metrics_listen = flags.metrics_listen.or(file.metrics_listen);
+    let insecure_no_auth = flags.insecure_no_auth || file.insecure_no_auth;
+    // `--insecure-no-auth` also drops a token that only the file sets.
+    let token = match flags.token.clone() {
+        Some(token) => Some(token),
+        None if flags.insecure_no_auth => None,
+        None => file.token,
+    };
+    let orbit_url = flags.orbit_url.clone().or(file.orbit.url);
+    let orbit_token = flags.orbit_token.clone().or(file.orbit.token);
+    let orbit_auth_url = flags.orbit_auth_url.clone().or(file.orbit.auth_url);
+    let orbit_runner_name = flags.orbit_runner_name.clone().or(file.orbit.runner_name);
+
     let is_orbit_mode = orbit_url.is_some();
-    if !is_orbit_mode && token.is_none() && !insecure_no_auth {
+    if is_orbit_mode && listen_unix.is_some() {
+        return Err("--listen-unix cannot be combined with --orbit-url".to_string());
+    }
+    if cfg!(not(unix)) && listen_unix.is_some() {
+        return Err("--listen-unix is only supported on Unix platforms".to_string());
+    }
+    if tls_cert.is_some() != tls_key.is_some() {
+        return Err("--tls-cert and --tls-key must be given together".to_string());
+    }
+    if tls_cert.is_some() && (is_orbit_mode || listen_unix.is_some()) {
+        return Err("--tls-cert only applies to the TCP listener".to_string());
+    }
+    let data_dir = flags
+        .data_dir
+        .clone()
+        .or(file.data_dir)
+        .unwrap_or_else(default_data_dir);
+    let has_scoped_tokens = TokenStore::load(tokens::tokens_path(&data_dir))
+        .map(|store| !store.is_empty())
+        .unwrap_or(true);
+    // Socket file permissions are the auth boundary in Unix socket mode.
+    let is_unix_mode = listen_unix.is_some();
+    if !is_orbit_mode && !is_unix_mode && token.is_none() && !insecure_no_auth && !has_scoped_tokens
+    {
         return Err(
             "Missing --token (or set CODEX_MONITOR_DAEMON_TOKEN). Use --insecure-no-auth for local dev only."
                 .to_string(),
@@ -1604,12 +2195,24 @@ fn parse_args() -> Result<DaemonConfig, String> {
 
     Ok(DaemonConfig {
         listen,
-        token,
-        data_dir: data_dir.unwrap_or_else(default_data_dir),
+        listen_unix,
+        tls_cert,
+        tls_key,
+        metrics_listen,
+        data_dir,
         orbit_url,
-        orbit_token,
         orbit_auth_url,
         orbit_runner_name,
+        runtime: RuntimeConfig {
+            token,
+            orbit_token,
+            max_in_flight_rpc_per_connection: file
+                .limits
+                .max_in_flight_rpc_per_connection
+                .unwrap_or(MAX_IN_FLIGHT_RPC_PER_CONNECTION),
+            allowed_workspace_roots: file.workspaces.allowed_roots,
+        },
+        flags,
     })
 }
 
@@ -1655,8 +2258,16 @@ mod tests {
             storage_path: data_dir.join("workspaces.json"),
             settings_path: data_dir.join("settings.json"),
             app_settings: Mutex::new(AppSettings::default()),
-            event_sink: DaemonEventSink { tx },
+            event_sink: DaemonEventSink::new(tx),
             codex_login_cancels: Mutex::new(HashMap::new()),
+            terminal_sessions: Mutex::new(HashMap::new()),
+            git_status_watchers: GitStatusWatchers::default(),
+            tokens: Mutex::new(
+                TokenStore::load(tokens::tokens_path(data_dir)).expect("load token store"),
+            ),
+            audit_log: AuditLog::new(data_dir),
+            metrics: Arc::new(DaemonMetrics::new(MAX_IN_FLIGHT_RPC_PER_CONNECTION)),
+            runtime: StdRwLock::new(RuntimeConfig::default()),
             daemon_mode: "tcp".to_string(),
             daemon_binary_path: Some("/tmp/codex-monitor-daemon".to_string()),
         }
@@ -1707,6 +2318,138 @@ mod tests {
         });
     }
 
+    #[test]
+    fn config_reload_applies_runtime_settings_but_keeps_flag_overrides() {
+        run_async_test(async {
+            let tmp = make_temp_dir("config-reload");
+            let allowed_root = tmp.join("allowed");
+            std::fs::create_dir_all(&allowed_root).expect("create allowed root");
+            let config_path = tmp.join("daemon.toml");
+            std::fs::write(
+                &config_path,
+                format!("token = \"from-file\"\ndata_dir = {:?}\n", tmp),
+            )
+            .expect("write config");
+
+            let flags = DaemonFlags {
+                config_path: Some(config_path.clone()),
+                token: Some("from-flag".to_string()),
+                ..DaemonFlags::default()
+            };
+            let file = config_file::load(&config_path).expect("load config");
+            let config = resolve_config(flags, file).expect("resolve config");
+            assert_eq!(config.data_dir, tmp);
+            assert_eq!(config.runtime.token.as_deref(), Some("from-flag"));
+
+            let state = test_state(&tmp);
+            state.apply_runtime_config(config.runtime.clone());
+            let inside = allowed_root.join("inside");
+            let outside = tmp.join("outside");
+            std::fs::create_dir_all(&inside).expect("create inside workspace");
+            insert_workspace(&state, "ws-inside", &inside.to_string_lossy()).await;
+            insert_workspace(&state, "ws-outside", &outside.to_string_lossy()).await;
+            std::fs::write(
+                &config_path,
+                format!(
+                    "token = \"rotated\"\ndata_dir = {:?}\n\n[limits]\nmax_in_flight_rpc_per_connection = 4\n\n[workspaces]\nallowed_roots = [{:?}]\n",
+                    tmp, allowed_root
+                ),
+            )
+            .expect("rewrite config");
+            config_file::reload(&config, &state).expect("reload config");
+
+            let runtime = state.runtime_config();
+            assert_eq!(runtime.token.as_deref(), Some("from-flag"));
+            assert_eq!(runtime.max_in_flight_rpc_per_connection, 4);
+            assert_eq!(
+                config_file::workspaces_outside_roots(&state).await,
+                vec![format!("ws-outside ({})", outside.to_string_lossy())]
+            );
+            assert_eq!(state.workspaces.lock().await.len(), 2);
+            let err = rpc::handle_rpc_request(
+                &state,
+                "add_workspace",
+                json!({ "path": tmp.to_string_lossy().to_string() }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect_err("workspace outside allowed roots");
+            assert!(err.contains("outside the daemon's allowed workspace roots"));
+            #[cfg(unix)]
+            {
+                std::os::unix::fs::symlink(&outside, allowed_root.join("escape"))
+                    .expect("symlink out of the allowed root");
+                std::fs::create_dir_all(&outside).expect("create outside folder");
+                let err = rpc::handle_rpc_request(
+                    &state,
+                    "add_workspace_from_git_url",
+                    json!({
+                        "url": "https://example.com/repo.git",
+                        "destinationPath": allowed_root.to_string_lossy().to_string(),
+                        "targetFolderName": "escape",
+                    }),
+                    "daemon-test".to_string(),
+                )
+                .await
+                .expect_err("clone target escaping the allowed roots");
+                assert!(err.contains("outside the daemon's allowed workspace roots"));
+            }
+
+            std::fs::write(
+                &config_path,
+                "[limits]\nmax_in_flight_rpc_per_connection = 0\n",
+            )
+            .expect("write invalid config");
+            assert!(config_file::reload(&config, &state).is_err());
+            assert_eq!(state.runtime_config(), runtime);
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
+    #[test]
+    fn rpc_scoped_tokens_limit_methods_and_take_effect_on_revoke() {
+        run_async_test(async {
+            let tmp = make_temp_dir("rpc-scoped-tokens");
+            let state = test_state(&tmp);
+            let minted = rpc::handle_rpc_request(
+                &state,
+                "token_mint",
+                json!({ "name": "watcher", "scope": "read-only" }),
+                "daemon-test".to_string(),
+            )
+            .await
+            .expect("mint token");
+            let secret = minted["token"].as_str().expect("token").to_string();
+
+            let principal = state
+                .tokens
+                .lock()
+                .await
+                .authenticate(None, &secret)
+                .expect("token authenticates");
+            let client = rpc::RpcClient::new("daemon-test".to_string(), "test".to_string());
+            client.set_principal(principal);
+
+            rpc::handle_client_rpc_request(&state, &client, "ping", json!({}))
+                .await
+                .expect("read-only method allowed");
+            let err = rpc::handle_client_rpc_request(&state, &client, "daemon_shutdown", json!({}))
+                .await
+                .expect_err("admin method rejected");
+            assert_eq!(err, "forbidden: `daemon_shutdown` requires the admin scope");
+
+            state
+                .token_revoke("watcher".to_string())
+                .await
+                .expect("revoke token");
+            let err = rpc::handle_client_rpc_request(&state, &client, "ping", json!({}))
+                .await
+                .expect_err("revoked token rejected");
+            assert!(err.starts_with("unauthorized"));
+            let _ = std::fs::remove_dir_all(&tmp);
+        });
+    }
+
     #[test]
     fn rpc_prompts_list_reads_workspace_prompts() {
         run_async_test(async {
@@ -1796,6 +2539,17 @@ mod tests {
 }
 
 fn main() {
+    if let Some(command) = env::args()
+        .nth(1)
+        .filter(|arg| service::is_service_command(arg))
+    {
+        if let Err(err) = service::run(&command, env::args().skip(2).collect()) {
+            eprintln!("{err}\n\n{}", usage());
+            std::process::exit(2);
+        }
+        return;
+    }
+
     let config = match parse_args() {
         Ok(config) => config,
         Err(err) => {
@@ -1811,12 +2565,34 @@ fn main() {
 
     runtime.block_on(async move {
         let (events_tx, _events_rx) = broadcast::channel::<DaemonEvent>(2048);
-        let event_sink = DaemonEventSink {
-            tx: events_tx.clone(),
-        };
+        let event_sink = DaemonEventSink::new(events_tx.clone());
         let state = Arc::new(DaemonState::load(&config, event_sink));
         let config = Arc::new(config);
 
+        if let Some(metrics_addr) = config.metrics_listen {
+            let listener = match TcpListener::bind(metrics_addr).await {
+                Ok(listener) => listener,
+                Err(err) => {
+                    eprintln!("failed to bind metrics listener {metrics_addr}: {err}");
+                    std::process::exit(2);
+                }
+            };
+            eprintln!("metrics endpoint listening on http://{metrics_addr}/metrics");
+            tokio::spawn(metrics::run_metrics_listener(listener, Arc::clone(&state)));
+        }
+
+        {
+            let state = Arc::clone(&state);
+            tokio::spawn(async move { state.watch_process_limits().await });
+        }
+
+        if config.flags.config_path.is_some() {
+            tokio::spawn(config_file::reload_on_sighup(
+                Arc::clone(&config),
+                Arc::clone(&state),
+            ));
+        }
+
         if config.orbit_url.is_some() {
             eprintln!(
                 "codex-monitor-daemon orbit mode (data dir: {})",
@@ -1830,6 +2606,23 @@ fn main() {
             return;
         }
 
+        #[cfg(unix)]
+        if let Some(socket_path) = config.listen_unix.clone() {
+            transport::run_unix_listener(&socket_path, state, events_tx).await;
+            return;
+        }
+
+        let tls = match (&config.tls_cert, &config.tls_key) {
+            (Some(cert_path), Some(key_path)) => match tls::load_tls(cert_path, key_path) {
+                Ok(tls) => Some(tls),
+                Err(err) => {
+                    eprintln!("{err}");
+                    std::process::exit(2);
+                }
+            },
+            _ => None,
+        };
+
         let listener = match TcpListener::bind(config.listen).await {
             Ok(listener) => listener,
             Err(err) => {
@@ -1838,23 +2631,41 @@ fn main() {
             }
         };
         eprintln!(
-            "codex-monitor-daemon listening on {} (data dir: {})",
+            "codex-monitor-daemon listening on {}{} (data dir: {})",
             config.listen,
+            if tls.is_some() { " over TLS" } else { "" },
             state
                 .storage_path
                 .parent()
                 .unwrap_or(&state.storage_path)
                 .display()
         );
+        if let Some(tls) = &tls {
+            // Clients pin this value as remoteBackendTlsFingerprint.
+            eprintln!("TLS certificate SHA-256 fingerprint: {}", tls.fingerprint);
+        }
 
         loop {
             match listener.accept().await {
-                Ok((socket, _addr)) => {
-                    let config = Arc::clone(&config);
+                Ok((socket, addr)) => {
                     let state = Arc::clone(&state);
                     let events = events_tx.clone();
+                    let peer = addr.to_string();
+                    let Some(acceptor) = tls.as_ref().map(|tls| tls.acceptor.clone()) else {
+                        tokio::spawn(async move {
+                            transport::handle_client(socket, peer, state, events).await;
+                        });
+                        continue;
+                    };
                     tokio::spawn(async move {
-                        transport::handle_client(socket, config, state, events).await;
+                        let handshake = acceptor.accept(socket);
+                        match tokio::time::timeout(tls::TLS_HANDSHAKE_TIMEOUT, handshake).await {
+                            Ok(Ok(stream)) => {
+                                transport::handle_client(stream, peer, state, events).await;
+                            }
+                            Ok(Err(err)) => eprintln!("[daemon] TLS handshake failed: {err}"),
+                            Err(_) => eprintln!("[daemon] TLS handshake timed out"),
+                        }
                     });
                 }
                 Err(_) => continue,
@@ -0,0 +1,347 @@
+//! Append-only record of mutating RPCs: who called what, against which
+//! workspace, and whether it succeeded. Uses the same JSONL rotation as the
+//! structured log and event journal.
+
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde_json::{json, Map, Value};
+
+use crate::shared::logging_core::{jsonl_file_path, rotate_jsonl_file_if_needed};
+
+const AUDIT_DIR_NAME: &str = "audit";
+const AUDIT_FILE_BASENAME: &str = "audit";
+const MAX_AUDIT_FILE_BYTES: u64 = 5 * 1024 * 1024;
+const MAX_AUDIT_FILES_TOTAL: usize = 5;
+const DEFAULT_QUERY_LIMIT: usize = 200;
+pub(super) const MAX_QUERY_LIMIT: u64 = 2000;
+const MAX_PARAM_STRING_CHARS: usize = 256;
+const MAX_PARAM_DEPTH: usize = 6;
+const REDACTED: &str = "[redacted]";
+const SECRET_KEY_MARKERS: [&str; 5] = ["token", "secret", "password", "authorization", "apikey"];
+
+/// Keystroke-level calls would drown everything else; `terminal_open` and
+/// `terminal_close` are still recorded.
+const UNAUDITED_METHODS: [&str; 2] = ["terminal_write", "terminal_resize"];
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct AuditRecord<'a> {
+    timestamp_ms: u128,
+    peer: &'a str,
+    /// Scoped token name; `None` for the shared token or unauthenticated
+    /// listeners.
+    token: Option<&'a str>,
+    method: &'a str,
+    workspace_id: Option<&'a str>,
+    params: Value,
+    status: &'a str,
+    error: Option<&'a str>,
+}
+
+pub(super) struct AuditEntry<'a> {
+    pub(super) peer: &'a str,
+    pub(super) token: Option<&'a str>,
+    pub(super) method: &'a str,
+    pub(super) params: &'a Value,
+    pub(super) result: &'a Result<Value, String>,
+}
+
+#[derive(Debug, Default, PartialEq)]
+pub(super) struct AuditQuery {
+    pub(super) method: Option<String>,
+    pub(super) workspace_id: Option<String>,
+    pub(super) since_ms: Option<u64>,
+    pub(super) until_ms: Option<u64>,
+    pub(super) limit: Option<u64>,
+}
+
+#[derive(Clone)]
+pub(super) struct AuditLog {
+    dir: PathBuf,
+    write_lock: Arc<StdMutex<()>>,
+}
+
+impl AuditLog {
+    pub(super) fn new(data_dir: &Path) -> Self {
+        Self {
+            dir: data_dir.join(AUDIT_DIR_NAME),
+            write_lock: Arc::new(StdMutex::new(())),
+        }
+    }
+
+    /// Best-effort append; a failing audit write must not fail the RPC. The
+    /// record is serialized here and written on the blocking pool, since
+    /// rotation and the append itself are synchronous file IO.
+    pub(super) async fn record(&self, entry: AuditEntry<'_>) {
+        if UNAUDITED_METHODS.contains(&entry.method) {
+            return;
+        }
+        let written = match serialize_entry(entry) {
+            Ok(line) => {
+                let log = self.clone();
+                tokio::task::spawn_blocking(move || log.append_line(&line))
+                    .await
+                    .unwrap_or_else(|err| Err(format!("failed to join audit write task: {err}")))
+            }
+            Err(err) => Err(err),
+        };
+        if let Err(err) = written {
+            eprintln!("[daemon] failed to write audit log: {err}");
+        }
+    }
+
+    fn append_line(&self, line: &str) -> Result<(), String> {
+        let _guard = self
+            .write_lock
+            .lock()
+            .map_err(|_| "audit log lock poisoned".to_string())?;
+        std::fs::create_dir_all(&self.dir)
+            .map_err(|err| format!("failed to create audit log dir: {err}"))?;
+        rotate_jsonl_file_if_needed(
+            &self.dir,
+            AUDIT_FILE_BASENAME,
+            MAX_AUDIT_FILE_BYTES,
+            MAX_AUDIT_FILES_TOTAL,
+        )?;
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(jsonl_file_path(&self.dir, AUDIT_FILE_BASENAME, None))
+            .map_err(|err| format!("failed to open audit log: {err}"))?;
+        file.write_all(line.as_bytes())
+            .map_err(|err| format!("failed to write audit record: {err}"))
+    }
+
+    /// Matching records, newest first.
+    pub(super) fn query(&self, query: &AuditQuery) -> Result<Value, String> {
+        let limit = query
+            .limit
+            .map(|limit| limit.clamp(1, MAX_QUERY_LIMIT) as usize)
+            .unwrap_or(DEFAULT_QUERY_LIMIT);
+        // Hold the lock so rotation cannot rename files out from under us.
+        let _guard = self
+            .write_lock
+            .lock()
+            .map_err(|_| "audit log lock poisoned".to_string())?;
+        let mut entries: Vec<Value> = Vec::new();
+        let mut has_more = false;
+        for path in self.files_newest_first() {
+            let Ok(file) = File::open(&path) else {
+                continue;
+            };
+            let mut matches: Vec<Value> = BufReader::new(file)
+                .lines()
+                .map_while(Result::ok)
+                .filter_map(|line| serde_json::from_str::<Value>(line.trim()).ok())
+                .filter(|record| matches_query(record, query))
+                .collect();
+            matches.reverse();
+            for record in matches {
+                if entries.len() >= limit {
+                    has_more = true;
+                    break;
+                }
+                entries.push(record);
+            }
+            if has_more {
+                break;
+            }
+        }
+        Ok(json!({ "entries": entries, "hasMore": has_more }))
+    }
+
+    fn files_newest_first(&self) -> Vec<PathBuf> {
+        let mut files = vec![jsonl_file_path(&self.dir, AUDIT_FILE_BASENAME, None)];
+        files.extend(
+            (1..MAX_AUDIT_FILES_TOTAL)
+                .map(|index| jsonl_file_path(&self.dir, AUDIT_FILE_BASENAME, Some(index))),
+        );
+        files.retain(|path| path.exists());
+        files
+    }
+}
+
+fn matches_query(record: &Value, query: &AuditQuery) -> bool {
+    if let Some(method) = query.method.as_deref() {
+        if record.get("method").and_then(Value::as_str) != Some(method) {
+            return false;
+        }
+    }
+    if let Some(workspace_id) = query.workspace_id.as_deref() {
+        if record.get("workspaceId").and_then(Value::as_str) != Some(workspace_id) {
+            return false;
+        }
+    }
+    let timestamp = record
+        .get("timestampMs")
+        .and_then(Value::as_u64)
+        .unwrap_or(0);
+    query.since_ms.is_none_or(|since| timestamp >= since)
+        && query.until_ms.is_none_or(|until| timestamp <= until)
+}
+
+fn serialize_entry(entry: AuditEntry<'_>) -> Result<String, String> {
+    let (status, error) = match entry.result {
+        Ok(_) => ("ok", None),
+        Err(message) => ("error", Some(message.as_str())),
+    };
+    let record = AuditRecord {
+        timestamp_ms: now_millis(),
+        peer: entry.peer,
+        token: entry.token,
+        method: entry.method,
+        workspace_id: workspace_id_of(entry.params),
+        params: sanitize_params(entry.params, 0),
+        status,
+        error,
+    };
+    let mut line = serde_json::to_string(&record)
+        .map_err(|err| format!("failed to serialize audit record: {err}"))?;
+    line.push('\n');
+    Ok(line)
+}
+
+fn workspace_id_of(params: &Value) -> Option<&str> {
+    ["workspaceId", "workspace_id", "sourceWorkspaceId", "id"]
+        .iter()
+        .find_map(|key| params.get(*key).and_then(Value::as_str))
+}
+
+/// Redacts credential-looking keys and truncates long strings (file
+/// contents, prompts) so the audit log records intent, not payloads.
+fn sanitize_params(value: &Value, depth: usize) -> Value {
+    if depth >= MAX_PARAM_DEPTH {
+        return Value::String("[truncated]".to_string());
+    }
+    match value {
+        Value::Object(map) => {
+            let mut sanitized = Map::new();
+            for (key, value) in map {
+                let lower = key.to_ascii_lowercase();
+                let value = if SECRET_KEY_MARKERS
+                    .iter()
+                    .any(|marker| lower.contains(marker))
+                {
+                    Value::String(REDACTED.to_string())
+                } else {
+                    sanitize_params(value, depth + 1)
+                };
+                sanitized.insert(key.clone(), value);
+            }
+            Value::Object(sanitized)
+        }
+        Value::Array(items) => Value::Array(
+            items
+                .iter()
+                .map(|item| sanitize_params(item, depth + 1))
+                .collect(),
+        ),
+        Value::String(text) => {
+            let char_count = text.chars().count();
+            if char_count <= MAX_PARAM_STRING_CHARS {
+                return value.clone();
+            }
+            let prefix: String = text.chars().take(MAX_PARAM_STRING_CHARS).collect();
+            Value::String(format!("{prefix}… ({char_count} chars)"))
+        }
+        _ => value.clone(),
+    }
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sanitize_params, AuditEntry, AuditLog, AuditQuery};
+    use serde_json::json;
+
+    #[test]
+    fn query_filters_by_method_and_workspace_newest_first() {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-audit-{}", uuid::Uuid::new_v4()));
+        let log = AuditLog::new(&dir);
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime");
+        let ok = Ok(json!({ "ok": true }));
+        let failed = Err("boom".to_string());
+        for (method, workspace_id, result) in [
+            ("remove_workspace", "ws-1", &ok),
+            ("revert_git_all", "ws-1", &failed),
+            ("revert_git_all", "ws-2", &ok),
+            ("terminal_write", "ws-1", &ok),
+        ] {
+            runtime.block_on(log.record(AuditEntry {
+                peer: "127.0.0.1:50000",
+                token: Some("teammate"),
+                method,
+                params: &json!({ "workspaceId": workspace_id }),
+                result,
+            }));
+        }
+
+        let all = log.query(&AuditQuery::default()).expect("query");
+        let entries = all["entries"].as_array().expect("entries");
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0]["workspaceId"], "ws-2");
+        assert_eq!(entries[2]["method"], "remove_workspace");
+        assert_eq!(entries[2]["token"], "teammate");
+
+        let filtered = log
+            .query(&AuditQuery {
+                method: Some("revert_git_all".to_string()),
+                workspace_id: Some("ws-1".to_string()),
+                ..AuditQuery::default()
+            })
+            .expect("query");
+        let entries = filtered["entries"].as_array().expect("entries");
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0]["status"], "error");
+        assert_eq!(entries[0]["error"], "boom");
+
+        let limited = log
+            .query(&AuditQuery {
+                limit: Some(1),
+                ..AuditQuery::default()
+            })
+            .expect("query");
+        assert_eq!(limited["hasMore"], true);
+
+        let future = log
+            .query(&AuditQuery {
+                since_ms: Some(u64::MAX),
+                ..AuditQuery::default()
+            })
+            .expect("query");
+        assert_eq!(future["entries"].as_array().map(Vec::len), Some(0));
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn sanitize_params_redacts_secrets_and_truncates_payloads() {
+        let sanitized = sanitize_params(
+            &json!({
+                "workspaceId": "ws-1",
+                "settings": { "remoteBackendToken": "secret", "theme": "dark" },
+                "content": "x".repeat(1000),
+            }),
+            0,
+        );
+        assert_eq!(sanitized["workspaceId"], "ws-1");
+        assert_eq!(sanitized["settings"]["remoteBackendToken"], "[redacted]");
+        assert_eq!(sanitized["settings"]["theme"], "dark");
+        let content = sanitized["content"].as_str().expect("content");
+        assert!(content.ends_with("(1000 chars)"));
+        assert!(content.len() < 300);
+    }
+}
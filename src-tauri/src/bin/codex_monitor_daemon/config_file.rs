@@ -0,0 +1,338 @@
+//! `--config <path>` TOML file. Flags and `CODEX_MONITOR_*` environment
+//! variables take precedence over it. On Unix, SIGHUP re-reads the file and
+//! applies the settings that do not need a new listener.
+
+use std::net::SocketAddr;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+
+use serde::Deserialize;
+
+use super::{DaemonConfig, DaemonState};
+
+/// Upper bound for `limits.max_in_flight_rpc_per_connection`.
+const MAX_IN_FLIGHT_RPC_CEILING: usize = 1024;
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(super) struct ConfigFile {
+    pub(super) listen: Option<SocketAddr>,
+    pub(super) listen_unix: Option<PathBuf>,
+    pub(super) metrics_listen: Option<SocketAddr>,
+    pub(super) data_dir: Option<PathBuf>,
+    pub(super) token: Option<String>,
+    pub(super) insecure_no_auth: bool,
+    pub(super) tls: Option<TlsSection>,
+    pub(super) orbit: OrbitSection,
+    pub(super) limits: LimitsSection,
+    pub(super) workspaces: WorkspacesSection,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub(super) struct TlsSection {
+    pub(super) cert: PathBuf,
+    pub(super) key: PathBuf,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(super) struct OrbitSection {
+    pub(super) url: Option<String>,
+    pub(super) token: Option<String>,
+    pub(super) auth_url: Option<String>,
+    pub(super) runner_name: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(super) struct LimitsSection {
+    pub(super) max_in_flight_rpc_per_connection: Option<usize>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub(super) struct WorkspacesSection {
+    /// When non-empty, workspaces can only be added below these folders.
+    pub(super) allowed_roots: Vec<PathBuf>,
+}
+
+pub(super) fn load(path: &Path) -> Result<ConfigFile, String> {
+    let raw = std::fs::read_to_string(path)
+        .map_err(|err| format!("failed to read config {}: {err}", path.display()))?;
+    parse(&raw).map_err(|err| format!("invalid config {}: {err}", path.display()))
+}
+
+fn parse(raw: &str) -> Result<ConfigFile, String> {
+    let mut file: ConfigFile = toml::from_str(raw).map_err(|err| err.to_string())?;
+    file.validate()?;
+    Ok(file)
+}
+
+fn non_empty(value: &mut Option<String>, key: &str) -> Result<(), String> {
+    if let Some(raw) = value.as_mut() {
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Err(format!("`{key}` must not be empty"));
+        }
+        *raw = trimmed.to_string();
+    }
+    Ok(())
+}
+
+fn non_empty_path(value: Option<&PathBuf>, key: &str) -> Result<(), String> {
+    match value {
+        Some(path) if path.as_os_str().is_empty() => Err(format!("`{key}` must not be empty")),
+        _ => Ok(()),
+    }
+}
+
+impl ConfigFile {
+    fn validate(&mut self) -> Result<(), String> {
+        non_empty(&mut self.token, "token")?;
+        non_empty(&mut self.orbit.url, "orbit.url")?;
+        non_empty(&mut self.orbit.token, "orbit.token")?;
+        non_empty(&mut self.orbit.auth_url, "orbit.auth_url")?;
+        non_empty(&mut self.orbit.runner_name, "orbit.runner_name")?;
+        non_empty_path(self.listen_unix.as_ref(), "listen_unix")?;
+        non_empty_path(self.data_dir.as_ref(), "data_dir")?;
+        if let Some(tls) = &self.tls {
+            non_empty_path(Some(&tls.cert), "tls.cert")?;
+            non_empty_path(Some(&tls.key), "tls.key")?;
+        }
+        if let Some(limit) = self.limits.max_in_flight_rpc_per_connection {
+            if limit == 0 || limit > MAX_IN_FLIGHT_RPC_CEILING {
+                return Err(format!(
+                    "`limits.max_in_flight_rpc_per_connection` must be between 1 and {MAX_IN_FLIGHT_RPC_CEILING}"
+                ));
+            }
+        }
+        if let Some(root) = self
+            .workspaces
+            .allowed_roots
+            .iter()
+            .find(|root| !root.is_absolute())
+        {
+            return Err(format!(
+                "`workspaces.allowed_roots` entries must be absolute paths: {}",
+                root.display()
+            ));
+        }
+        if self.token.is_some() && self.insecure_no_auth {
+            return Err("`token` cannot be combined with `insecure_no_auth`".to_string());
+        }
+        Ok(())
+    }
+}
+
+/// Settings a running daemon picks up again on SIGHUP.
+#[derive(Debug, Clone, PartialEq)]
+pub(super) struct RuntimeConfig {
+    pub(super) token: Option<String>,
+    pub(super) orbit_token: Option<String>,
+    pub(super) max_in_flight_rpc_per_connection: usize,
+    pub(super) allowed_workspace_roots: Vec<PathBuf>,
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            token: None,
+            orbit_token: None,
+            max_in_flight_rpc_per_connection: super::MAX_IN_FLIGHT_RPC_PER_CONNECTION,
+            allowed_workspace_roots: Vec::new(),
+        }
+    }
+}
+
+impl RuntimeConfig {
+    /// Rejects workspace folders outside `workspaces.allowed_roots`.
+    pub(super) fn check_workspace_path(&self, path: &str) -> Result<(), String> {
+        if self.allowed_workspace_roots.is_empty() {
+            return Ok(());
+        }
+        let path = resolve_path(Path::new(path.trim()));
+        let allowed = self
+            .allowed_workspace_roots
+            .iter()
+            .any(|root| path.starts_with(resolve_path(root)));
+        if allowed {
+            Ok(())
+        } else {
+            Err(format!(
+                "{} is outside the daemon's allowed workspace roots",
+                path.display()
+            ))
+        }
+    }
+}
+
+/// Canonical form when the path exists; otherwise the path with `.` and `..`
+/// folded lexically so a missing folder cannot climb out of a root.
+fn resolve_path(path: &Path) -> PathBuf {
+    if let Ok(canonical) = path.canonicalize() {
+        return canonical;
+    }
+    let mut resolved = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                resolved.pop();
+            }
+            other => resolved.push(other),
+        }
+    }
+    resolved
+}
+
+/// Names of the settings in `next` that only take effect after a restart.
+fn restart_only_changes(current: &DaemonConfig, next: &DaemonConfig) -> Vec<&'static str> {
+    let mut changed = Vec::new();
+    if current.listen != next.listen {
+        changed.push("listen");
+    }
+    if current.listen_unix != next.listen_unix {
+        changed.push("listen_unix");
+    }
+    if current.tls_cert != next.tls_cert || current.tls_key != next.tls_key {
+        changed.push("tls");
+    }
+    if current.metrics_listen != next.metrics_listen {
+        changed.push("metrics_listen");
+    }
+    if current.data_dir != next.data_dir {
+        changed.push("data_dir");
+    }
+    if current.orbit_url != next.orbit_url
+        || current.orbit_auth_url != next.orbit_auth_url
+        || current.orbit_runner_name != next.orbit_runner_name
+    {
+        changed.push("orbit");
+    }
+    changed
+}
+
+/// Re-reads the config file and applies its runtime settings. A file that
+/// fails to load or validate leaves the running settings untouched.
+pub(super) fn reload(config: &DaemonConfig, state: &DaemonState) -> Result<(), String> {
+    let Some(path) = config.flags.config_path.as_deref() else {
+        return Err("no --config file to reload".to_string());
+    };
+    let next = super::resolve_config(config.flags.clone(), load(path)?)?;
+    let ignored = restart_only_changes(config, &next);
+    if !ignored.is_empty() {
+        eprintln!(
+            "[daemon] config reload ignores changes to {} until restart",
+            ignored.join(", ")
+        );
+    }
+    state.apply_runtime_config(next.runtime);
+    Ok(())
+}
+
+#[cfg(unix)]
+pub(super) async fn reload_on_sighup(config: Arc<DaemonConfig>, state: Arc<DaemonState>) {
+    use tokio::signal::unix::{signal, SignalKind};
+
+    let mut hangups = match signal(SignalKind::hangup()) {
+        Ok(stream) => stream,
+        Err(err) => {
+            eprintln!("[daemon] failed to listen for SIGHUP: {err}");
+            return;
+        }
+    };
+    while hangups.recv().await.is_some() {
+        match reload(&config, &state) {
+            Ok(()) => {
+                eprintln!("[daemon] reloaded config");
+                for workspace in workspaces_outside_roots(&state).await {
+                    eprintln!(
+                        "[daemon] workspace {workspace} is outside workspaces.allowed_roots; it stays registered until removed"
+                    );
+                }
+            }
+            Err(err) => eprintln!("[daemon] config reload failed: {err}"),
+        }
+    }
+}
+
+/// `allowed_roots` only gates new registrations. Workspaces registered before
+/// a reload narrowed the roots are kept and reported as `id (path)` instead.
+pub(super) async fn workspaces_outside_roots(state: &DaemonState) -> Vec<String> {
+    let runtime = state.runtime_config();
+    let mut outside: Vec<String> = state
+        .workspaces
+        .lock()
+        .await
+        .values()
+        .filter(|entry| runtime.check_workspace_path(&entry.path).is_err())
+        .map(|entry| format!("{} ({})", entry.id, entry.path))
+        .collect();
+    outside.sort();
+    outside
+}
+
+#[cfg(not(unix))]
+pub(super) async fn reload_on_sighup(_config: Arc<DaemonConfig>, _state: Arc<DaemonState>) {}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, RuntimeConfig};
+    use std::path::PathBuf;
+
+    #[test]
+    fn parse_reads_sections_and_rejects_bad_values() {
+        let file = parse(
+            r#"
+listen = "0.0.0.0:4732"
+token = "  secret  "
+
+[orbit]
+runner_name = "ci"
+
+[limits]
+max_in_flight_rpc_per_connection = 8
+
+[workspaces]
+allowed_roots = ["/srv/code"]
+"#,
+        )
+        .expect("valid config");
+        assert_eq!(file.listen, Some("0.0.0.0:4732".parse().unwrap()));
+        assert_eq!(file.token.as_deref(), Some("secret"));
+        assert_eq!(file.orbit.runner_name.as_deref(), Some("ci"));
+        assert_eq!(file.limits.max_in_flight_rpc_per_connection, Some(8));
+        assert_eq!(
+            file.workspaces.allowed_roots,
+            vec![PathBuf::from("/srv/code")]
+        );
+
+        assert!(parse("listen = \"nope\"").is_err());
+        assert!(parse("lisen = \"127.0.0.1:1\"").is_err());
+        assert!(parse("token = \" \"").is_err());
+        assert!(parse("[limits]\nmax_in_flight_rpc_per_connection = 0").is_err());
+        assert!(parse("[workspaces]\nallowed_roots = [\"relative\"]").is_err());
+        assert!(parse("token = \"x\"\ninsecure_no_auth = true").is_err());
+    }
+
+    #[test]
+    fn allowed_roots_reject_paths_that_escape() {
+        let open = RuntimeConfig::default();
+        assert!(open.check_workspace_path("/anywhere").is_ok());
+
+        let restricted = RuntimeConfig {
+            allowed_workspace_roots: vec![PathBuf::from("/codex-monitor-test-root")],
+            ..RuntimeConfig::default()
+        };
+        assert!(restricted
+            .check_workspace_path("/codex-monitor-test-root/app")
+            .is_ok());
+        assert!(restricted
+            .check_workspace_path("/codex-monitor-test-root/../etc")
+            .is_err());
+        assert!(restricted
+            .check_workspace_path("/codex-monitor-test-rootless")
+            .is_err());
+    }
+}
@@ -9,5 +9,6 @@ pub(crate) fn daemon_info(mode: &str, binary_path: Option<&str>) -> Value {
         "pid": std::process::id(),
         "mode": mode,
         "binaryPath": binary_path,
+        "managedBy": super::service::service_manager(),
     })
 }
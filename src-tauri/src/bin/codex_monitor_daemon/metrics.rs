@@ -0,0 +1,573 @@
+//! In-process counters behind the `daemon_metrics` RPC and the optional
+//! `--metrics-listen` Prometheus endpoint. Everything is kept in memory and
+//! resets when the daemon restarts.
+
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use serde::Serialize;
+use serde_json::Value;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::Semaphore;
+use tokio::time::Instant;
+
+use crate::backend::background_callbacks::BackgroundCallbackMetrics;
+
+use super::{tokens, DaemonState};
+
+/// Upper bounds, in seconds, of the RPC latency histogram buckets.
+const LATENCY_BUCKETS_SECONDS: [f64; 12] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 30.0,
+];
+/// Label for calls to methods the daemon does not dispatch, and for calls
+/// rejected before dispatch, so arbitrary client input cannot grow the
+/// histogram map.
+const UNKNOWN_METHOD_LABEL: &str = "unknown";
+const HTTP_READ_TIMEOUT: Duration = Duration::from_secs(5);
+const MAX_HTTP_REQUEST_BYTES: usize = 8 * 1024;
+const PROMETHEUS_CONTENT_TYPE: &str = "text/plain; version=0.0.4; charset=utf-8";
+
+#[derive(Default)]
+struct LatencyHistogram {
+    /// Non-cumulative counts per bucket; slower calls only add to `count`.
+    buckets: [u64; LATENCY_BUCKETS_SECONDS.len()],
+    count: u64,
+    errors: u64,
+    sum_seconds: f64,
+}
+
+struct ConnectionState {
+    peer: String,
+    connected_at: Instant,
+    request_limiter: Arc<Semaphore>,
+    /// Permits `request_limiter` started with; the configured limit may have
+    /// been reloaded since.
+    request_limit: usize,
+    events_dropped: AtomicU64,
+}
+
+pub(super) struct DaemonMetrics {
+    started_at: Instant,
+    max_in_flight_per_connection: AtomicUsize,
+    next_connection_id: AtomicU64,
+    connections_total: AtomicU64,
+    events_dropped_total: AtomicU64,
+    connections: StdMutex<BTreeMap<u64, Arc<ConnectionState>>>,
+    rpc_latency: StdMutex<BTreeMap<String, LatencyHistogram>>,
+}
+
+/// Registration of one client connection; dropping it removes the
+/// connection from the snapshot.
+pub(super) struct ConnectionMetrics {
+    id: u64,
+    state: Arc<ConnectionState>,
+    metrics: Arc<DaemonMetrics>,
+}
+
+impl ConnectionMetrics {
+    /// Events this connection missed because its broadcast receiver lagged.
+    pub(super) fn record_events_dropped(&self, count: u64) {
+        self.state
+            .events_dropped
+            .fetch_add(count, Ordering::Relaxed);
+        self.metrics
+            .events_dropped_total
+            .fetch_add(count, Ordering::Relaxed);
+    }
+}
+
+impl Drop for ConnectionMetrics {
+    fn drop(&mut self) {
+        if let Ok(mut connections) = self.metrics.connections.lock() {
+            connections.remove(&self.id);
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ConnectionSnapshot {
+    id: u64,
+    peer: String,
+    connected_seconds: u64,
+    in_flight_rpcs: usize,
+    events_dropped: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct SessionSnapshot {
+    pub(super) workspace_id: String,
+    pub(super) uptime_seconds: u64,
+    pub(super) restart_count: u32,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct LatencyBucket {
+    le: f64,
+    /// Cumulative, as in Prometheus.
+    count: u64,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct RpcLatencySnapshot {
+    method: String,
+    count: u64,
+    errors: u64,
+    sum_seconds: f64,
+    buckets: Vec<LatencyBucket>,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct MetricsSnapshot {
+    uptime_seconds: u64,
+    connected_clients: usize,
+    connections_total: u64,
+    max_in_flight_rpc_per_connection: usize,
+    events_dropped_total: u64,
+    connections: Vec<ConnectionSnapshot>,
+    sessions: Vec<SessionSnapshot>,
+    rpc_latency: Vec<RpcLatencySnapshot>,
+    background_callbacks: BackgroundCallbackMetrics,
+}
+
+impl DaemonMetrics {
+    pub(super) fn new(max_in_flight_per_connection: usize) -> Self {
+        Self {
+            started_at: Instant::now(),
+            max_in_flight_per_connection: AtomicUsize::new(max_in_flight_per_connection),
+            next_connection_id: AtomicU64::new(1),
+            connections_total: AtomicU64::new(0),
+            events_dropped_total: AtomicU64::new(0),
+            connections: StdMutex::new(BTreeMap::new()),
+            rpc_latency: StdMutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Limit reported for connections opened from now on.
+    pub(super) fn set_max_in_flight_per_connection(&self, limit: usize) {
+        self.max_in_flight_per_connection
+            .store(limit, Ordering::Relaxed);
+    }
+
+    pub(super) fn register_connection(
+        self: &Arc<Self>,
+        peer: String,
+        request_limiter: Arc<Semaphore>,
+        request_limit: usize,
+    ) -> ConnectionMetrics {
+        let id = self.next_connection_id.fetch_add(1, Ordering::Relaxed);
+        self.connections_total.fetch_add(1, Ordering::Relaxed);
+        let state = Arc::new(ConnectionState {
+            peer,
+            connected_at: Instant::now(),
+            request_limiter,
+            request_limit,
+            events_dropped: AtomicU64::new(0),
+        });
+        if let Ok(mut connections) = self.connections.lock() {
+            connections.insert(id, Arc::clone(&state));
+        }
+        ConnectionMetrics {
+            id,
+            state,
+            metrics: Arc::clone(self),
+        }
+    }
+
+    pub(super) fn record_rpc(
+        &self,
+        method: &str,
+        elapsed: Duration,
+        result: &Result<Value, String>,
+    ) {
+        let rejected = matches!(
+            result,
+            Err(message) if message.starts_with("forbidden:") || message.starts_with("unauthorized")
+        );
+        let label = if tokens::method_scope(method).is_some() && !rejected {
+            method
+        } else {
+            UNKNOWN_METHOD_LABEL
+        };
+        let seconds = elapsed.as_secs_f64();
+        let Ok(mut histograms) = self.rpc_latency.lock() else {
+            return;
+        };
+        let histogram = histograms.entry(label.to_string()).or_default();
+        if let Some(index) = LATENCY_BUCKETS_SECONDS
+            .iter()
+            .position(|bound| seconds <= *bound)
+        {
+            histogram.buckets[index] += 1;
+        }
+        histogram.count += 1;
+        histogram.sum_seconds += seconds;
+        if result.is_err() {
+            histogram.errors += 1;
+        }
+    }
+
+    pub(super) fn snapshot(
+        &self,
+        sessions: Vec<SessionSnapshot>,
+        background_callbacks: BackgroundCallbackMetrics,
+    ) -> MetricsSnapshot {
+        let connections: Vec<ConnectionSnapshot> = self
+            .connections
+            .lock()
+            .map(|connections| {
+                connections
+                    .iter()
+                    .map(|(id, connection)| ConnectionSnapshot {
+                        id: *id,
+                        peer: connection.peer.clone(),
+                        connected_seconds: connection.connected_at.elapsed().as_secs(),
+                        in_flight_rpcs: connection
+                            .request_limit
+                            .saturating_sub(connection.request_limiter.available_permits()),
+                        events_dropped: connection.events_dropped.load(Ordering::Relaxed),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let rpc_latency = self
+            .rpc_latency
+            .lock()
+            .map(|histograms| {
+                histograms
+                    .iter()
+                    .map(|(method, histogram)| RpcLatencySnapshot {
+                        method: method.clone(),
+                        count: histogram.count,
+                        errors: histogram.errors,
+                        sum_seconds: histogram.sum_seconds,
+                        buckets: LATENCY_BUCKETS_SECONDS
+                            .iter()
+                            .zip(histogram.buckets.iter())
+                            .scan(0, |cumulative, (le, count)| {
+                                *cumulative += count;
+                                Some(LatencyBucket {
+                                    le: *le,
+                                    count: *cumulative,
+                                })
+                            })
+                            .collect(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        MetricsSnapshot {
+            uptime_seconds: self.started_at.elapsed().as_secs(),
+            connected_clients: connections.len(),
+            connections_total: self.connections_total.load(Ordering::Relaxed),
+            max_in_flight_rpc_per_connection: self
+                .max_in_flight_per_connection
+                .load(Ordering::Relaxed),
+            events_dropped_total: self.events_dropped_total.load(Ordering::Relaxed),
+            connections,
+            sessions,
+            rpc_latency,
+            background_callbacks,
+        }
+    }
+}
+
+fn escape_label(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
+
+fn write_metric_header(out: &mut String, name: &str, kind: &str, help: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+/// Renders a snapshot in the Prometheus text exposition format.
+pub(super) fn render_prometheus(snapshot: &MetricsSnapshot) -> String {
+    let mut out = String::new();
+    let scalars: [(&str, &str, &str, f64); 5] = [
+        (
+            "codex_monitor_daemon_uptime_seconds",
+            "gauge",
+            "Seconds since the daemon started.",
+            snapshot.uptime_seconds as f64,
+        ),
+        (
+            "codex_monitor_daemon_connected_clients",
+            "gauge",
+            "Currently connected clients.",
+            snapshot.connected_clients as f64,
+        ),
+        (
+            "codex_monitor_daemon_connections_total",
+            "counter",
+            "Client connections accepted since start.",
+            snapshot.connections_total as f64,
+        ),
+        (
+            "codex_monitor_daemon_max_in_flight_rpcs",
+            "gauge",
+            "Per-connection limit on concurrently running RPCs.",
+            snapshot.max_in_flight_rpc_per_connection as f64,
+        ),
+        (
+            "codex_monitor_daemon_event_lag_dropped_total",
+            "counter",
+            "Events dropped because a client's event stream lagged.",
+            snapshot.events_dropped_total as f64,
+        ),
+    ];
+    for (name, kind, help, value) in scalars {
+        write_metric_header(&mut out, name, kind, help);
+        let _ = writeln!(out, "{name} {value}");
+    }
+
+    let name = "codex_monitor_daemon_in_flight_rpcs";
+    write_metric_header(
+        &mut out,
+        name,
+        "gauge",
+        "RPCs currently running per connection.",
+    );
+    for connection in &snapshot.connections {
+        let _ = writeln!(
+            out,
+            "{name}{{connection=\"{}\",peer=\"{}\"}} {}",
+            connection.id,
+            escape_label(&connection.peer),
+            connection.in_flight_rpcs
+        );
+    }
+
+    let name = "codex_monitor_daemon_session_uptime_seconds";
+    write_metric_header(
+        &mut out,
+        name,
+        "gauge",
+        "Seconds since the workspace's app-server session started.",
+    );
+    for session in &snapshot.sessions {
+        let _ = writeln!(
+            out,
+            "{name}{{workspace_id=\"{}\"}} {}",
+            escape_label(&session.workspace_id),
+            session.uptime_seconds
+        );
+    }
+    let name = "codex_monitor_daemon_app_server_restarts_total";
+    write_metric_header(
+        &mut out,
+        name,
+        "counter",
+        "Supervised app-server restarts per workspace session.",
+    );
+    for session in &snapshot.sessions {
+        let _ = writeln!(
+            out,
+            "{name}{{workspace_id=\"{}\"}} {}",
+            escape_label(&session.workspace_id),
+            session.restart_count
+        );
+    }
+
+    let name = "codex_monitor_daemon_rpc_duration_seconds";
+    write_metric_header(&mut out, name, "histogram", "RPC handling time by method.");
+    for rpc in &snapshot.rpc_latency {
+        let method = escape_label(&rpc.method);
+        for bucket in &rpc.buckets {
+            let _ = writeln!(
+                out,
+                "{name}_bucket{{method=\"{method}\",le=\"{}\"}} {}",
+                bucket.le, bucket.count
+            );
+        }
+        let _ = writeln!(
+            out,
+            "{name}_bucket{{method=\"{method}\",le=\"+Inf\"}} {}",
+            rpc.count
+        );
+        let _ = writeln!(out, "{name}_sum{{method=\"{method}\"}} {}", rpc.sum_seconds);
+        let _ = writeln!(out, "{name}_count{{method=\"{method}\"}} {}", rpc.count);
+    }
+    let name = "codex_monitor_daemon_rpc_errors_total";
+    write_metric_header(
+        &mut out,
+        name,
+        "counter",
+        "RPCs that returned an error, by method.",
+    );
+    for rpc in &snapshot.rpc_latency {
+        let _ = writeln!(
+            out,
+            "{name}{{method=\"{}\"}} {}",
+            escape_label(&rpc.method),
+            rpc.errors
+        );
+    }
+
+    let background = &snapshot.background_callbacks;
+    let name = "codex_monitor_daemon_background_events_dropped_total";
+    write_metric_header(
+        &mut out,
+        name,
+        "counter",
+        "Background-thread events dropped at capacity.",
+    );
+    let _ = writeln!(out, "{name} {}", background.events_dropped);
+    out
+}
+
+/// Request path from an HTTP/1.x request head, without the query string.
+fn request_path(head: &str) -> Option<&str> {
+    let mut parts = head.lines().next()?.split_whitespace();
+    if parts.next()? != "GET" {
+        return None;
+    }
+    parts.next()?.split('?').next()
+}
+
+async fn read_request_head(stream: &mut TcpStream) -> Option<String> {
+    let mut buffer = Vec::new();
+    let mut chunk = [0u8; 1024];
+    while !buffer.windows(4).any(|window| window == b"\r\n\r\n") {
+        if buffer.len() > MAX_HTTP_REQUEST_BYTES {
+            return None;
+        }
+        let read = tokio::time::timeout(HTTP_READ_TIMEOUT, stream.read(&mut chunk))
+            .await
+            .ok()?
+            .ok()?;
+        if read == 0 {
+            break;
+        }
+        buffer.extend_from_slice(&chunk[..read]);
+    }
+    String::from_utf8(buffer).ok()
+}
+
+async fn serve_metrics_request(mut stream: TcpStream, state: Arc<DaemonState>) {
+    let head = read_request_head(&mut stream).await.unwrap_or_default();
+    let (status, content_type, body) = match request_path(&head) {
+        Some("/metrics") => (
+            "200 OK",
+            PROMETHEUS_CONTENT_TYPE,
+            render_prometheus(&state.daemon_metrics().await),
+        ),
+        Some("/healthz") => ("200 OK", "text/plain", "ok\n".to_string()),
+        Some(_) => ("404 Not Found", "text/plain", "not found\n".to_string()),
+        None => ("400 Bad Request", "text/plain", "bad request\n".to_string()),
+    };
+    let response = format!(
+        "HTTP/1.1 {status}\r\nContent-Type: {content_type}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        body.len()
+    );
+    let _ = stream.write_all(response.as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+/// Serves `GET /metrics` and `GET /healthz`. There is no auth, so the
+/// listener should stay on loopback or a private interface.
+pub(super) async fn run_metrics_listener(listener: TcpListener, state: Arc<DaemonState>) {
+    loop {
+        match listener.accept().await {
+            Ok((stream, _addr)) => {
+                tokio::spawn(serve_metrics_request(stream, Arc::clone(&state)));
+            }
+            Err(_) => continue,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{render_prometheus, request_path, DaemonMetrics, SessionSnapshot};
+    use crate::backend::background_callbacks::BackgroundCallbackMetrics;
+    use serde_json::json;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use tokio::sync::Semaphore;
+
+    #[test]
+    fn snapshot_tracks_connections_latency_and_lag() {
+        let metrics = Arc::new(DaemonMetrics::new(4));
+        let limiter = Arc::new(Semaphore::new(4));
+        let connection =
+            metrics.register_connection("127.0.0.1:5000".to_string(), Arc::clone(&limiter), 4);
+        let _permit = limiter.try_acquire().expect("permit");
+        connection.record_events_dropped(3);
+        metrics.record_rpc("get_git_status", Duration::from_millis(20), &Ok(json!({})));
+        metrics.record_rpc(
+            "get_git_status",
+            Duration::from_secs(60),
+            &Err("boom".to_string()),
+        );
+        metrics.record_rpc(
+            "bogus",
+            Duration::from_millis(1),
+            &Err("unknown method: bogus".to_string()),
+        );
+        metrics.record_rpc("made_up", Duration::from_millis(1), &Ok(json!({})));
+        metrics.record_rpc(
+            "remove_workspace",
+            Duration::from_millis(1),
+            &Err("forbidden: `remove_workspace` requires the admin scope".to_string()),
+        );
+
+        let snapshot = metrics.snapshot(
+            vec![SessionSnapshot {
+                workspace_id: "ws-1".to_string(),
+                uptime_seconds: 90,
+                restart_count: 2,
+            }],
+            BackgroundCallbackMetrics::default(),
+        );
+        let value = serde_json::to_value(&snapshot).expect("serialize");
+        assert_eq!(value["connectedClients"], 1);
+        assert_eq!(value["connections"][0]["inFlightRpcs"], 1);
+        assert_eq!(value["eventsDroppedTotal"], 3);
+        assert_eq!(value["rpcLatency"][0]["method"], "get_git_status");
+        assert_eq!(value["rpcLatency"][0]["count"], 2);
+        assert_eq!(value["rpcLatency"][0]["errors"], 1);
+        // 20ms lands in the 25ms bucket; the 60s call only counts toward +Inf.
+        assert_eq!(value["rpcLatency"][0]["buckets"][1]["count"], 0);
+        assert_eq!(value["rpcLatency"][0]["buckets"][2]["count"], 1);
+        assert_eq!(value["rpcLatency"][0]["buckets"][11]["count"], 1);
+        assert_eq!(value["rpcLatency"][1]["method"], "unknown");
+        assert_eq!(value["rpcLatency"][1]["count"], 3);
+        assert_eq!(value["rpcLatency"].as_array().map(Vec::len), Some(2));
+
+        let text = render_prometheus(&snapshot);
+        assert!(text
+            .contains("codex_monitor_daemon_app_server_restarts_total{workspace_id=\"ws-1\"} 2"));
+        assert!(text.contains(
+            "codex_monitor_daemon_rpc_duration_seconds_bucket{method=\"get_git_status\",le=\"+Inf\"} 2"
+        ));
+        assert!(text.contains("codex_monitor_daemon_event_lag_dropped_total 3"));
+
+        drop(connection);
+        let snapshot = metrics.snapshot(Vec::new(), BackgroundCallbackMetrics::default());
+        let value = serde_json::to_value(&snapshot).expect("serialize");
+        assert_eq!(value["connectedClients"], 0);
+        assert_eq!(value["connectionsTotal"], 1);
+    }
+
+    #[test]
+    fn request_path_accepts_get_only() {
+        assert_eq!(
+            request_path("GET /metrics?x=1 HTTP/1.1\r\nHost: a\r\n\r\n"),
+            Some("/metrics")
+        );
+        assert_eq!(request_path("POST /metrics HTTP/1.1\r\n\r\n"), None);
+        assert_eq!(request_path(""), None);
+    }
+}
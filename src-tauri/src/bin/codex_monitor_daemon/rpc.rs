@@ -1,10 +1,224 @@
 use super::*;
+use crate::backend::app_server::extract_thread_id;
+use crate::backend::background_callbacks::background_callback_metrics;
+use crate::backend::protocol::is_thread_boundary_method;
 use serde::de::DeserializeOwned;
+use std::collections::HashSet;
+use tokio::time::Instant;
 
 const MAX_PAGINATION_LIMIT: u32 = 500;
 const MAX_GIT_ROOT_DEPTH: u32 = 64;
 const MAX_LOCAL_USAGE_DAYS: u32 = 366;
 const MAX_GITHUB_PR_NUMBER: u64 = 10_000_000;
+const THREAD_ACTIVITY_SUMMARY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Per-connection state shared by the RPC handlers and the event forwarder.
+pub(super) struct RpcClient {
+    client_version: String,
+    thread_subscriptions: Mutex<ThreadLiveSubscriptions>,
+    /// Released when the connection goes away.
+    git_status_watches: Mutex<GitStatusWatchSet>,
+    principal: std::sync::OnceLock<Principal>,
+    /// Remote address, or the listener kind when there is none.
+    peer: String,
+}
+
+impl RpcClient {
+    pub(super) fn new(client_version: String, peer: String) -> Self {
+        Self {
+            client_version,
+            peer,
+            thread_subscriptions: Mutex::new(ThreadLiveSubscriptions::default()),
+            git_status_watches: Mutex::new(GitStatusWatchSet::default()),
+            principal: std::sync::OnceLock::new(),
+        }
+    }
+
+    /// Records who the connection authenticated as; set once per connection.
+    pub(super) fn set_principal(&self, principal: Principal) {
+        let _ = self.principal.set(principal);
+    }
+}
+
+/// Threads one client is watching live. Until a client subscribes to its
+/// first thread it receives every event, which keeps older clients working.
+#[derive(Debug, Default)]
+pub(super) struct ThreadLiveSubscriptions {
+    threads: HashMap<String, HashSet<String>>,
+    opted_in: bool,
+}
+
+impl ThreadLiveSubscriptions {
+    pub(super) fn subscribe(&mut self, workspace_id: &str, thread_id: &str) -> bool {
+        self.opted_in = true;
+        self.threads
+            .entry(workspace_id.to_string())
+            .or_default()
+            .insert(thread_id.to_string())
+    }
+
+    pub(super) fn unsubscribe(&mut self, workspace_id: &str, thread_id: &str) -> bool {
+        let Some(threads) = self.threads.get_mut(workspace_id) else {
+            return false;
+        };
+        let removed = threads.remove(thread_id);
+        if threads.is_empty() {
+            self.threads.remove(workspace_id);
+        }
+        removed
+    }
+
+    fn is_watching(&self, workspace_id: &str, thread_id: &str) -> bool {
+        self.threads
+            .get(workspace_id)
+            .is_some_and(|threads| threads.contains(thread_id))
+    }
+
+    /// Workspace-level events (no thread id) always go through.
+    fn should_forward(&self, workspace_id: &str, thread_id: Option<&str>) -> bool {
+        match thread_id {
+            Some(thread_id) => !self.opted_in || self.is_watching(workspace_id, thread_id),
+            None => true,
+        }
+    }
+}
+
+#[derive(Default)]
+struct ThreadActivity {
+    last_sent: Option<Instant>,
+    suppressed: u64,
+    last_method: String,
+}
+
+/// Collapses streaming events for threads a client is not watching into at
+/// most one `thread/activity` notification per thread per interval. Counts
+/// still pending when a burst ends go out from [`Self::flush_due`], which the
+/// forwarder calls once [`Self::next_deadline`] passes.
+#[derive(Default)]
+struct ThreadActivitySummarizer {
+    threads: HashMap<(String, String), ThreadActivity>,
+}
+
+impl ThreadActivitySummarizer {
+    fn record(
+        &mut self,
+        workspace_id: &str,
+        thread_id: &str,
+        method: &str,
+        now: Instant,
+    ) -> Option<Value> {
+        let activity = self
+            .threads
+            .entry((workspace_id.to_string(), thread_id.to_string()))
+            .or_default();
+        activity.suppressed += 1;
+        activity.last_method = method.to_string();
+        let interval_elapsed = activity
+            .last_sent
+            .is_none_or(|last| now.duration_since(last) >= THREAD_ACTIVITY_SUMMARY_INTERVAL);
+        if !interval_elapsed {
+            return None;
+        }
+        Some(take_activity_summary(
+            workspace_id,
+            thread_id,
+            activity,
+            now,
+        ))
+    }
+
+    /// When the earliest pending summary is due or an idle entry can be
+    /// dropped; `None` when nothing is tracked.
+    fn next_deadline(&self) -> Option<Instant> {
+        self.threads
+            .values()
+            .filter_map(|activity| activity.last_sent)
+            .min()
+            .map(|last| last + THREAD_ACTIVITY_SUMMARY_INTERVAL)
+    }
+
+    /// Summaries for threads whose interval has passed with events still
+    /// pending. Threads with nothing pending are forgotten instead.
+    fn flush_due(&mut self, now: Instant) -> Vec<Value> {
+        let mut summaries = Vec::new();
+        self.threads.retain(|(workspace_id, thread_id), activity| {
+            let due = activity
+                .last_sent
+                .is_none_or(|last| now.duration_since(last) >= THREAD_ACTIVITY_SUMMARY_INTERVAL);
+            if !due {
+                return true;
+            }
+            if activity.suppressed == 0 {
+                return false;
+            }
+            summaries.push(take_activity_summary(
+                workspace_id,
+                thread_id,
+                activity,
+                now,
+            ));
+            true
+        });
+        summaries
+    }
+
+    fn forget(&mut self, workspace_id: &str, thread_id: &str) {
+        self.threads
+            .remove(&(workspace_id.to_string(), thread_id.to_string()));
+    }
+}
+
+fn take_activity_summary(
+    workspace_id: &str,
+    thread_id: &str,
+    activity: &mut ThreadActivity,
+    now: Instant,
+) -> Value {
+    let event_count = activity.suppressed;
+    activity.suppressed = 0;
+    activity.last_sent = Some(now);
+    json!({
+        "method": "app-server-event",
+        "params": {
+            "workspace_id": workspace_id,
+            "message": {
+                "method": "thread/activity",
+                "params": {
+                    "workspaceId": workspace_id,
+                    "threadId": thread_id,
+                    "lastMethod": activity.last_method,
+                    "eventCount": event_count,
+                }
+            }
+        }
+    })
+}
+
+/// Thread id of an event the client is not watching, or `None` when the event
+/// should be forwarded as-is. Server requests (approvals, user input), turn
+/// boundaries and `thread/*` lifecycle events are never filtered, so sidebars
+/// stay accurate; only streaming item traffic is summarized.
+fn unwatched_thread_id(
+    subscriptions: &ThreadLiveSubscriptions,
+    payload: &AppServerEvent,
+) -> Option<String> {
+    if payload.message.get("id").is_some() {
+        return None;
+    }
+    let method = payload
+        .message
+        .get("method")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    if method.starts_with("thread/") || is_thread_boundary_method(method) {
+        return None;
+    }
+    let thread_id = extract_thread_id(&payload.message)?;
+    if subscriptions.should_forward(&payload.workspace_id, Some(&thread_id)) {
+        return None;
+    }
+    Some(thread_id)
+}
 
 fn is_valid_event_request_id(value: &Value) -> bool {
     match value {
@@ -75,6 +289,10 @@ fn build_event_notification(event: DaemonEvent) -> Option<String> {
             "method": "terminal-exit",
             "params": payload,
         }),
+        DaemonEvent::GitStatusChanged(payload) => json!({
+            "method": "git-status-changed",
+            "params": payload,
+        }),
     };
     serde_json::to_string(&payload).ok()
 }
@@ -159,6 +377,10 @@ fn parse_required_bounded_u64(value: &Value, key: &str, max: u64) -> Result<u64,
     Ok(number)
 }
 
+fn parse_u16(value: &Value, key: &str) -> Result<u16, String> {
+    parse_required_bounded_u64(value, key, u16::MAX as u64).map(|number| number as u16)
+}
+
 fn parse_optional_bool(value: &Value, key: &str) -> Option<bool> {
     match value {
         Value::Object(map) => map.get(key).and_then(|value| value.as_bool()),
@@ -199,6 +421,10 @@ fn parse_string_array(value: &Value, key: &str) -> Result<Vec<String>, String> {
 fn classify_rpc_error_code(message: &str) -> &'static str {
     if message.starts_with("unknown method:") {
         "METHOD_NOT_FOUND"
+    } else if message.starts_with("forbidden:") {
+        "FORBIDDEN"
+    } else if message.starts_with("unauthorized") {
+        "UNAUTHORIZED"
     } else if message.starts_with("missing ")
         || message.starts_with("invalid ")
         || message.contains("missing or invalid")
@@ -216,6 +442,25 @@ fn parse_optional_value(value: &Value, key: &str) -> Option<Value> {
     }
 }
 
+fn parse_hunk_selection(params: &Value) -> Result<GitHunkSelection, String> {
+    let selection = parse_optional_value(params, "selection").ok_or("missing `selection`")?;
+    serde_json::from_value(selection).map_err(|err| format!("invalid `selection`: {err}"))
+}
+
+fn parse_conflict_resolution(params: &Value) -> Result<GitConflictResolution, String> {
+    let resolution = parse_optional_value(params, "resolution").ok_or("missing `resolution`")?;
+    serde_json::from_value(resolution).map_err(|err| format!("invalid `resolution`: {err}"))
+}
+
+fn parse_git_log_filter(params: &Value) -> Result<Option<GitLogFilter>, String> {
+    match parse_optional_value(params, "filter") {
+        Some(Value::Null) | None => Ok(None),
+        Some(filter) => serde_json::from_value(filter)
+            .map(Some)
+            .map_err(|err| format!("invalid `filter`: {err}")),
+    }
+}
+
 fn normalize_request_id(value: &Value) -> Result<Value, String> {
     match value {
         Value::Number(number) => Ok(Value::Number(number.clone())),
@@ -290,6 +535,48 @@ fn parse_input<T: DeserializeOwned>(params: &Value) -> Result<T, String> {
     serde_json::from_value(input_value).map_err(|err| err.to_string())
 }
 
+/// Methods whose behaviour depends on the calling connection; everything else
+/// is delegated to [`handle_rpc_request`].
+pub(super) async fn handle_client_rpc_request(
+    state: &DaemonState,
+    client: &RpcClient,
+    method: &str,
+    params: Value,
+) -> Result<Value, String> {
+    state.authorize(client.principal.get(), method).await?;
+    match method {
+        "thread_live_subscribe" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            state
+                .thread_live_subscribe(&client.thread_subscriptions, workspace_id, thread_id)
+                .await
+        }
+        "thread_live_unsubscribe" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let thread_id = parse_string(&params, "threadId")?;
+            state
+                .thread_live_unsubscribe(&client.thread_subscriptions, workspace_id, thread_id)
+                .await
+        }
+        "watch_git_status" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            state
+                .watch_git_status(&client.git_status_watches, workspace_id)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "unwatch_git_status" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            state
+                .unwatch_git_status(&client.git_status_watches, workspace_id)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        _ => handle_rpc_request(state, method, params, client.client_version.clone()).await,
+    }
+}
+
 pub(super) async fn handle_rpc_request(
     state: &DaemonState,
     method: &str,
@@ -299,7 +586,29 @@ pub(super) async fn handle_rpc_request(
     match method {
         "ping" => Ok(json!({ "ok": true })),
         "daemon_info" => Ok(state.daemon_info()),
+        "audit_log_query" => {
+            let query = AuditQuery {
+                method: parse_optional_string(&params, "method"),
+                workspace_id: parse_optional_string(&params, "workspaceId"),
+                since_ms: parse_optional_u64(&params, "sinceMs"),
+                until_ms: parse_optional_u64(&params, "untilMs"),
+                limit: parse_optional_u64(&params, "limit")
+                    .map(|limit| limit.min(audit::MAX_QUERY_LIMIT)),
+            };
+            state.audit_log_query(query).await
+        }
+        "token_list" => Ok(state.token_list().await),
+        "token_mint" => {
+            let name = parse_string(&params, "name")?;
+            let scope = TokenScope::parse(&parse_string(&params, "scope")?)?;
+            state.token_mint(name, scope).await
+        }
+        "token_revoke" => {
+            let name = parse_string(&params, "name")?;
+            state.token_revoke(name).await
+        }
         "daemon_shutdown" => {
+            state.shutdown_sessions().await;
             tokio::spawn(async {
                 sleep(Duration::from_millis(100)).await;
                 std::process::exit(0);
@@ -362,6 +671,45 @@ pub(super) async fn handle_rpc_request(
             state.connect_workspace(id, client_version).await?;
             Ok(json!({ "ok": true }))
         }
+        "terminal_open" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let terminal_id = parse_string(&params, "terminalId")?;
+            let cols = parse_u16(&params, "cols")?;
+            let rows = parse_u16(&params, "rows")?;
+            let info = state
+                .terminal_open(workspace_id, terminal_id, cols, rows)
+                .await?;
+            serde_json::to_value(info).map_err(|err| err.to_string())
+        }
+        "terminal_write" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let terminal_id = parse_string(&params, "terminalId")?;
+            let data = parse_string(&params, "data")?;
+            state
+                .terminal_write(workspace_id, terminal_id, data)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "terminal_resize" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let terminal_id = parse_string(&params, "terminalId")?;
+            let cols = parse_u16(&params, "cols")?;
+            let rows = parse_u16(&params, "rows")?;
+            state
+                .terminal_resize(workspace_id, terminal_id, cols, rows)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "terminal_close" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let terminal_id = parse_string(&params, "terminalId")?;
+            state.terminal_close(workspace_id, terminal_id).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "workspace_process_stats" => {
+            let workspace_id = parse_optional_string(&params, "workspaceId");
+            state.workspace_process_stats(workspace_id).await
+        }
         "remove_workspace" => {
             let id = parse_string(&params, "id")?;
             state.remove_workspace(id).await?;
@@ -522,15 +870,21 @@ pub(super) async fn handle_rpc_request(
             let thread_id = parse_string(&params, "threadId")?;
             state.compact_thread(workspace_id, thread_id).await
         }
-        "thread_live_subscribe" => {
-            let workspace_id = parse_string(&params, "workspaceId")?;
-            let thread_id = parse_string(&params, "threadId")?;
-            state.thread_live_subscribe(workspace_id, thread_id).await
+        "daemon_metrics" => {
+            serde_json::to_value(state.daemon_metrics().await).map_err(|err| err.to_string())
         }
-        "thread_live_unsubscribe" => {
+        "daemon_state_snapshot" => {
+            serde_json::to_value(state.daemon_state_snapshot().await).map_err(|err| err.to_string())
+        }
+        "background_callback_metrics" => {
+            serde_json::to_value(background_callback_metrics()).map_err(|err| err.to_string())
+        }
+        "replay_events" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
-            let thread_id = parse_string(&params, "threadId")?;
-            state.thread_live_unsubscribe(workspace_id, thread_id).await
+            let since_seq = parse_optional_u64(&params, "sinceSeq").unwrap_or(0);
+            let limit =
+                parse_optional_bounded_u32(&params, "limit", event_journal_core::MAX_REPLAY_LIMIT)?;
+            state.replay_events(workspace_id, since_seq, limit).await
         }
         "set_thread_name" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
@@ -778,7 +1132,11 @@ pub(super) async fn handle_rpc_request(
             let workspace_id = parse_string(&params, "workspaceId")?;
             let limit = parse_optional_bounded_u32(&params, "limit", MAX_PAGINATION_LIMIT)?
                 .map(|value| value as usize);
-            let log = state.get_git_log(workspace_id, limit).await?;
+            let cursor = parse_optional_string(&params, "cursor");
+            let filter = parse_git_log_filter(&params)?;
+            let log = state
+                .get_git_log(workspace_id, limit, cursor, filter)
+                .await?;
             serde_json::to_value(log).map_err(|err| err.to_string())
         }
         "get_git_commit_diff" => {
@@ -820,6 +1178,31 @@ pub(super) async fn handle_rpc_request(
             state.revert_git_all(workspace_id).await?;
             Ok(json!({ "ok": true }))
         }
+        "stage_git_hunks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let selection = parse_hunk_selection(&params)?;
+            state.stage_git_hunks(workspace_id, path, selection).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "unstage_git_hunks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let selection = parse_hunk_selection(&params)?;
+            state
+                .unstage_git_hunks(workspace_id, path, selection)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "revert_git_hunks" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let selection = parse_hunk_selection(&params)?;
+            state
+                .revert_git_hunks(workspace_id, path, selection)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
         "commit_git" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let message = parse_string(&params, "message")?;
@@ -888,6 +1271,75 @@ pub(super) async fn handle_rpc_request(
             state.create_git_branch(workspace_id, name).await?;
             Ok(json!({ "ok": true }))
         }
+        "list_git_stashes" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let stashes = state.list_git_stashes(workspace_id).await?;
+            serde_json::to_value(stashes).map_err(|err| err.to_string())
+        }
+        "create_git_stash" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let message = parse_optional_string(&params, "message");
+            let include_untracked =
+                parse_optional_bool(&params, "includeUntracked").unwrap_or(false);
+            let stash = state
+                .create_git_stash(workspace_id, message, include_untracked)
+                .await?;
+            serde_json::to_value(stash).map_err(|err| err.to_string())
+        }
+        "apply_git_stash" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let sha = parse_string(&params, "sha")?;
+            state.apply_git_stash(workspace_id, sha).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "pop_git_stash" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let sha = parse_string(&params, "sha")?;
+            state.pop_git_stash(workspace_id, sha).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "drop_git_stash" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let sha = parse_string(&params, "sha")?;
+            state.drop_git_stash(workspace_id, sha).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "get_git_stash_diff" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let sha = parse_string(&params, "sha")?;
+            let diffs = state.get_git_stash_diff(workspace_id, sha).await?;
+            serde_json::to_value(diffs).map_err(|err| err.to_string())
+        }
+        "get_git_conflicts" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let conflicts = state.get_git_conflicts(workspace_id).await?;
+            serde_json::to_value(conflicts).map_err(|err| err.to_string())
+        }
+        "resolve_git_conflict" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            let path = parse_string(&params, "path")?;
+            let resolution = parse_conflict_resolution(&params)?;
+            let content = parse_optional_string(&params, "content");
+            state
+                .resolve_git_conflict(workspace_id, path, resolution, content)
+                .await?;
+            Ok(json!({ "ok": true }))
+        }
+        "abort_merge" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            state.abort_merge(workspace_id).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "continue_merge" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            state.continue_merge(workspace_id).await?;
+            Ok(json!({ "ok": true }))
+        }
+        "abort_rebase" => {
+            let workspace_id = parse_string(&params, "workspaceId")?;
+            state.abort_rebase(workspace_id).await?;
+            Ok(json!({ "ok": true }))
+        }
         "prompts_list" => {
             let workspace_id = parse_string(&params, "workspaceId")?;
             let prompts = state.prompts_list(workspace_id).await?;
@@ -1008,24 +1460,93 @@ pub(super) async fn handle_rpc_request(
     }
 }
 
+async fn send_state_snapshot(
+    state: &DaemonState,
+    out_tx_events: &mpsc::UnboundedSender<String>,
+) -> Result<(), ()> {
+    let snapshot = state.daemon_state_snapshot().await;
+    match snapshot::build_snapshot_notification(&snapshot) {
+        Some(payload) => out_tx_events.send(payload).map_err(|_| ()),
+        None => Ok(()),
+    }
+}
+
+/// Streams daemon events to one client. A snapshot of the daemon state goes
+/// out first and again after every lag, so the client can rebuild what it
+/// missed instead of waiting on events that were dropped.
 pub(super) async fn forward_events(
+    state: Arc<DaemonState>,
     mut rx: broadcast::Receiver<DaemonEvent>,
     out_tx_events: mpsc::UnboundedSender<String>,
+    client: Arc<RpcClient>,
+    connection: Arc<metrics::ConnectionMetrics>,
 ) {
+    let mut summarizer = ThreadActivitySummarizer::default();
+    if send_state_snapshot(&state, &out_tx_events).await.is_err() {
+        return;
+    }
     loop {
-        let event = match rx.recv().await {
+        // Pending counts for a thread that went quiet still get reported.
+        let flushed = summarizer
+            .flush_due(Instant::now())
+            .iter()
+            .filter_map(|summary| serde_json::to_string(summary).ok())
+            .all(|summary| out_tx_events.send(summary).is_ok());
+        if !flushed {
+            break;
+        }
+        let received = match summarizer.next_deadline() {
+            Some(deadline) => match tokio::time::timeout_at(deadline, rx.recv()).await {
+                Ok(received) => received,
+                Err(_) => continue,
+            },
+            None => rx.recv().await,
+        };
+        let event = match received {
             Ok(event) => event,
             Err(broadcast::error::RecvError::Lagged(dropped_count)) => {
+                connection.record_events_dropped(dropped_count);
                 if let Some(payload) = build_event_stream_lagged_notification(dropped_count) {
                     if out_tx_events.send(payload).is_err() {
                         break;
                     }
                 }
+                if send_state_snapshot(&state, &out_tx_events).await.is_err() {
+                    break;
+                }
                 continue;
             }
             Err(broadcast::error::RecvError::Closed) => break,
         };
 
+        if let DaemonEvent::AppServer(payload) = &event {
+            if payload.message.get("method").and_then(Value::as_str) == Some("thread/archived") {
+                if let Some(thread_id) = extract_thread_id(&payload.message) {
+                    summarizer.forget(&payload.workspace_id, &thread_id);
+                }
+            }
+            let unwatched = {
+                let subscriptions = client.thread_subscriptions.lock().await;
+                unwatched_thread_id(&subscriptions, payload)
+            };
+            if let Some(thread_id) = unwatched {
+                let method = payload
+                    .message
+                    .get("method")
+                    .and_then(Value::as_str)
+                    .unwrap_or("");
+                let summary =
+                    summarizer.record(&payload.workspace_id, &thread_id, method, Instant::now());
+                if let Some(summary) = summary.and_then(|value| serde_json::to_string(&value).ok())
+                {
+                    if out_tx_events.send(summary).is_err() {
+                        break;
+                    }
+                }
+                continue;
+            }
+        }
+
         let Some(payload) = build_event_notification(event) else {
             continue;
         };
@@ -1042,14 +1563,32 @@ pub(super) fn spawn_rpc_response_task(
     id: Option<u64>,
     method: String,
     params: Value,
-    client_version: String,
+    client: Arc<RpcClient>,
     request_limiter: Arc<Semaphore>,
 ) {
     tokio::spawn(async move {
         let Ok(_permit) = request_limiter.acquire_owned().await else {
             return;
         };
-        let result = handle_rpc_request(&state, &method, params, client_version).await;
+        let audited_params =
+            (tokens::required_scope(&method) != TokenScope::ReadOnly).then(|| params.clone());
+        let started = Instant::now();
+        let result = handle_client_rpc_request(&state, &client, &method, params).await;
+        state
+            .metrics
+            .record_rpc(&method, started.elapsed(), &result);
+        if let Some(params) = audited_params {
+            state
+                .audit_log
+                .record(audit::AuditEntry {
+                    peer: &client.peer,
+                    token: client.principal.get().and_then(Principal::token_name),
+                    method: &method,
+                    params: &params,
+                    result: &result,
+                })
+                .await;
+        }
         let response = match result {
             Ok(result) => build_result_response(id, result),
             Err(message) => build_error_response(id, classify_rpc_error_code(&message), &message),
@@ -1066,12 +1605,14 @@ pub(super) fn spawn_rpc_response_task(
 mod tests {
     use super::{
         build_error_response, build_event_notification, build_event_stream_lagged_notification,
-        normalize_request_id,
-        parse_optional_bounded_u32, parse_optional_string_array, parse_string_array,
-        validate_response_workspace_binding, DaemonEvent, MAX_LOCAL_USAGE_DAYS,
+        normalize_request_id, parse_optional_bounded_u32, parse_optional_string_array,
+        parse_string_array, parse_u16, unwatched_thread_id, validate_response_workspace_binding,
+        DaemonEvent, ThreadActivitySummarizer, ThreadLiveSubscriptions, MAX_LOCAL_USAGE_DAYS,
+        THREAD_ACTIVITY_SUMMARY_INTERVAL,
     };
     use crate::backend::events::AppServerEvent;
     use serde_json::json;
+    use tokio::time::Instant;
 
     #[test]
     fn parse_string_array_success() {
@@ -1132,6 +1673,19 @@ mod tests {
         );
     }
 
+    #[test]
+    fn parse_u16_requires_terminal_dimensions_in_range() {
+        assert_eq!(parse_u16(&json!({ "cols": 120 }), "cols"), Ok(120));
+        assert_eq!(
+            parse_u16(&json!({}), "rows"),
+            Err("missing or invalid `rows`".to_string())
+        );
+        assert_eq!(
+            parse_u16(&json!({ "cols": 70_000 }), "cols"),
+            Err(format!("invalid `cols`: must be <= {}", u16::MAX))
+        );
+    }
+
     #[test]
     fn build_error_response_includes_error_code() {
         let payload = build_error_response(Some(7), "INVALID_PARAMS", "missing `workspaceId`")
@@ -1200,7 +1754,146 @@ mod tests {
                 "method": "approval/request",
                 "params": {}
             }),
+            seq: None,
         });
         assert!(build_event_notification(event).is_none());
     }
+
+    #[test]
+    fn unwatched_thread_events_are_filtered_after_subscribe() {
+        let mut subscriptions = ThreadLiveSubscriptions::default();
+        let delta = AppServerEvent {
+            workspace_id: "ws-1".to_string(),
+            message: json!({
+                "method": "item/agentMessage/delta",
+                "params": { "threadId": "thread-2", "delta": "hi" }
+            }),
+            seq: None,
+        };
+        assert_eq!(unwatched_thread_id(&subscriptions, &delta), None);
+
+        subscriptions.subscribe("ws-1", "thread-1");
+        assert_eq!(
+            unwatched_thread_id(&subscriptions, &delta),
+            Some("thread-2".to_string())
+        );
+
+        let approval = AppServerEvent {
+            workspace_id: "ws-1".to_string(),
+            message: json!({
+                "id": 7,
+                "method": "item/commandExecution/requestApproval",
+                "params": { "threadId": "thread-2" }
+            }),
+            seq: None,
+        };
+        assert_eq!(unwatched_thread_id(&subscriptions, &approval), None);
+
+        let turn_started = AppServerEvent {
+            workspace_id: "ws-1".to_string(),
+            message: json!({
+                "method": "turn/started",
+                "params": { "threadId": "thread-2", "turn": { "id": "turn-1" } }
+            }),
+            seq: None,
+        };
+        assert_eq!(unwatched_thread_id(&subscriptions, &turn_started), None);
+
+        let workspace_event = AppServerEvent {
+            workspace_id: "ws-1".to_string(),
+            message: json!({ "method": "codex/connected", "params": { "workspaceId": "ws-1" } }),
+            seq: None,
+        };
+        assert_eq!(unwatched_thread_id(&subscriptions, &workspace_event), None);
+    }
+
+    #[test]
+    fn thread_activity_summarizer_throttles_per_interval() {
+        let mut summarizer = ThreadActivitySummarizer::default();
+        let start = Instant::now();
+        let first = summarizer
+            .record("ws-1", "thread-1", "item/started", start)
+            .expect("first summary");
+        assert_eq!(first["params"]["message"]["method"], "thread/activity");
+        assert_eq!(first["params"]["message"]["params"]["eventCount"], 1);
+
+        assert!(summarizer
+            .record("ws-1", "thread-1", "item/agentMessage/delta", start)
+            .is_none());
+        assert!(summarizer
+            .record("ws-1", "thread-1", "item/agentMessage/delta", start)
+            .is_none());
+
+        let later = summarizer
+            .record(
+                "ws-1",
+                "thread-1",
+                "item/agentMessage/delta",
+                start + THREAD_ACTIVITY_SUMMARY_INTERVAL,
+            )
+            .expect("interval summary");
+        assert_eq!(later["params"]["message"]["params"]["eventCount"], 3);
+        assert_eq!(
+            later["params"]["message"]["params"]["lastMethod"],
+            "item/agentMessage/delta"
+        );
+    }
+
+    #[test]
+    fn thread_activity_summarizer_flushes_the_last_burst_and_prunes_idle_threads() {
+        let mut summarizer = ThreadActivitySummarizer::default();
+        let start = Instant::now();
+        let due = start + THREAD_ACTIVITY_SUMMARY_INTERVAL;
+        assert!(summarizer.next_deadline().is_none());
+
+        summarizer
+            .record("ws-1", "thread-1", "item/started", start)
+            .expect("first summary");
+        assert!(summarizer
+            .record("ws-1", "thread-1", "item/completed", start)
+            .is_none());
+        assert_eq!(summarizer.next_deadline(), Some(due));
+        assert!(summarizer.flush_due(start).is_empty());
+
+        let flushed = summarizer.flush_due(due);
+        assert_eq!(flushed.len(), 1);
+        assert_eq!(flushed[0]["params"]["message"]["params"]["eventCount"], 1);
+        assert_eq!(
+            flushed[0]["params"]["message"]["params"]["lastMethod"],
+            "item/completed"
+        );
+
+        // Nothing new arrived, so the next pass drops the thread entirely.
+        assert!(summarizer
+            .flush_due(due + THREAD_ACTIVITY_SUMMARY_INTERVAL)
+            .is_empty());
+        assert!(summarizer.next_deadline().is_none());
+
+        summarizer.record("ws-1", "thread-2", "item/started", start);
+        summarizer.forget("ws-1", "thread-2");
+        assert!(summarizer.next_deadline().is_none());
+    }
+
+    #[test]
+    fn thread_live_subscriptions_forward_everything_until_opted_in() {
+        let mut subscriptions = ThreadLiveSubscriptions::default();
+        assert!(subscriptions.should_forward("ws-1", Some("thread-1")));
+
+        assert!(subscriptions.subscribe("ws-1", "thread-1"));
+        assert!(!subscriptions.subscribe("ws-1", "thread-1"));
+        assert!(subscriptions.should_forward("ws-1", Some("thread-1")));
+        assert!(!subscriptions.should_forward("ws-1", Some("thread-2")));
+        assert!(!subscriptions.should_forward("ws-2", Some("thread-1")));
+        assert!(subscriptions.should_forward("ws-2", None));
+    }
+
+    #[test]
+    fn thread_live_subscriptions_stay_filtered_after_last_unsubscribe() {
+        let mut subscriptions = ThreadLiveSubscriptions::default();
+        subscriptions.subscribe("ws-1", "thread-1");
+        assert!(subscriptions.unsubscribe("ws-1", "thread-1"));
+        assert!(!subscriptions.unsubscribe("ws-1", "thread-1"));
+        assert!(!subscriptions.should_forward("ws-1", Some("thread-1")));
+        assert!(subscriptions.should_forward("ws-1", None));
+    }
 }
@@ -0,0 +1,651 @@
+//! `install-service`, `uninstall-service` and `service-status` subcommands.
+//! The daemon is registered as a systemd user unit on Linux and a launchd
+//! agent on macOS. Either way the service manager sets
+//! `CODEX_MONITOR_DAEMON_SERVICE`, which `daemon_info` reports so the desktop
+//! app leaves the daemon's lifecycle to the service manager.
+
+#[cfg(any(target_os = "linux", target_os = "macos", test))]
+use std::path::Path;
+use std::path::PathBuf;
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+use std::process::Command;
+
+use super::tokens::{tokens_path, TokenStore};
+use super::{default_data_dir, DEFAULT_LISTEN_ADDR};
+
+pub(super) const SERVICE_ENV_VAR: &str = "CODEX_MONITOR_DAEMON_SERVICE";
+const TOKEN_ENV_VAR: &str = "CODEX_MONITOR_DAEMON_TOKEN";
+#[cfg(target_os = "linux")]
+const SYSTEMD_UNIT_NAME: &str = "codex-monitor-daemon.service";
+#[cfg(target_os = "linux")]
+const ENV_FILE_NAME: &str = "daemon.env";
+#[cfg(any(target_os = "macos", test))]
+const LAUNCHD_LABEL: &str = "com.dimillian.codexmonitor.daemon";
+#[cfg(target_os = "macos")]
+const LOG_FILE_NAME: &str = "daemon.log";
+
+pub(super) const SERVICE_USAGE: &str = "\
+  codex-monitor-daemon install-service --user [--listen <addr> | --listen-unix <path>] [--data-dir <path>] [--token <token>] [--tls-cert <path> --tls-key <path>]\n  codex-monitor-daemon uninstall-service --user\n  codex-monitor-daemon service-status --user\n";
+
+pub(super) fn is_service_command(arg: &str) -> bool {
+    matches!(
+        arg,
+        "install-service" | "uninstall-service" | "service-status"
+    )
+}
+
+/// Service manager that launched this process, if any.
+pub(super) fn service_manager() -> Option<String> {
+    std::env::var(SERVICE_ENV_VAR)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+#[derive(Debug, Default, PartialEq)]
+struct InstallOptions {
+    listen: Option<String>,
+    listen_unix: Option<PathBuf>,
+    data_dir: Option<PathBuf>,
+    token: Option<String>,
+    tls_cert: Option<PathBuf>,
+    tls_key: Option<PathBuf>,
+}
+
+impl InstallOptions {
+    fn data_dir(&self) -> PathBuf {
+        self.data_dir.clone().unwrap_or_else(default_data_dir)
+    }
+
+    /// Daemon arguments, minus the token, which is passed via the
+    /// environment so it never shows up in `ps` or the unit file.
+    #[cfg(any(target_os = "linux", target_os = "macos", test))]
+    fn daemon_args(&self) -> Vec<String> {
+        let mut args = Vec::new();
+        if let Some(path) = &self.listen_unix {
+            args.push("--listen-unix".to_string());
+            args.push(path.display().to_string());
+        } else {
+            args.push("--listen".to_string());
+            args.push(
+                self.listen
+                    .clone()
+                    .unwrap_or_else(|| DEFAULT_LISTEN_ADDR.to_string()),
+            );
+        }
+        args.push("--data-dir".to_string());
+        args.push(self.data_dir().display().to_string());
+        if let (Some(cert), Some(key)) = (&self.tls_cert, &self.tls_key) {
+            args.push("--tls-cert".to_string());
+            args.push(cert.display().to_string());
+            args.push("--tls-key".to_string());
+            args.push(key.display().to_string());
+        }
+        args
+    }
+}
+
+pub(super) fn run(command: &str, args: Vec<String>) -> Result<(), String> {
+    match command {
+        "install-service" => {
+            let options = parse_install_args(args, env_token())?;
+            install(&options)
+        }
+        "uninstall-service" => {
+            require_user_flag(&args)?;
+            uninstall()
+        }
+        "service-status" => {
+            require_user_flag(&args)?;
+            status()
+        }
+        _ => Err(format!("Unknown command: {command}")),
+    }
+}
+
+fn env_token() -> Option<String> {
+    std::env::var(TOKEN_ENV_VAR)
+        .ok()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+}
+
+fn require_user_flag(args: &[String]) -> Result<(), String> {
+    for arg in args {
+        if arg != "--user" {
+            return Err(format!("Unknown argument: {arg}"));
+        }
+    }
+    if cfg!(target_os = "linux") && !args.iter().any(|arg| arg == "--user") {
+        return Err("Only systemd user services are supported; pass --user.".to_string());
+    }
+    Ok(())
+}
+
+fn parse_install_args(
+    args: Vec<String>,
+    env_token: Option<String>,
+) -> Result<InstallOptions, String> {
+    let mut options = InstallOptions {
+        token: env_token,
+        ..InstallOptions::default()
+    };
+    let mut user = false;
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        let mut value = |flag: &str| -> Result<String, String> {
+            let value = args
+                .next()
+                .ok_or_else(|| format!("{flag} requires a value"))?;
+            let trimmed = value.trim();
+            if trimmed.is_empty() {
+                return Err(format!("{flag} requires a non-empty value"));
+            }
+            Ok(trimmed.to_string())
+        };
+        match arg.as_str() {
+            "--user" => user = true,
+            "--listen" => options.listen = Some(value("--listen")?),
+            "--listen-unix" => options.listen_unix = Some(PathBuf::from(value("--listen-unix")?)),
+            "--data-dir" => options.data_dir = Some(PathBuf::from(value("--data-dir")?)),
+            "--token" => options.token = Some(value("--token")?),
+            "--tls-cert" => options.tls_cert = Some(PathBuf::from(value("--tls-cert")?)),
+            "--tls-key" => options.tls_key = Some(PathBuf::from(value("--tls-key")?)),
+            _ => return Err(format!("Unknown argument: {arg}")),
+        }
+    }
+
+    if cfg!(target_os = "linux") && !user {
+        return Err("Only systemd user services are supported; pass --user.".to_string());
+    }
+    if options.listen.is_some() && options.listen_unix.is_some() {
+        return Err("--listen and --listen-unix cannot be combined".to_string());
+    }
+    if options.tls_cert.is_some() != options.tls_key.is_some() {
+        return Err("--tls-cert and --tls-key must be given together".to_string());
+    }
+    if options.tls_cert.is_some() && options.listen_unix.is_some() {
+        return Err("--tls-cert only applies to the TCP listener".to_string());
+    }
+    if let Some(listen) = &options.listen {
+        listen
+            .parse::<std::net::SocketAddr>()
+            .map_err(|err| format!("Invalid --listen address `{listen}`: {err}"))?;
+    }
+    let has_scoped_tokens = TokenStore::load(tokens_path(&options.data_dir()))
+        .map(|store| !store.is_empty())
+        .unwrap_or(false);
+    if options.listen_unix.is_none() && options.token.is_none() && !has_scoped_tokens {
+        return Err(format!(
+            "A TCP service needs --token (or {TOKEN_ENV_VAR}) or scoped tokens in the data dir."
+        ));
+    }
+    Ok(options)
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn current_binary() -> Result<PathBuf, String> {
+    std::env::current_exe()
+        .and_then(|path| path.canonicalize())
+        .map_err(|err| format!("Unable to resolve daemon binary path: {err}"))
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn home_dir() -> Result<PathBuf, String> {
+    std::env::var_os("HOME")
+        .map(PathBuf::from)
+        .ok_or_else(|| "HOME is not set".to_string())
+}
+
+/// Create `path` owner-only so its contents are never readable by others,
+/// not even briefly. An existing file is restricted before it is truncated
+/// and rewritten.
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn write_private_file(path: &Path, contents: &str) -> Result<(), String> {
+    use std::io::Write;
+    use std::os::unix::fs::{OpenOptionsExt, PermissionsExt};
+
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)
+            .map_err(|err| format!("Failed to create {}: {err}", parent.display()))?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .mode(0o600)
+        .open(path)
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))?;
+    file.set_permissions(std::fs::Permissions::from_mode(0o600))
+        .map_err(|err| format!("Failed to restrict {}: {err}", path.display()))?;
+    file.set_len(0)
+        .and_then(|()| file.write_all(contents.as_bytes()))
+        .map_err(|err| format!("Failed to write {}: {err}", path.display()))
+}
+
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn run_tool(program: &str, args: &[&str]) -> Result<String, String> {
+    let output = Command::new(program)
+        .args(args)
+        .output()
+        .map_err(|err| format!("Failed to run {program}: {err}"))?;
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    if output.status.success() {
+        return Ok(stdout);
+    }
+    let stderr = String::from_utf8_lossy(&output.stderr).trim().to_string();
+    Err(format!(
+        "{program} {} failed: {}",
+        args.join(" "),
+        if stderr.is_empty() { stdout } else { stderr }
+    ))
+}
+
+/// Quotes an `ExecStart=` argument per systemd.syntax(7); `%` starts a
+/// specifier, so it is doubled.
+#[cfg(any(target_os = "linux", test))]
+fn systemd_quote(arg: &str) -> String {
+    let escaped = arg
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('%', "%%");
+    format!("\"{escaped}\"")
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn render_systemd_unit(binary: &Path, args: &[String], env_file: &Path) -> String {
+    let exec_start = std::iter::once(binary.display().to_string())
+        .chain(args.iter().cloned())
+        .map(|arg| systemd_quote(&arg))
+        .collect::<Vec<_>>()
+        .join(" ");
+    format!(
+        "\
+[Unit]
+Description=Codex Monitor daemon
+After=network-online.target
+
+[Service]
+Type=simple
+EnvironmentFile=-{env_file}
+Environment={SERVICE_ENV_VAR}=systemd
+ExecStart={exec_start}
+Restart=on-failure
+RestartSec=2
+
+[Install]
+WantedBy=default.target
+",
+        env_file = env_file.display()
+    )
+}
+
+#[cfg(any(target_os = "linux", test))]
+fn render_env_file(token: Option<&str>) -> String {
+    match token {
+        Some(token) => format!("{TOKEN_ENV_VAR}={token}\n"),
+        None => String::new(),
+    }
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn xml_escape(value: &str) -> String {
+    value
+        .replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(any(target_os = "macos", test))]
+fn render_launchd_plist(
+    binary: &Path,
+    args: &[String],
+    token: Option<&str>,
+    log_path: &Path,
+) -> String {
+    let program_arguments = std::iter::once(binary.display().to_string())
+        .chain(args.iter().cloned())
+        .map(|arg| format!("    <string>{}</string>\n", xml_escape(&arg)))
+        .collect::<String>();
+    let token_entry = token
+        .map(|token| {
+            format!(
+                "    <key>{TOKEN_ENV_VAR}</key>\n    <string>{}</string>\n",
+                xml_escape(token)
+            )
+        })
+        .unwrap_or_default();
+    let log_path = xml_escape(&log_path.display().to_string());
+    format!(
+        "\
+<?xml version=\"1.0\" encoding=\"UTF-8\"?>
+<!DOCTYPE plist PUBLIC \"-//Apple//DTD PLIST 1.0//EN\" \"http://www.apple.com/DTDs/PropertyList-1.0.dtd\">
+<plist version=\"1.0\">
+<dict>
+  <key>Label</key>
+  <string>{LAUNCHD_LABEL}</string>
+  <key>ProgramArguments</key>
+  <array>
+{program_arguments}  </array>
+  <key>EnvironmentVariables</key>
+  <dict>
+    <key>{SERVICE_ENV_VAR}</key>
+    <string>launchd</string>
+{token_entry}  </dict>
+  <key>RunAtLoad</key>
+  <true/>
+  <key>KeepAlive</key>
+  <dict>
+    <key>SuccessfulExit</key>
+    <false/>
+  </dict>
+  <key>StandardErrorPath</key>
+  <string>{log_path}</string>
+</dict>
+</plist>
+"
+    )
+}
+
+#[cfg(target_os = "linux")]
+fn systemd_unit_path() -> Result<PathBuf, String> {
+    let config_dir = match std::env::var_os("XDG_CONFIG_HOME") {
+        Some(dir) if !dir.is_empty() => PathBuf::from(dir),
+        _ => home_dir()?.join(".config"),
+    };
+    Ok(config_dir
+        .join("systemd")
+        .join("user")
+        .join(SYSTEMD_UNIT_NAME))
+}
+
+#[cfg(target_os = "linux")]
+fn install(options: &InstallOptions) -> Result<(), String> {
+    let data_dir = options.data_dir();
+    let env_file = data_dir.join(ENV_FILE_NAME);
+    write_private_file(&env_file, &render_env_file(options.token.as_deref()))?;
+    let unit_path = systemd_unit_path()?;
+    let unit = render_systemd_unit(&current_binary()?, &options.daemon_args(), &env_file);
+    write_private_file(&unit_path, &unit)?;
+    run_tool("systemctl", &["--user", "daemon-reload"])?;
+    run_tool(
+        "systemctl",
+        &["--user", "enable", "--now", SYSTEMD_UNIT_NAME],
+    )?;
+    println!("Installed {}", unit_path.display());
+    println!("Token file: {}", env_file.display());
+    println!("Run `loginctl enable-linger $USER` to keep the daemon running after logout.");
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn uninstall() -> Result<(), String> {
+    let unit_path = systemd_unit_path()?;
+    if !unit_path.exists() {
+        return Err(format!("{SYSTEMD_UNIT_NAME} is not installed"));
+    }
+    let env_file = std::fs::read_to_string(&unit_path).ok().and_then(|unit| {
+        unit.lines()
+            .find_map(|line| line.strip_prefix("EnvironmentFile=-"))
+            .map(|path| PathBuf::from(path.trim()))
+    });
+    let _ = run_tool(
+        "systemctl",
+        &["--user", "disable", "--now", SYSTEMD_UNIT_NAME],
+    );
+    std::fs::remove_file(&unit_path)
+        .map_err(|err| format!("Failed to remove {}: {err}", unit_path.display()))?;
+    if let Some(env_file) = env_file.filter(|path| path.ends_with(ENV_FILE_NAME)) {
+        let _ = std::fs::remove_file(env_file);
+    }
+    run_tool("systemctl", &["--user", "daemon-reload"])?;
+    println!("Removed {}", unit_path.display());
+    Ok(())
+}
+
+#[cfg(target_os = "linux")]
+fn status() -> Result<(), String> {
+    let unit_path = systemd_unit_path()?;
+    if !unit_path.exists() {
+        println!("{SYSTEMD_UNIT_NAME}: not installed");
+        return Ok(());
+    }
+    // Both commands exit non-zero for disabled/inactive units; the text is
+    // what matters.
+    let query = |verb: &str| {
+        run_tool("systemctl", &["--user", verb, SYSTEMD_UNIT_NAME])
+            .unwrap_or_else(|err| err.rsplit(": ").next().unwrap_or_default().to_string())
+    };
+    println!("{SYSTEMD_UNIT_NAME}: installed ({})", unit_path.display());
+    println!("enabled: {}", query("is-enabled"));
+    println!("active: {}", query("is-active"));
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn launchd_plist_path() -> Result<PathBuf, String> {
+    Ok(home_dir()?
+        .join("Library")
+        .join("LaunchAgents")
+        .join(format!("{LAUNCHD_LABEL}.plist")))
+}
+
+#[cfg(target_os = "macos")]
+fn launchd_domain() -> String {
+    // SAFETY: getuid has no preconditions and cannot fail.
+    format!("gui/{}", unsafe { libc::getuid() })
+}
+
+#[cfg(target_os = "macos")]
+fn install(options: &InstallOptions) -> Result<(), String> {
+    let data_dir = options.data_dir();
+    std::fs::create_dir_all(&data_dir)
+        .map_err(|err| format!("Failed to create {}: {err}", data_dir.display()))?;
+    let plist_path = launchd_plist_path()?;
+    let plist = render_launchd_plist(
+        &current_binary()?,
+        &options.daemon_args(),
+        options.token.as_deref(),
+        &data_dir.join(LOG_FILE_NAME),
+    );
+    write_private_file(&plist_path, &plist)?;
+    let domain = launchd_domain();
+    let _ = run_tool(
+        "launchctl",
+        &["bootout", &format!("{domain}/{LAUNCHD_LABEL}")],
+    );
+    run_tool(
+        "launchctl",
+        &["bootstrap", &domain, &plist_path.display().to_string()],
+    )?;
+    println!("Installed {}", plist_path.display());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn uninstall() -> Result<(), String> {
+    let plist_path = launchd_plist_path()?;
+    if !plist_path.exists() {
+        return Err(format!("{LAUNCHD_LABEL} is not installed"));
+    }
+    let _ = run_tool(
+        "launchctl",
+        &["bootout", &format!("{}/{LAUNCHD_LABEL}", launchd_domain())],
+    );
+    std::fs::remove_file(&plist_path)
+        .map_err(|err| format!("Failed to remove {}: {err}", plist_path.display()))?;
+    println!("Removed {}", plist_path.display());
+    Ok(())
+}
+
+#[cfg(target_os = "macos")]
+fn status() -> Result<(), String> {
+    let plist_path = launchd_plist_path()?;
+    if !plist_path.exists() {
+        println!("{LAUNCHD_LABEL}: not installed");
+        return Ok(());
+    }
+    println!("{LAUNCHD_LABEL}: installed ({})", plist_path.display());
+    match run_tool(
+        "launchctl",
+        &["print", &format!("{}/{LAUNCHD_LABEL}", launchd_domain())],
+    ) {
+        Ok(output) => {
+            let state = output
+                .lines()
+                .find_map(|line| line.trim().strip_prefix("state = "))
+                .unwrap_or("unknown");
+            println!("loaded: yes");
+            println!("state: {state}");
+        }
+        Err(_) => println!("loaded: no"),
+    }
+    Ok(())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn install(_options: &InstallOptions) -> Result<(), String> {
+    Err(
+        "Service installation is only supported on Linux (systemd) and macOS (launchd)."
+            .to_string(),
+    )
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn uninstall() -> Result<(), String> {
+    install(&InstallOptions::default())
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn status() -> Result<(), String> {
+    install(&InstallOptions::default())
+}
+
+#[cfg(test)]
+mod tests {
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    use super::write_private_file;
+    use super::{
+        parse_install_args, render_env_file, render_launchd_plist, render_systemd_unit,
+        systemd_quote,
+    };
+    use std::path::Path;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|value| value.to_string()).collect()
+    }
+
+    #[test]
+    fn install_args_validate_listener_and_token() {
+        let options = parse_install_args(
+            args(&[
+                "--user",
+                "--listen",
+                "0.0.0.0:4732",
+                "--data-dir",
+                "/srv/cm",
+            ]),
+            Some("secret".to_string()),
+        )
+        .expect("valid options");
+        assert_eq!(options.token.as_deref(), Some("secret"));
+        assert_eq!(
+            options.daemon_args(),
+            args(&["--listen", "0.0.0.0:4732", "--data-dir", "/srv/cm"])
+        );
+
+        let unix = parse_install_args(
+            args(&[
+                "--user",
+                "--listen-unix",
+                "/run/cm.sock",
+                "--data-dir",
+                "/srv/cm",
+            ]),
+            None,
+        )
+        .expect("unix socket needs no token");
+        assert_eq!(
+            unix.daemon_args()[..2],
+            args(&["--listen-unix", "/run/cm.sock"])
+        );
+
+        let missing_token = format!("/tmp/codex-monitor-service-{}", uuid::Uuid::new_v4());
+        assert!(parse_install_args(args(&["--user", "--data-dir", &missing_token]), None).is_err());
+        assert!(parse_install_args(
+            args(&["--user", "--tls-cert", "/c.pem"]),
+            Some("secret".to_string())
+        )
+        .is_err());
+        assert!(parse_install_args(args(&["--user", "--bogus"]), None).is_err());
+    }
+
+    #[test]
+    fn systemd_unit_quotes_exec_start_and_keeps_token_out() {
+        let unit = render_systemd_unit(
+            Path::new("/opt/Codex Monitor/codex-monitor-daemon"),
+            &args(&["--data-dir", "/home/me/100%"]),
+            Path::new("/home/me/.local/share/codex-monitor-daemon/daemon.env"),
+        );
+        assert!(unit.contains(
+            "ExecStart=\"/opt/Codex Monitor/codex-monitor-daemon\" \"--data-dir\" \"/home/me/100%%\""
+        ));
+        assert!(
+            unit.contains("EnvironmentFile=-/home/me/.local/share/codex-monitor-daemon/daemon.env")
+        );
+        assert!(unit.contains("Environment=CODEX_MONITOR_DAEMON_SERVICE=systemd"));
+        assert_eq!(systemd_quote("a\"b"), "\"a\\\"b\"");
+        assert_eq!(
+            render_env_file(Some("secret")),
+            "CODEX_MONITOR_DAEMON_TOKEN=secret\n"
+        );
+    }
+
+    #[test]
+    fn launchd_plist_escapes_arguments() {
+        let plist = render_launchd_plist(
+            Path::new("/Applications/Codex Monitor.app/daemon"),
+            &args(&["--data-dir", "/tmp/a&b"]),
+            Some("t<k>"),
+            Path::new("/tmp/daemon.log"),
+        );
+        assert!(plist.contains("<string>/tmp/a&amp;b</string>"));
+        assert!(plist.contains("<string>t&lt;k&gt;</string>"));
+        assert!(plist.contains("<string>launchd</string>"));
+    }
+
+    #[cfg(any(target_os = "linux", target_os = "macos"))]
+    #[test]
+    fn private_files_are_restricted_and_fully_rewritten() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let dir = std::env::temp_dir().join(format!(
+            "codex-monitor-service-{}-{}",
+            std::process::id(),
+            uuid::Uuid::new_v4().simple()
+        ));
+        let path = dir.join("daemon.env");
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        std::fs::write(
+            &path,
+            "CODEX_MONITOR_DAEMON_TOKEN=a-much-longer-old-token\n",
+        )
+        .expect("seed file");
+        std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644))
+            .expect("loosen seed file");
+
+        write_private_file(&path, "CODEX_MONITOR_DAEMON_TOKEN=new\n").expect("write");
+
+        let mode = std::fs::metadata(&path)
+            .expect("metadata")
+            .permissions()
+            .mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(
+            std::fs::read_to_string(&path).expect("read"),
+            "CODEX_MONITOR_DAEMON_TOKEN=new\n"
+        );
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
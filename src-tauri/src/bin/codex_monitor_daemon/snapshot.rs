@@ -0,0 +1,289 @@
+//! State a client needs to rebuild its view after missing events: the
+//! `daemon_state_snapshot` RPC and the `codex/eventStreamSnapshot`
+//! notification sent on connect and after `codex/eventStreamLagged`.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::backend::events::AppServerEvent;
+use crate::backend::protocol::{ServerNotification, ServerRequest};
+
+/// Unanswered server requests kept at most; the oldest are dropped first so a
+/// client that never answers cannot grow the map without bound.
+const MAX_PENDING_SERVER_REQUESTS: usize = 256;
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct PendingServerRequest {
+    pub(super) workspace_id: String,
+    pub(super) request_id: Value,
+    pub(super) method: String,
+    pub(super) thread_id: Option<String>,
+    pub(super) params: Value,
+    pub(super) received_at_ms: u128,
+}
+
+/// Approvals and other app-server requests that no client has answered yet.
+#[derive(Default)]
+pub(super) struct PendingServerRequests {
+    requests: HashMap<(String, String), PendingServerRequest>,
+}
+
+fn request_key(workspace_id: &str, request_id: &Value) -> (String, String) {
+    let id = match request_id {
+        Value::String(raw) => raw.clone(),
+        other => other.to_string(),
+    };
+    (workspace_id.to_string(), id)
+}
+
+impl PendingServerRequests {
+    /// Records server requests and forgets the ones a finished turn or a
+    /// `serverRequest/resolved` notification made moot.
+    pub(super) fn observe(&mut self, event: &AppServerEvent) {
+        let message = &event.message;
+        if let Some(request) = ServerRequest::parse(message) {
+            self.insert(PendingServerRequest {
+                workspace_id: event.workspace_id.clone(),
+                thread_id: request.thread_id(),
+                request_id: request.id,
+                method: request.method,
+                params: request.params,
+                received_at_ms: now_millis(),
+            });
+            return;
+        }
+
+        let method = message.get("method").and_then(Value::as_str).unwrap_or("");
+        if method == "serverRequest/resolved" {
+            if let Some(request_id) = message
+                .get("params")
+                .and_then(|params| params.get("requestId"))
+            {
+                self.resolve(&event.workspace_id, request_id);
+            }
+            return;
+        }
+
+        let finished_thread = match ServerNotification::parse(message) {
+            Some(ServerNotification::TurnCompleted { thread_id, .. })
+            | Some(ServerNotification::TurnError {
+                thread_id: Some(thread_id),
+                will_retry: false,
+                ..
+            }) => thread_id,
+            _ => return,
+        };
+        self.requests.retain(|_, request| {
+            request.workspace_id != event.workspace_id
+                || request.thread_id.as_deref() != Some(finished_thread.as_str())
+        });
+    }
+
+    pub(super) fn resolve(&mut self, workspace_id: &str, request_id: &Value) {
+        self.requests.remove(&request_key(workspace_id, request_id));
+    }
+
+    /// Oldest first.
+    pub(super) fn list(&self) -> Vec<PendingServerRequest> {
+        let mut requests: Vec<PendingServerRequest> = self.requests.values().cloned().collect();
+        requests.sort_by_key(|request| request.received_at_ms);
+        requests
+    }
+
+    fn insert(&mut self, request: PendingServerRequest) {
+        let key = request_key(&request.workspace_id, &request.request_id);
+        self.requests.insert(key, request);
+        while self.requests.len() > MAX_PENDING_SERVER_REQUESTS {
+            let Some(oldest) = self
+                .requests
+                .iter()
+                .min_by_key(|(_, request)| request.received_at_ms)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.requests.remove(&oldest);
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct WorkspaceConnection {
+    pub(super) id: String,
+    pub(super) name: String,
+    pub(super) connected: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ActiveTurn {
+    pub(super) workspace_id: String,
+    pub(super) thread_id: String,
+    pub(super) turn_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct RunningTerminal {
+    pub(super) workspace_id: String,
+    pub(super) terminal_id: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct DaemonStateSnapshot {
+    pub(super) generated_at_ms: u128,
+    pub(super) workspaces: Vec<WorkspaceConnection>,
+    pub(super) active_turns: Vec<ActiveTurn>,
+    pub(super) pending_requests: Vec<PendingServerRequest>,
+    pub(super) terminals: Vec<RunningTerminal>,
+}
+
+impl DaemonStateSnapshot {
+    pub(super) fn new(
+        mut workspaces: Vec<WorkspaceConnection>,
+        mut active_turns: Vec<ActiveTurn>,
+        pending_requests: Vec<PendingServerRequest>,
+        mut terminals: Vec<RunningTerminal>,
+    ) -> Self {
+        workspaces.sort_by(|a, b| a.id.cmp(&b.id));
+        active_turns
+            .sort_by(|a, b| (&a.workspace_id, &a.thread_id).cmp(&(&b.workspace_id, &b.thread_id)));
+        terminals.sort_by(|a, b| {
+            (&a.workspace_id, &a.terminal_id).cmp(&(&b.workspace_id, &b.terminal_id))
+        });
+        // Requests from a workspace that has since disconnected cannot be
+        // answered any more.
+        let pending_requests = pending_requests
+            .into_iter()
+            .filter(|request| {
+                workspaces
+                    .iter()
+                    .any(|workspace| workspace.connected && workspace.id == request.workspace_id)
+            })
+            .collect();
+        Self {
+            generated_at_ms: now_millis(),
+            workspaces,
+            active_turns,
+            pending_requests,
+            terminals,
+        }
+    }
+}
+
+pub(super) fn build_snapshot_notification(snapshot: &DaemonStateSnapshot) -> Option<String> {
+    serde_json::to_string(&json!({
+        "method": "app-server-event",
+        "params": {
+            "workspace_id": "__daemon__",
+            "message": {
+                "method": "codex/eventStreamSnapshot",
+                "params": snapshot,
+            }
+        }
+    }))
+    .ok()
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DaemonStateSnapshot, PendingServerRequests, WorkspaceConnection};
+    use crate::backend::events::AppServerEvent;
+    use serde_json::{json, Value};
+
+    fn event(workspace_id: &str, message: Value) -> AppServerEvent {
+        AppServerEvent {
+            workspace_id: workspace_id.to_string(),
+            message,
+            seq: None,
+        }
+    }
+
+    #[test]
+    fn pending_requests_track_approvals_until_answered_or_turn_ends() {
+        let mut pending = PendingServerRequests::default();
+        pending.observe(&event(
+            "ws-1",
+            json!({
+                "id": 7,
+                "method": "item/commandExecution/requestApproval",
+                "params": { "threadId": "thread-1", "turnId": "turn-1" }
+            }),
+        ));
+        pending.observe(&event(
+            "ws-1",
+            json!({
+                "id": "req-8",
+                "method": "item/fileChange/requestApproval",
+                "params": { "threadId": "thread-2" }
+            }),
+        ));
+        pending.observe(&event(
+            "ws-1",
+            json!({ "method": "item/agentMessage/delta", "params": { "threadId": "thread-1" } }),
+        ));
+        assert_eq!(pending.list().len(), 2);
+
+        pending.resolve("ws-1", &json!(7));
+        let remaining = pending.list();
+        assert_eq!(remaining.len(), 1);
+        assert_eq!(remaining[0].request_id, json!("req-8"));
+        assert_eq!(remaining[0].thread_id.as_deref(), Some("thread-2"));
+
+        pending.observe(&event(
+            "ws-1",
+            json!({
+                "method": "turn/completed",
+                "params": { "threadId": "thread-2", "turn": { "id": "turn-2" } }
+            }),
+        ));
+        assert!(pending.list().is_empty());
+    }
+
+    #[test]
+    fn snapshot_drops_requests_of_disconnected_workspaces() {
+        let mut pending = PendingServerRequests::default();
+        for workspace_id in ["ws-on", "ws-off"] {
+            pending.observe(&event(
+                workspace_id,
+                json!({ "id": 1, "method": "item/tool/requestUserInput", "params": {} }),
+            ));
+        }
+        let workspaces = vec![
+            WorkspaceConnection {
+                id: "ws-on".to_string(),
+                name: "On".to_string(),
+                connected: true,
+            },
+            WorkspaceConnection {
+                id: "ws-off".to_string(),
+                name: "Off".to_string(),
+                connected: false,
+            },
+        ];
+
+        let snapshot = DaemonStateSnapshot::new(workspaces, Vec::new(), pending.list(), Vec::new());
+
+        assert_eq!(snapshot.pending_requests.len(), 1);
+        assert_eq!(snapshot.pending_requests[0].workspace_id, "ws-on");
+        let value = serde_json::to_value(&snapshot).expect("serialize snapshot");
+        assert_eq!(
+            value["pendingRequests"][0]["method"],
+            "item/tool/requestUserInput"
+        );
+        assert!(value["activeTurns"].as_array().is_some());
+    }
+}
@@ -0,0 +1,448 @@
+//! Named, scoped auth tokens stored in `<data-dir>/tokens.json`. Only SHA-256
+//! hashes are persisted; the plaintext is returned once, when minted. The
+//! legacy `--token` value keeps working as an unscoped admin token.
+
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+const TOKENS_FILE: &str = "tokens.json";
+const TOKEN_PREFIX: &str = "cmd_";
+
+/// Scopes are ordered: each one includes everything below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub(super) enum TokenScope {
+    ReadOnly,
+    Operator,
+    Admin,
+}
+
+impl TokenScope {
+    pub(super) fn parse(value: &str) -> Result<Self, String> {
+        match value.trim() {
+            "read-only" => Ok(Self::ReadOnly),
+            "operator" => Ok(Self::Operator),
+            "admin" => Ok(Self::Admin),
+            other => Err(format!(
+                "unknown token scope `{other}` (expected read-only, operator or admin)"
+            )),
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::ReadOnly => "read-only",
+            Self::Operator => "operator",
+            Self::Admin => "admin",
+        }
+    }
+}
+
+/// Who a connection authenticated as. `Root` covers the legacy shared token,
+/// unauthenticated listeners and Orbit, which authenticates upstream.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(super) enum Principal {
+    Root,
+    Token(String),
+}
+
+impl Principal {
+    pub(super) fn token_name(&self) -> Option<&str> {
+        match self {
+            Self::Root => None,
+            Self::Token(name) => Some(name),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub(super) struct ScopedToken {
+    pub(super) name: String,
+    pub(super) scope: TokenScope,
+    token_sha256: String,
+    pub(super) created_at_ms: u64,
+}
+
+#[derive(Default, Serialize, Deserialize)]
+struct TokensFile {
+    #[serde(default)]
+    tokens: Vec<ScopedToken>,
+}
+
+pub(super) struct TokenStore {
+    path: PathBuf,
+    tokens: Vec<ScopedToken>,
+}
+
+pub(super) fn tokens_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(TOKENS_FILE)
+}
+
+impl TokenStore {
+    pub(super) fn load(path: PathBuf) -> Result<Self, String> {
+        let tokens = if path.exists() {
+            let data = std::fs::read_to_string(&path).map_err(|err| err.to_string())?;
+            serde_json::from_str::<TokensFile>(&data)
+                .map_err(|err| format!("Invalid {}: {err}", path.display()))?
+                .tokens
+        } else {
+            Vec::new()
+        };
+        Ok(Self { path, tokens })
+    }
+
+    pub(super) fn is_empty(&self) -> bool {
+        self.tokens.is_empty()
+    }
+
+    pub(super) fn list(&self) -> &[ScopedToken] {
+        &self.tokens
+    }
+
+    pub(super) fn scope_of(&self, name: &str) -> Option<TokenScope> {
+        self.tokens
+            .iter()
+            .find(|token| token.name == name)
+            .map(|token| token.scope)
+    }
+
+    /// Checks `provided` against the legacy token and every stored hash
+    /// without short-circuiting, so timing does not reveal which matched.
+    pub(super) fn authenticate(
+        &self,
+        legacy_token: Option<&str>,
+        provided: &str,
+    ) -> Option<Principal> {
+        let provided_hash = sha256(provided.as_bytes());
+        let mut principal = None;
+        if let Some(legacy) = legacy_token {
+            if constant_time_eq(&sha256(legacy.as_bytes()), &provided_hash) {
+                principal = Some(Principal::Root);
+            }
+        }
+        for token in &self.tokens {
+            let Some(stored) = decode_hex(&token.token_sha256) else {
+                continue;
+            };
+            if constant_time_eq(&stored, &provided_hash) && principal.is_none() {
+                principal = Some(Principal::Token(token.name.clone()));
+            }
+        }
+        principal
+    }
+
+    /// Creates a token and returns its plaintext, which is not stored.
+    pub(super) fn mint(&mut self, name: &str, scope: TokenScope) -> Result<String, String> {
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("token name is required".to_string());
+        }
+        if self.scope_of(name).is_some() {
+            return Err(format!("a token named `{name}` already exists"));
+        }
+        let secret = format!(
+            "{TOKEN_PREFIX}{}{}",
+            uuid::Uuid::new_v4().simple(),
+            uuid::Uuid::new_v4().simple()
+        );
+        let created_at_ms = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|duration| duration.as_millis() as u64)
+            .unwrap_or(0);
+        let mut tokens = self.tokens.clone();
+        tokens.push(ScopedToken {
+            name: name.to_string(),
+            scope,
+            token_sha256: encode_hex(&sha256(secret.as_bytes())),
+            created_at_ms,
+        });
+        self.persist(tokens)?;
+        Ok(secret)
+    }
+
+    pub(super) fn revoke(&mut self, name: &str) -> Result<bool, String> {
+        let mut tokens = self.tokens.clone();
+        let before = tokens.len();
+        tokens.retain(|token| token.name != name.trim());
+        if tokens.len() == before {
+            return Ok(false);
+        }
+        self.persist(tokens)?;
+        Ok(true)
+    }
+
+    fn persist(&mut self, tokens: Vec<ScopedToken>) -> Result<(), String> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent).map_err(|err| err.to_string())?;
+        }
+        let file = TokensFile { tokens };
+        let data = serde_json::to_string_pretty(&file).map_err(|err| err.to_string())?;
+        let temp_path = self
+            .path
+            .with_extension(format!("{}.tmp", uuid::Uuid::new_v4().simple()));
+        std::fs::write(&temp_path, data).map_err(|err| err.to_string())?;
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&temp_path, std::fs::Permissions::from_mode(0o600))
+                .map_err(|err| err.to_string())?;
+        }
+        std::fs::rename(&temp_path, &self.path).map_err(|err| {
+            let _ = std::fs::remove_file(&temp_path);
+            err.to_string()
+        })?;
+        self.tokens = file.tokens;
+        Ok(())
+    }
+}
+
+/// Minimum scope needed to call `method`. Unlisted methods fail closed and
+/// require admin scope.
+pub(super) fn required_scope(method: &str) -> TokenScope {
+    method_scope(method).unwrap_or(TokenScope::Admin)
+}
+
+/// Scope of every RPC method the daemon dispatches; `None` for anything
+/// else. New RPC methods must be listed here.
+pub(super) fn method_scope(method: &str) -> Option<TokenScope> {
+    Some(match method {
+        "ping"
+        | "daemon_info"
+        | "daemon_metrics"
+        | "daemon_state_snapshot"
+        | "list_workspaces"
+        | "is_workspace_path_dir"
+        | "worktree_setup_status"
+        | "workspace_process_stats"
+        | "list_workspace_files"
+        | "read_workspace_file"
+        | "file_read"
+        | "get_codex_config_path"
+        | "get_config_model"
+        | "list_threads"
+        | "list_mcp_server_status"
+        | "background_callback_metrics"
+        | "replay_events"
+        | "thread_live_subscribe"
+        | "thread_live_unsubscribe"
+        | "model_list"
+        | "experimental_feature_list"
+        | "collaboration_mode_list"
+        | "get_agents_settings"
+        | "read_agent_config_toml"
+        | "account_rate_limits"
+        | "account_read"
+        | "skills_list"
+        | "apps_list"
+        | "get_git_status"
+        | "watch_git_status"
+        | "unwatch_git_status"
+        | "list_git_roots"
+        | "get_git_diffs"
+        | "get_git_log"
+        | "get_git_commit_diff"
+        | "get_git_remote"
+        | "list_git_branches"
+        | "list_git_stashes"
+        | "get_git_stash_diff"
+        | "get_git_conflicts"
+        | "get_github_issues"
+        | "get_github_pull_requests"
+        | "get_github_pull_request_diff"
+        | "get_github_pull_request_comments"
+        | "prompts_list"
+        | "prompts_workspace_dir"
+        | "prompts_global_dir"
+        | "local_usage_snapshot"
+        | "is_macos_debug_build" => TokenScope::ReadOnly,
+        "daemon_shutdown"
+        | "audit_log_query"
+        | "token_list"
+        | "token_mint"
+        | "token_revoke"
+        | "get_app_settings"
+        | "update_app_settings"
+        | "add_workspace"
+        | "add_workspace_from_git_url"
+        | "add_clone"
+        | "add_worktree"
+        | "remove_workspace"
+        | "remove_worktree"
+        | "update_workspace_settings"
+        | "update_workspace_codex_bin"
+        | "file_write"
+        | "terminal_open"
+        | "terminal_write"
+        | "terminal_resize"
+        | "terminal_close"
+        | "orbit_connect_test"
+        | "orbit_sign_in_start"
+        | "orbit_sign_in_poll"
+        | "orbit_sign_out"
+        | "codex_login"
+        | "codex_login_cancel"
+        | "codex_update"
+        | "set_codex_feature_flag"
+        | "set_agents_core_settings"
+        | "create_agent"
+        | "update_agent"
+        | "delete_agent"
+        | "write_agent_config_toml" => TokenScope::Admin,
+        "abort_merge"
+        | "abort_rebase"
+        | "apply_git_stash"
+        | "apply_worktree_changes"
+        | "archive_thread"
+        | "archive_threads"
+        | "checkout_git_branch"
+        | "codex_doctor"
+        | "commit_git"
+        | "compact_thread"
+        | "connect_workspace"
+        | "continue_merge"
+        | "create_git_branch"
+        | "create_git_stash"
+        | "drop_git_stash"
+        | "fetch_git"
+        | "fork_thread"
+        | "generate_commit_message"
+        | "generate_run_metadata"
+        | "get_open_app_icon"
+        | "menu_set_accelerators"
+        | "open_workspace_in"
+        | "pop_git_stash"
+        | "prompts_create"
+        | "prompts_delete"
+        | "prompts_move"
+        | "prompts_update"
+        | "pull_git"
+        | "push_git"
+        | "remember_approval_rule"
+        | "rename_worktree"
+        | "rename_worktree_upstream"
+        | "resolve_git_conflict"
+        | "respond_to_server_request"
+        | "resume_thread"
+        | "revert_git_all"
+        | "revert_git_file"
+        | "revert_git_hunks"
+        | "send_notification_fallback"
+        | "send_user_message"
+        | "set_thread_name"
+        | "stage_git_all"
+        | "stage_git_file"
+        | "stage_git_hunks"
+        | "start_review"
+        | "start_thread"
+        | "sync_git"
+        | "turn_interrupt"
+        | "turn_steer"
+        | "unstage_git_file"
+        | "unstage_git_hunks"
+        | "worktree_setup_mark_ran" => TokenScope::Operator,
+        _ => return None,
+    })
+}
+
+pub(super) fn forbidden_message(method: &str, required: TokenScope) -> String {
+    format!(
+        "forbidden: `{method}` requires the {} scope",
+        required.as_str()
+    )
+}
+
+fn sha256(data: &[u8]) -> Vec<u8> {
+    Sha256::digest(data).to_vec()
+}
+
+fn constant_time_eq(left: &[u8], right: &[u8]) -> bool {
+    if left.len() != right.len() {
+        return false;
+    }
+    left.iter()
+        .zip(right)
+        .fold(0u8, |diff, (a, b)| diff | (a ^ b))
+        == 0
+}
+
+fn encode_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    let pairs = value.as_bytes().chunks_exact(2);
+    if !pairs.remainder().is_empty() {
+        return None;
+    }
+    pairs
+        .map(|pair| u8::from_str_radix(std::str::from_utf8(pair).ok()?, 16).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{required_scope, tokens_path, Principal, TokenScope, TokenStore};
+
+    fn temp_store() -> (std::path::PathBuf, TokenStore) {
+        let dir =
+            std::env::temp_dir().join(format!("codex-monitor-tokens-{}", uuid::Uuid::new_v4()));
+        let store = TokenStore::load(tokens_path(&dir)).expect("load empty store");
+        (dir, store)
+    }
+
+    #[test]
+    fn minted_tokens_authenticate_until_revoked_and_survive_reload() {
+        let (dir, mut store) = temp_store();
+        assert!(store.is_empty());
+        let secret = store
+            .mint("teammate", TokenScope::ReadOnly)
+            .expect("mint token");
+        assert!(store.mint("teammate", TokenScope::Admin).is_err());
+
+        let reloaded = TokenStore::load(tokens_path(&dir)).expect("reload");
+        assert_eq!(
+            reloaded.authenticate(None, &secret),
+            Some(Principal::Token("teammate".to_string()))
+        );
+        assert_eq!(reloaded.scope_of("teammate"), Some(TokenScope::ReadOnly));
+        let persisted = std::fs::read_to_string(tokens_path(&dir)).expect("read file");
+        assert!(!persisted.contains(&secret));
+
+        assert_eq!(
+            store.authenticate(Some("legacy"), "legacy"),
+            Some(Principal::Root)
+        );
+        assert_eq!(store.authenticate(Some("legacy"), "wrong"), None);
+
+        assert_eq!(store.revoke("teammate"), Ok(true));
+        assert_eq!(store.revoke("teammate"), Ok(false));
+        assert_eq!(store.authenticate(None, &secret), None);
+        let _ = std::fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn scopes_are_ordered_and_methods_classified() {
+        assert!(TokenScope::Admin > TokenScope::Operator);
+        assert!(TokenScope::Operator > TokenScope::ReadOnly);
+        assert_eq!(required_scope("get_git_diffs"), TokenScope::ReadOnly);
+        assert_eq!(required_scope("send_user_message"), TokenScope::Operator);
+        assert_eq!(
+            required_scope("respond_to_server_request"),
+            TokenScope::Operator
+        );
+        // Resuming spawns or attaches a live session, which reads cannot do.
+        assert_eq!(required_scope("resume_thread"), TokenScope::Operator);
+        assert_eq!(required_scope("remove_workspace"), TokenScope::Admin);
+        assert_eq!(
+            required_scope("add_worktree"),
+            required_scope("remove_worktree")
+        );
+        assert_eq!(required_scope("daemon_shutdown"), TokenScope::Admin);
+        assert_eq!(required_scope("not_a_method"), TokenScope::Admin);
+        assert_eq!(TokenScope::parse("read-only"), Ok(TokenScope::ReadOnly));
+        assert!(TokenScope::parse("root").is_err());
+    }
+}
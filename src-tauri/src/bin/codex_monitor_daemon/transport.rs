@@ -1,19 +1,21 @@
 use super::rpc::{
     build_error_response, build_result_response, forward_events, parse_auth_token,
-    spawn_rpc_response_task,
+    spawn_rpc_response_task, RpcClient,
 };
 use super::*;
 
 const DAEMON_OUTBOUND_BUFFER: usize = 256;
 const DAEMON_BACKPRESSURE_TIMEOUT: Duration = Duration::from_secs(3);
 
-pub(super) async fn handle_client(
-    socket: TcpStream,
-    config: Arc<DaemonConfig>,
+pub(super) async fn handle_client<S>(
+    socket: S,
+    peer: String,
     state: Arc<DaemonState>,
     events: broadcast::Sender<DaemonEvent>,
-) {
-    let (reader, mut writer) = socket.into_split();
+) where
+    S: AsyncRead + AsyncWrite + Send + 'static,
+{
+    let (reader, mut writer) = tokio::io::split(socket);
     let mut lines = BufReader::new(reader).lines();
 
     let (out_tx, mut out_rx) = mpsc::unbounded_channel::<String>();
@@ -45,15 +47,33 @@ pub(super) async fn handle_client(
         }
     });
 
-    let mut authenticated = config.token.is_none();
+    let runtime = state.runtime_config();
+    let requires_auth = runtime.token.is_some() || !state.tokens.lock().await.is_empty();
+    let mut authenticated = !requires_auth;
     let mut events_task: Option<tokio::task::JoinHandle<()>> = None;
-    let request_limiter = Arc::new(Semaphore::new(MAX_IN_FLIGHT_RPC_PER_CONNECTION));
-    let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
+    let request_limit = runtime.max_in_flight_rpc_per_connection;
+    let request_limiter = Arc::new(Semaphore::new(request_limit));
+    let connection = Arc::new(state.metrics.register_connection(
+        peer.clone(),
+        Arc::clone(&request_limiter),
+        request_limit,
+    ));
+    let client = Arc::new(RpcClient::new(
+        format!("daemon-{}", env!("CARGO_PKG_VERSION")),
+        peer,
+    ));
 
     if authenticated {
+        client.set_principal(Principal::Root);
         let rx = events.subscribe();
         let out_tx_events = out_tx.clone();
-        events_task = Some(tokio::spawn(forward_events(rx, out_tx_events)));
+        events_task = Some(tokio::spawn(forward_events(
+            Arc::clone(&state),
+            rx,
+            out_tx_events,
+            Arc::clone(&client),
+            Arc::clone(&connection),
+        )));
     }
 
     while let Ok(Some(line)) = lines.next_line().await {
@@ -86,9 +106,13 @@ pub(super) async fn handle_client(
                 continue;
             }
 
-            let expected = config.token.clone().unwrap_or_default();
             let provided = parse_auth_token(&params).unwrap_or_default();
-            if expected != provided {
+            let principal = state
+                .tokens
+                .lock()
+                .await
+                .authenticate(state.runtime_config().token.as_deref(), &provided);
+            let Some(principal) = principal else {
                 if let Some(response) = build_error_response(id, "UNAUTHORIZED", "invalid token") {
                     if out_tx.send(response).is_err() {
                         eprintln!("[daemon] failed to send invalid-token response");
@@ -96,8 +120,9 @@ pub(super) async fn handle_client(
                     }
                 }
                 continue;
-            }
+            };
 
+            client.set_principal(principal);
             authenticated = true;
             if let Some(response) = build_result_response(id, json!({ "ok": true })) {
                 if out_tx.send(response).is_err() {
@@ -108,7 +133,13 @@ pub(super) async fn handle_client(
 
             let rx = events.subscribe();
             let out_tx_events = out_tx.clone();
-            events_task = Some(tokio::spawn(forward_events(rx, out_tx_events)));
+            events_task = Some(tokio::spawn(forward_events(
+                Arc::clone(&state),
+                rx,
+                out_tx_events,
+                Arc::clone(&client),
+                Arc::clone(&connection),
+            )));
 
             continue;
         }
@@ -119,7 +150,7 @@ pub(super) async fn handle_client(
             id,
             method,
             params,
-            client_version.clone(),
+            Arc::clone(&client),
             Arc::clone(&request_limiter),
         );
     }
@@ -136,7 +167,7 @@ fn handle_orbit_line(
     line: &str,
     state: Arc<DaemonState>,
     out_tx: mpsc::UnboundedSender<String>,
-    client_version: String,
+    client: Arc<RpcClient>,
     request_limiter: Arc<Semaphore>,
 ) {
     let message: Value = match serde_json::from_str(line) {
@@ -173,15 +204,94 @@ fn handle_orbit_line(
         return;
     }
 
-    spawn_rpc_response_task(
-        state,
-        out_tx,
-        id,
-        method,
-        params,
-        client_version,
-        request_limiter,
+    spawn_rpc_response_task(state, out_tx, id, method, params, client, request_limiter);
+}
+
+/// Bind inside a fresh owner-only directory, restrict the socket, then move
+/// it into place. Binding at `socket_path` and chmodding afterwards would
+/// leave a window where other local users could connect.
+#[cfg(unix)]
+fn bind_owner_only_socket(
+    socket_path: &std::path::Path,
+) -> std::io::Result<tokio::net::UnixListener> {
+    use std::os::unix::fs::{DirBuilderExt, PermissionsExt};
+
+    let parent = socket_path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or(std::path::Path::new("."));
+    // Kept short: socket paths are limited to about 100 bytes.
+    let staging_dir = parent.join(format!(".cm-{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&staging_dir);
+    std::fs::DirBuilder::new()
+        .mode(0o700)
+        .create(&staging_dir)?;
+    let staged_path = staging_dir.join("s");
+    let bound = tokio::net::UnixListener::bind(&staged_path).and_then(|listener| {
+        std::fs::set_permissions(&staged_path, std::fs::Permissions::from_mode(0o600))?;
+        std::fs::rename(&staged_path, socket_path)?;
+        Ok(listener)
+    });
+    let _ = std::fs::remove_dir_all(&staging_dir);
+    bound
+}
+
+#[cfg(unix)]
+pub(super) async fn run_unix_listener(
+    socket_path: &std::path::Path,
+    state: Arc<DaemonState>,
+    events_tx: broadcast::Sender<DaemonEvent>,
+) {
+    use std::os::unix::fs::FileTypeExt;
+
+    if let Ok(metadata) = std::fs::symlink_metadata(socket_path) {
+        if !metadata.file_type().is_socket() {
+            eprintln!(
+                "refusing to replace non-socket file at {}",
+                socket_path.display()
+            );
+            std::process::exit(2);
+        }
+        // A previous daemon left its socket behind; bind would fail otherwise.
+        let _ = std::fs::remove_file(socket_path);
+    }
+    if let Some(parent) = socket_path.parent() {
+        if !parent.as_os_str().is_empty() {
+            if let Err(err) = std::fs::create_dir_all(parent) {
+                eprintln!("failed to create {}: {err}", parent.display());
+                std::process::exit(2);
+            }
+        }
+    }
+    let listener = match bind_owner_only_socket(socket_path) {
+        Ok(listener) => listener,
+        Err(err) => {
+            eprintln!("failed to bind {}: {err}", socket_path.display());
+            std::process::exit(2);
+        }
+    };
+    eprintln!(
+        "codex-monitor-daemon listening on unix:{} (data dir: {})",
+        socket_path.display(),
+        state
+            .storage_path
+            .parent()
+            .unwrap_or(&state.storage_path)
+            .display()
     );
+
+    loop {
+        match listener.accept().await {
+            Ok((socket, _addr)) => {
+                let state = Arc::clone(&state);
+                let events = events_tx.clone();
+                tokio::spawn(async move {
+                    handle_client(socket, "unix".to_string(), state, events).await;
+                });
+            }
+            Err(_) => continue,
+        }
+    }
 }
 
 pub(super) async fn run_orbit_mode(
@@ -197,9 +307,9 @@ pub(super) async fn run_orbit_mode(
 
     let mut reconnect_delay = Duration::from_secs(1);
     loop {
+        let orbit_token = state.runtime_config().orbit_token;
         let ws_url =
-            match shared::orbit_core::build_orbit_ws_url(&orbit_url, config.orbit_token.as_deref())
-            {
+            match shared::orbit_core::build_orbit_ws_url(&orbit_url, orbit_token.as_deref()) {
                 Ok(value) => value,
                 Err(err) => {
                     eprintln!("invalid orbit url: {err}");
@@ -256,10 +366,29 @@ pub(super) async fn run_orbit_mode(
             }
         });
 
+        let client = Arc::new(RpcClient::new(
+            format!("daemon-{}", env!("CARGO_PKG_VERSION")),
+            "orbit".to_string(),
+        ));
+        // Orbit authenticates the runner upstream.
+        client.set_principal(Principal::Root);
+        let request_limit = state.runtime_config().max_in_flight_rpc_per_connection;
+        let request_limiter = Arc::new(Semaphore::new(request_limit));
+        let connection = Arc::new(state.metrics.register_connection(
+            "orbit".to_string(),
+            Arc::clone(&request_limiter),
+            request_limit,
+        ));
         let events_task = {
             let rx = events_tx.subscribe();
             let out_tx_events = out_tx.clone();
-            tokio::spawn(forward_events(rx, out_tx_events))
+            tokio::spawn(forward_events(
+                Arc::clone(&state),
+                rx,
+                out_tx_events,
+                Arc::clone(&client),
+                Arc::clone(&connection),
+            ))
         };
 
         if out_tx
@@ -277,8 +406,6 @@ pub(super) async fn run_orbit_mode(
             eprintln!("[daemon] failed to send orbit anchor.hello message");
         }
 
-        let client_version = format!("daemon-{}", env!("CARGO_PKG_VERSION"));
-        let request_limiter = Arc::new(Semaphore::new(MAX_IN_FLIGHT_RPC_PER_CONNECTION));
         while let Some(frame) = reader.next().await {
             match frame {
                 Ok(Message::Text(text)) => {
@@ -287,7 +414,7 @@ pub(super) async fn run_orbit_mode(
                             line,
                             Arc::clone(&state),
                             out_tx.clone(),
-                            client_version.clone(),
+                            Arc::clone(&client),
                             Arc::clone(&request_limiter),
                         );
                     }
@@ -299,7 +426,7 @@ pub(super) async fn run_orbit_mode(
                                 line,
                                 Arc::clone(&state),
                                 out_tx.clone(),
-                                client_version.clone(),
+                                Arc::clone(&client),
                                 Arc::clone(&request_limiter),
                             );
                         }
@@ -328,3 +455,37 @@ pub(super) async fn run_orbit_mode(
         reconnect_delay = (reconnect_delay * 2).min(Duration::from_secs(20));
     }
 }
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::bind_owner_only_socket;
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    #[test]
+    fn unix_socket_is_owner_only_and_staging_dir_is_removed() {
+        let dir = std::env::temp_dir().join(format!("cm-sock-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).expect("create temp dir");
+        let socket_path = dir.join("daemon.sock");
+
+        let runtime = tokio::runtime::Runtime::new().expect("runtime should initialize");
+        let listener = runtime.block_on(async { bind_owner_only_socket(&socket_path) });
+        let listener = listener.expect("bind");
+
+        let metadata = std::fs::metadata(&socket_path).expect("socket metadata");
+        assert!(metadata.file_type().is_socket());
+        assert_eq!(metadata.permissions().mode() & 0o777, 0o600);
+        let entries: Vec<_> = std::fs::read_dir(&dir)
+            .expect("read dir")
+            .map(|entry| entry.expect("entry").file_name())
+            .collect();
+        assert_eq!(entries, vec![std::ffi::OsString::from("daemon.sock")]);
+        runtime.block_on(async {
+            tokio::net::UnixStream::connect(&socket_path)
+                .await
+                .expect("connect through the moved socket");
+        });
+
+        drop(listener);
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
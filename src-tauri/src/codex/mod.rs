@@ -10,11 +10,13 @@ pub(crate) mod home;
 
 use crate::backend::app_server::spawn_workspace_session as spawn_workspace_session_inner;
 pub(crate) use crate::backend::app_server::WorkspaceSession;
+use crate::backend::background_callbacks;
 use crate::backend::events::AppServerEvent;
 use crate::event_sink::TauriEventSink;
 use crate::remote_backend;
 use crate::shared::agents_config_core;
 use crate::shared::codex_core;
+use crate::shared::event_journal_core;
 use crate::state::AppState;
 use crate::types::WorkspaceEntry;
 
@@ -47,6 +49,7 @@ fn emit_thread_live_event(app: &AppHandle, workspace_id: &str, method: &str, par
                 "method": method,
                 "params": params,
             }),
+            seq: None,
         },
     ) {
         eprintln!("failed to emit thread live event {method}: {err}");
@@ -202,6 +205,41 @@ pub(crate) async fn thread_live_unsubscribe(
     Ok(json!({ "ok": true }))
 }
 
+#[tauri::command]
+pub(crate) async fn replay_events(
+    workspace_id: String,
+    since_seq: Option<u64>,
+    limit: Option<u32>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "replay_events",
+            json!({ "workspaceId": workspace_id, "sinceSeq": since_seq, "limit": limit }),
+        )
+        .await;
+    }
+
+    event_journal_core::replay_events_core(workspace_id, since_seq.unwrap_or(0), limit).await
+}
+
+#[tauri::command]
+pub(crate) async fn background_callback_metrics(
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(&*state, app, "background_callback_metrics", json!({}))
+            .await;
+    }
+
+    serde_json::to_value(background_callbacks::background_callback_metrics())
+        .map_err(|err| err.to_string())
+}
+
 #[tauri::command]
 pub(crate) async fn fork_thread(
     workspace_id: String,
@@ -401,6 +439,7 @@ pub(crate) async fn send_user_message(
 
     codex_core::send_user_message_core(
         &state.sessions,
+        &state.workspaces,
         workspace_id,
         thread_id,
         text,
@@ -948,6 +987,7 @@ pub(crate) async fn generate_commit_message(
                             "action": "hide"
                         }
                     }),
+                    seq: None,
                 },
             ) {
                 eprintln!("failed to emit codex/backgroundThread hide event: {err}");
@@ -990,6 +1030,7 @@ pub(crate) async fn generate_run_metadata(
                             "action": "hide"
                         }
                     }),
+                    seq: None,
                 },
             ) {
                 eprintln!("failed to emit codex/backgroundThread hide event: {err}");
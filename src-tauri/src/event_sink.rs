@@ -1,6 +1,8 @@
 use tauri::{AppHandle, Emitter};
 
-use crate::backend::events::{AppServerEvent, EventSink, TerminalExit, TerminalOutput};
+use crate::backend::events::{
+    AppServerEvent, EventSink, GitStatusChanged, TerminalExit, TerminalOutput,
+};
 
 #[derive(Clone)]
 pub(crate) struct TauriEventSink {
@@ -31,4 +33,10 @@ impl EventSink for TauriEventSink {
             eprintln!("failed to emit terminal-exit: {err}");
         }
     }
+
+    fn emit_git_status_changed(&self, event: GitStatusChanged) {
+        if let Err(err) = self.app.emit("git-status-changed", event) {
+            eprintln!("failed to emit git-status-changed: {err}");
+        }
+    }
 }
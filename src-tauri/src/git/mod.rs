@@ -2,12 +2,14 @@ use serde::de::DeserializeOwned;
 use serde_json::{json, Value};
 use tauri::{AppHandle, State};
 
+use crate::event_sink::TauriEventSink;
 use crate::remote_backend;
-use crate::shared::git_ui_core;
+use crate::shared::{git_status_watch_core, git_ui_core};
 use crate::state::AppState;
 use crate::types::{
-    GitCommitDiff, GitFileDiff, GitHubIssuesResponse, GitHubPullRequestComment,
-    GitHubPullRequestDiff, GitHubPullRequestsResponse, GitLogResponse,
+    GitCommitDiff, GitConflictResolution, GitConflictStatus, GitFileDiff, GitHubIssuesResponse,
+    GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestsResponse, GitHunkSelection,
+    GitLogFilter, GitLogResponse, GitStashEntry,
 };
 
 async fn call_remote_if_enabled(
@@ -162,6 +164,57 @@ pub(crate) async fn revert_git_all(
     git_ui_core::revert_git_all_core(&state.workspaces, workspace_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn stage_git_hunks(
+    workspace_id: String,
+    path: String,
+    selection: GitHunkSelection,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "stage_git_hunks",
+        json!({ "workspaceId": &workspace_id, "path": &path, "selection": &selection })
+    );
+    git_ui_core::stage_git_hunks_core(&state.workspaces, workspace_id, path, selection).await
+}
+
+#[tauri::command]
+pub(crate) async fn unstage_git_hunks(
+    workspace_id: String,
+    path: String,
+    selection: GitHunkSelection,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "unstage_git_hunks",
+        json!({ "workspaceId": &workspace_id, "path": &path, "selection": &selection })
+    );
+    git_ui_core::unstage_git_hunks_core(&state.workspaces, workspace_id, path, selection).await
+}
+
+#[tauri::command]
+pub(crate) async fn revert_git_hunks(
+    workspace_id: String,
+    path: String,
+    selection: GitHunkSelection,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "revert_git_hunks",
+        json!({ "workspaceId": &workspace_id, "path": &path, "selection": &selection })
+    );
+    git_ui_core::revert_git_hunks_core(&state.workspaces, workspace_id, path, selection).await
+}
+
 #[tauri::command]
 pub(crate) async fn commit_git(
     workspace_id: String,
@@ -238,6 +291,43 @@ pub(crate) async fn sync_git(
     git_ui_core::sync_git_core(&state.workspaces, workspace_id).await
 }
 
+#[tauri::command]
+pub(crate) async fn watch_git_status(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "watch_git_status",
+        json!({ "workspaceId": &workspace_id })
+    );
+    git_status_watch_core::watch_git_status_core(
+        &state.git_status_watchers,
+        &state.git_status_watches,
+        &state.workspaces,
+        workspace_id,
+        TauriEventSink::new(app),
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn unwatch_git_status(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "unwatch_git_status",
+        json!({ "workspaceId": &workspace_id })
+    );
+    git_status_watch_core::unwatch_git_status_core(&state.git_status_watches, workspace_id).await
+}
+
 #[tauri::command]
 pub(crate) async fn list_git_roots(
     workspace_id: String,
@@ -288,6 +378,8 @@ pub(crate) async fn get_git_diffs(
 pub(crate) async fn get_git_log(
     workspace_id: String,
     limit: Option<usize>,
+    cursor: Option<String>,
+    filter: Option<GitLogFilter>,
     state: State<'_, AppState>,
     app: AppHandle,
 ) -> Result<GitLogResponse, String> {
@@ -295,10 +387,15 @@ pub(crate) async fn get_git_log(
         state,
         app,
         "get_git_log",
-        json!({ "workspaceId": &workspace_id, "limit": limit }),
+        json!({
+            "workspaceId": &workspace_id,
+            "limit": limit,
+            "cursor": &cursor,
+            "filter": &filter,
+        }),
         GitLogResponse
     );
-    git_ui_core::get_git_log_core(&state.workspaces, workspace_id, limit).await
+    git_ui_core::get_git_log_core(&state.workspaces, workspace_id, limit, cursor, filter).await
 }
 
 #[tauri::command]
@@ -448,3 +545,200 @@ pub(crate) async fn create_git_branch(
     );
     git_ui_core::create_git_branch_core(&state.workspaces, workspace_id, name).await
 }
+
+#[tauri::command]
+pub(crate) async fn list_git_stashes(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitStashEntry>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "list_git_stashes",
+        json!({ "workspaceId": &workspace_id }),
+        Vec<GitStashEntry>
+    );
+    git_ui_core::list_git_stashes_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn create_git_stash(
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: Option<bool>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitStashEntry, String> {
+    let include_untracked = include_untracked.unwrap_or(false);
+    try_remote_typed!(
+        state,
+        app,
+        "create_git_stash",
+        json!({
+            "workspaceId": &workspace_id,
+            "message": &message,
+            "includeUntracked": include_untracked,
+        }),
+        GitStashEntry
+    );
+    git_ui_core::create_git_stash_core(&state.workspaces, workspace_id, message, include_untracked)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn apply_git_stash(
+    workspace_id: String,
+    sha: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "apply_git_stash",
+        json!({ "workspaceId": &workspace_id, "sha": &sha })
+    );
+    git_ui_core::apply_git_stash_core(&state.workspaces, workspace_id, sha).await
+}
+
+#[tauri::command]
+pub(crate) async fn pop_git_stash(
+    workspace_id: String,
+    sha: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "pop_git_stash",
+        json!({ "workspaceId": &workspace_id, "sha": &sha })
+    );
+    git_ui_core::pop_git_stash_core(&state.workspaces, workspace_id, sha).await
+}
+
+#[tauri::command]
+pub(crate) async fn drop_git_stash(
+    workspace_id: String,
+    sha: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "drop_git_stash",
+        json!({ "workspaceId": &workspace_id, "sha": &sha })
+    );
+    git_ui_core::drop_git_stash_core(&state.workspaces, workspace_id, sha).await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_stash_diff(
+    workspace_id: String,
+    sha: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Vec<GitFileDiff>, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_git_stash_diff",
+        json!({ "workspaceId": &workspace_id, "sha": &sha }),
+        Vec<GitFileDiff>
+    );
+    git_ui_core::get_git_stash_diff_core(&state.workspaces, &state.app_settings, workspace_id, sha)
+        .await
+}
+
+#[tauri::command]
+pub(crate) async fn get_git_conflicts(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<GitConflictStatus, String> {
+    try_remote_typed!(
+        state,
+        app,
+        "get_git_conflicts",
+        json!({ "workspaceId": &workspace_id }),
+        GitConflictStatus
+    );
+    git_ui_core::get_git_conflicts_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn resolve_git_conflict(
+    workspace_id: String,
+    path: String,
+    resolution: GitConflictResolution,
+    content: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "resolve_git_conflict",
+        json!({
+            "workspaceId": &workspace_id,
+            "path": &path,
+            "resolution": resolution,
+            "content": &content,
+        })
+    );
+    git_ui_core::resolve_git_conflict_core(
+        &state.workspaces,
+        workspace_id,
+        path,
+        resolution,
+        content,
+    )
+    .await
+}
+
+#[tauri::command]
+pub(crate) async fn abort_merge(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "abort_merge",
+        json!({ "workspaceId": &workspace_id })
+    );
+    git_ui_core::abort_merge_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn continue_merge(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "continue_merge",
+        json!({ "workspaceId": &workspace_id })
+    );
+    git_ui_core::continue_merge_core(&state.workspaces, workspace_id).await
+}
+
+#[tauri::command]
+pub(crate) async fn abort_rebase(
+    workspace_id: String,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<(), String> {
+    try_remote_unit!(
+        state,
+        app,
+        "abort_rebase",
+        json!({ "workspaceId": &workspace_id })
+    );
+    git_ui_core::abort_rebase_core(&state.workspaces, workspace_id).await
+}
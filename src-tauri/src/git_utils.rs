@@ -28,11 +28,14 @@ pub(crate) fn commit_to_entry(commit: git2::Commit) -> GitLogEntry {
     let summary = commit.summary().unwrap_or("").to_string();
     let author = commit.author().name().unwrap_or("").to_string();
     let timestamp = commit.time().seconds();
+    let parents = commit.parent_ids().map(|oid| oid.to_string()).collect();
     GitLogEntry {
         sha: commit.id().to_string(),
         summary,
         author,
         timestamp,
+        parents,
+        graph: None,
     }
 }
 
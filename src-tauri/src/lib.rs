@@ -85,6 +85,23 @@ pub fn run() {
                 eprintln!("Failed to run startup logging/cache maintenance: {err}");
             }
             app.manage(state);
+            {
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    let event_sink = event_sink::TauriEventSink::new(app_handle.clone());
+                    loop {
+                        tokio::time::sleep(shared::process_stats_core::PROCESS_LIMIT_POLL_INTERVAL)
+                            .await;
+                        let state = app_handle.state::<state::AppState>();
+                        shared::process_stats_core::check_process_limits_core(
+                            &state.workspaces,
+                            &state.sessions,
+                            &event_sink,
+                        )
+                        .await;
+                    }
+                });
+            }
             #[cfg(desktop)]
             {
                 let app_handle = app.handle().clone();
@@ -220,10 +237,15 @@ pub fn run() {
             codex::compact_thread,
             codex::thread_live_subscribe,
             codex::thread_live_unsubscribe,
+            codex::replay_events,
+            codex::background_callback_metrics,
             codex::set_thread_name,
             codex::collaboration_mode_list,
             workspaces::connect_workspace,
+            workspaces::workspace_process_stats,
             git::get_git_status,
+            git::watch_git_status,
+            git::unwatch_git_status,
             git::list_git_roots,
             git::get_git_diffs,
             git::get_git_log,
@@ -234,6 +256,9 @@ pub fn run() {
             git::unstage_git_file,
             git::revert_git_file,
             git::revert_git_all,
+            git::stage_git_hunks,
+            git::unstage_git_hunks,
+            git::revert_git_hunks,
             git::commit_git,
             git::push_git,
             git::pull_git,
@@ -250,6 +275,17 @@ pub fn run() {
             git::list_git_branches,
             git::checkout_git_branch,
             git::create_git_branch,
+            git::list_git_stashes,
+            git::create_git_stash,
+            git::apply_git_stash,
+            git::pop_git_stash,
+            git::drop_git_stash,
+            git::get_git_stash_diff,
+            git::get_git_conflicts,
+            git::resolve_git_conflict,
+            git::abort_merge,
+            git::continue_merge,
+            git::abort_rebase,
             codex::model_list,
             codex::experimental_feature_list,
             codex::set_codex_feature_flag,
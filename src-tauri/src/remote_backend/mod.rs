@@ -1,9 +1,12 @@
 mod orbit_ws_transport;
 mod protocol;
 mod tcp_transport;
+mod tls;
 mod transport;
+mod unix_transport;
 
 use serde_json::{json, Value};
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::Arc;
 use std::sync::OnceLock;
@@ -20,6 +23,7 @@ use self::orbit_ws_transport::OrbitWsTransport;
 use self::protocol::{build_request_line, DEFAULT_REMOTE_HOST, DISCONNECTED_MESSAGE};
 use self::tcp_transport::TcpTransport;
 use self::transport::{PendingMap, RemoteTransport, RemoteTransportConfig, RemoteTransportKind};
+use self::unix_transport::UnixTransport;
 
 pub(crate) fn normalize_path_for_remote(path: String) -> String {
     let trimmed = path.trim();
@@ -117,6 +121,49 @@ impl RemoteBackend {
     }
 }
 
+/// Watches this app holds on the daemon. The daemon drops a client's watches
+/// with its connection, so each new connection re-issues them.
+#[derive(Default)]
+pub(crate) struct RemoteWatches {
+    git_status: HashMap<String, usize>,
+}
+
+impl RemoteWatches {
+    fn tracks(method: &str) -> bool {
+        matches!(method, "watch_git_status" | "unwatch_git_status")
+    }
+
+    fn record(&mut self, method: &str, params: &Value) {
+        let Some(workspace_id) = params.get("workspaceId").and_then(Value::as_str) else {
+            return;
+        };
+        match method {
+            "watch_git_status" => {
+                *self.git_status.entry(workspace_id.to_string()).or_default() += 1;
+            }
+            "unwatch_git_status" => {
+                if let Some(count) = self.git_status.get_mut(workspace_id) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.git_status.remove(workspace_id);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn replay_requests(&self) -> Vec<(&'static str, Value)> {
+        self.git_status
+            .iter()
+            .flat_map(|(workspace_id, count)| {
+                (0..*count)
+                    .map(move |_| ("watch_git_status", json!({ "workspaceId": workspace_id })))
+            })
+            .collect()
+    }
+}
+
 pub(crate) async fn is_remote_mode(state: &AppState) -> bool {
     let settings = state.app_settings.lock().await;
     matches!(settings.backend_mode, BackendMode::Remote)
@@ -127,6 +174,24 @@ pub(crate) async fn call_remote(
     app: AppHandle,
     method: &str,
     params: Value,
+) -> Result<Value, String> {
+    let watch_params = RemoteWatches::tracks(method).then(|| params.clone());
+    let result = call_remote_with_retry(state, app, method, params).await;
+    if let (Ok(_), Some(watch_params)) = (&result, watch_params) {
+        state
+            .remote_watches
+            .lock()
+            .await
+            .record(method, &watch_params);
+    }
+    result
+}
+
+async fn call_remote_with_retry(
+    state: &AppState,
+    app: AppHandle,
+    method: &str,
+    params: Value,
 ) -> Result<Value, String> {
     let client = ensure_remote_backend(state, app.clone()).await?;
     match client.call(method, params.clone()).await {
@@ -158,14 +223,17 @@ fn can_retry_after_disconnect(method: &str) -> bool {
         "account_rate_limits"
             | "account_read"
             | "apps_list"
+            | "background_callback_metrics"
             | "collaboration_mode_list"
             | "connect_workspace"
             | "file_read"
             | "get_config_model"
             | "get_git_commit_diff"
+            | "get_git_conflicts"
             | "get_git_diffs"
             | "get_git_log"
             | "get_git_remote"
+            | "get_git_stash_diff"
             | "get_git_status"
             | "get_github_issues"
             | "get_github_pull_request_comments"
@@ -174,6 +242,7 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "is_workspace_path_dir"
             | "list_git_branches"
             | "list_git_roots"
+            | "list_git_stashes"
             | "list_mcp_server_status"
             | "list_threads"
             | "local_usage_snapshot"
@@ -181,10 +250,14 @@ fn can_retry_after_disconnect(method: &str) -> bool {
             | "list_workspaces"
             | "model_list"
             | "read_workspace_file"
+            | "replay_events"
             | "resume_thread"
             | "thread_live_subscribe"
             | "thread_live_unsubscribe"
+            | "unwatch_git_status"
+            | "watch_git_status"
             | "skills_list"
+            | "workspace_process_stats"
             | "worktree_setup_status"
     )
 }
@@ -215,6 +288,7 @@ async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<Remot
     let transport: Box<dyn RemoteTransport> = match transport_config.kind() {
         RemoteTransportKind::Tcp => Box::new(TcpTransport),
         RemoteTransportKind::OrbitWs => Box::new(OrbitWsTransport),
+        RemoteTransportKind::Unix => Box::new(UnixTransport),
     };
     let connection = transport.connect(app, transport_config).await?;
 
@@ -227,7 +301,10 @@ async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<Remot
         }),
     };
 
-    if matches!(transport_kind, RemoteTransportKind::Tcp) {
+    if matches!(
+        transport_kind,
+        RemoteTransportKind::Tcp | RemoteTransportKind::Unix
+    ) {
         if let Some(token) = auth_token {
             client
                 .call("auth", json!({ "token": token }))
@@ -236,6 +313,13 @@ async fn ensure_remote_backend(state: &AppState, app: AppHandle) -> Result<Remot
         }
     }
 
+    let replay = state.remote_watches.lock().await.replay_requests();
+    for (method, params) in replay {
+        if let Err(err) = client.call(method, params).await {
+            eprintln!("failed to restore {method} on the remote backend: {err}");
+        }
+    }
+
     {
         let mut guard = state.remote_backend.lock().await;
         *guard = Some(client.clone());
@@ -254,9 +338,17 @@ fn resolve_transport_config(
             } else {
                 settings.remote_backend_host.clone()
             };
+            let tls_fingerprint = settings
+                .remote_backend_tls_fingerprint
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(tls::normalize_fingerprint)
+                .transpose()?;
             Ok(RemoteTransportConfig::Tcp {
                 host,
                 auth_token: settings.remote_backend_token.clone(),
+                tls_fingerprint,
             })
         }
         RemoteBackendProvider::Orbit => {
@@ -271,14 +363,29 @@ fn resolve_transport_config(
                 auth_token: settings.remote_backend_token.clone(),
             })
         }
+        RemoteBackendProvider::Unix => {
+            let socket_path = settings
+                .remote_backend_socket_path
+                .as_ref()
+                .map(|value| value.trim().to_string())
+                .filter(|value| !value.is_empty())
+                .ok_or_else(|| {
+                    "Unix provider requires remoteBackendSocketPath in app settings.".to_string()
+                })?;
+            Ok(RemoteTransportConfig::Unix {
+                socket_path,
+                auth_token: settings.remote_backend_token.clone(),
+            })
+        }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::{can_retry_after_disconnect, resolve_transport_config};
+    use super::{can_retry_after_disconnect, resolve_transport_config, RemoteWatches};
     use crate::remote_backend::transport::RemoteTransportConfig;
     use crate::types::{AppSettings, RemoteBackendProvider};
+    use serde_json::json;
 
     #[test]
     fn resolve_orbit_transport_uses_orbit_ws_url() {
@@ -293,6 +400,46 @@ mod tests {
         assert_eq!(ws_url, "https://orbit.example/ws/live");
     }
 
+    #[test]
+    fn resolve_tcp_transport_validates_pinned_fingerprint() {
+        let mut settings = AppSettings::default();
+        let config = resolve_transport_config(&settings).expect("transport config");
+        let RemoteTransportConfig::Tcp {
+            tls_fingerprint, ..
+        } = config
+        else {
+            panic!("expected tcp transport config");
+        };
+        assert!(tls_fingerprint.is_none());
+
+        settings.remote_backend_tls_fingerprint = Some("not-a-fingerprint".to_string());
+        assert!(resolve_transport_config(&settings).is_err());
+
+        settings.remote_backend_tls_fingerprint = Some("AB:".repeat(31) + "AB");
+        let config = resolve_transport_config(&settings).expect("transport config");
+        let RemoteTransportConfig::Tcp {
+            tls_fingerprint, ..
+        } = config
+        else {
+            panic!("expected tcp transport config");
+        };
+        assert_eq!(tls_fingerprint, Some("ab".repeat(32)));
+    }
+
+    #[test]
+    fn resolve_unix_transport_requires_socket_path() {
+        let mut settings = AppSettings::default();
+        settings.remote_backend_provider = RemoteBackendProvider::Unix;
+        assert!(resolve_transport_config(&settings).is_err());
+
+        settings.remote_backend_socket_path = Some(" /run/codex-monitor.sock ".to_string());
+        let config = resolve_transport_config(&settings).expect("transport config");
+        let RemoteTransportConfig::Unix { socket_path, .. } = config else {
+            panic!("expected unix transport config");
+        };
+        assert_eq!(socket_path, "/run/codex-monitor.sock");
+    }
+
     #[test]
     fn retries_only_retry_safe_methods_after_disconnect() {
         assert!(can_retry_after_disconnect("resume_thread"));
@@ -300,8 +447,32 @@ mod tests {
         assert!(can_retry_after_disconnect("local_usage_snapshot"));
         assert!(can_retry_after_disconnect("thread_live_subscribe"));
         assert!(can_retry_after_disconnect("thread_live_unsubscribe"));
+        assert!(can_retry_after_disconnect("watch_git_status"));
+        assert!(can_retry_after_disconnect("unwatch_git_status"));
         assert!(!can_retry_after_disconnect("send_user_message"));
         assert!(!can_retry_after_disconnect("start_thread"));
         assert!(!can_retry_after_disconnect("remove_workspace"));
     }
+
+    #[test]
+    fn remote_watches_replay_what_is_still_held_after_reconnect() {
+        let mut watches = RemoteWatches::default();
+        let ws = |id: &str| json!({ "workspaceId": id });
+        watches.record("watch_git_status", &ws("ws-1"));
+        watches.record("watch_git_status", &ws("ws-1"));
+        watches.record("watch_git_status", &ws("ws-2"));
+        watches.record("unwatch_git_status", &ws("ws-2"));
+        watches.record("unwatch_git_status", &ws("ws-3"));
+        watches.record("get_git_status", &ws("ws-3"));
+
+        assert_eq!(
+            watches.replay_requests(),
+            vec![
+                ("watch_git_status", ws("ws-1")),
+                ("watch_git_status", ws("ws-1")),
+            ]
+        );
+        assert!(RemoteWatches::tracks("unwatch_git_status"));
+        assert!(!RemoteWatches::tracks("get_git_status"));
+    }
 }
@@ -56,6 +56,13 @@ struct TerminalExitEnvelope {
     terminal_id: String,
 }
 
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct GitStatusChangedEnvelope {
+    workspace_id: String,
+    status: Value,
+}
+
 fn validate_request_id(value: &Value) -> bool {
     match value {
         Value::Number(_) => true,
@@ -110,6 +117,13 @@ fn validate_terminal_exit(params: &Value) -> bool {
     !parsed.workspace_id.trim().is_empty() && !parsed.terminal_id.trim().is_empty()
 }
 
+fn validate_git_status_changed(params: &Value) -> bool {
+    let Ok(parsed) = serde_json::from_value::<GitStatusChangedEnvelope>(params.clone()) else {
+        return false;
+    };
+    !parsed.workspace_id.trim().is_empty() && parsed.status.is_object()
+}
+
 fn parse_notification(message: Value) -> Option<IncomingMessage> {
     let notification = serde_json::from_value::<IncomingNotification>(message).ok()?;
     if notification.method.trim().is_empty() {
@@ -120,6 +134,7 @@ fn parse_notification(message: Value) -> Option<IncomingMessage> {
         "app-server-event" => validate_app_server_event(&notification.params),
         "terminal-output" => validate_terminal_output(&notification.params),
         "terminal-exit" => validate_terminal_exit(&notification.params),
+        "git-status-changed" => validate_git_status_changed(&notification.params),
         _ => false,
     };
 
@@ -262,4 +277,23 @@ mod tests {
         .to_string();
         assert!(parse_incoming_line(&raw).is_none());
     }
+    #[test]
+    fn git_status_changed_requires_workspace_and_status_object() {
+        let valid = json!({
+            "method": "git-status-changed",
+            "params": {
+                "workspaceId": "ws-1",
+                "status": { "branchName": "main", "files": [] }
+            }
+        })
+        .to_string();
+        assert!(parse_incoming_line(&valid).is_some());
+
+        let missing_status = json!({
+            "method": "git-status-changed",
+            "params": { "workspaceId": "ws-1", "status": null }
+        })
+        .to_string();
+        assert!(parse_incoming_line(&missing_status).is_none());
+    }
 }
@@ -1,6 +1,7 @@
 use tauri::AppHandle;
 use tokio::net::TcpStream;
 
+use super::tls::{pinned_connector, server_name_for_host};
 use super::transport::{
     spawn_transport_io, RemoteTransport, RemoteTransportConfig, TransportFuture,
 };
@@ -10,13 +11,26 @@ pub(crate) struct TcpTransport;
 impl RemoteTransport for TcpTransport {
     fn connect(&self, app: AppHandle, config: RemoteTransportConfig) -> TransportFuture {
         Box::pin(async move {
-            let RemoteTransportConfig::Tcp { host, .. } = config else {
+            let RemoteTransportConfig::Tcp {
+                host,
+                tls_fingerprint,
+                ..
+            } = config
+            else {
                 return Err("invalid transport config for tcp transport".to_string());
             };
 
             let stream = TcpStream::connect(host.clone())
                 .await
                 .map_err(|err| format!("Failed to connect to remote backend at {host}: {err}"))?;
+            if let Some(fingerprint) = tls_fingerprint {
+                let stream = pinned_connector(&fingerprint)?
+                    .connect(server_name_for_host(&host), stream)
+                    .await
+                    .map_err(|err| format!("TLS handshake with {host} failed: {err}"))?;
+                let (reader, writer) = tokio::io::split(stream);
+                return Ok(spawn_transport_io(app, reader, writer));
+            }
             let (reader, writer) = stream.into_split();
             Ok(spawn_transport_io(app, reader, writer))
         })
@@ -0,0 +1,150 @@
+//! Certificate pinning for TLS connections to the daemon's TCP listener. The
+//! daemon usually runs with a self-signed certificate, so the leaf
+//! certificate's SHA-256 fingerprint (as printed by the daemon on startup) is
+//! the only thing trusted; CA chains and host names are not consulted.
+
+use std::sync::Arc;
+
+use tokio_rustls::rustls::client::danger::{
+    HandshakeSignatureValid, ServerCertVerified, ServerCertVerifier,
+};
+use tokio_rustls::rustls::crypto::{self, CryptoProvider};
+use tokio_rustls::rustls::pki_types::{CertificateDer, ServerName, UnixTime};
+use tokio_rustls::rustls::{ClientConfig, DigitallySignedStruct, Error, SignatureScheme};
+use tokio_rustls::TlsConnector;
+
+use crate::shared::tls_core::certificate_fingerprint;
+
+const FALLBACK_SERVER_NAME: &str = "codex-monitor-daemon";
+
+/// Accepts the daemon's output, bare hex, or openssl's
+/// `sha256 Fingerprint=AB:CD:...` line.
+pub(crate) fn normalize_fingerprint(value: &str) -> Result<String, String> {
+    let value = value.rsplit('=').next().unwrap_or(value);
+    let normalized: String = value
+        .chars()
+        .filter(|ch| *ch != ':' && !ch.is_whitespace())
+        .map(|ch| ch.to_ascii_lowercase())
+        .collect();
+    if normalized.len() != 64 || !normalized.chars().all(|ch| ch.is_ascii_hexdigit()) {
+        return Err("remoteBackendTlsFingerprint must be a SHA-256 fingerprint".to_string());
+    }
+    Ok(normalized)
+}
+
+pub(crate) fn server_name_for_host(host: &str) -> ServerName<'static> {
+    let name = host
+        .rsplit_once(':')
+        .map(|(name, _port)| name)
+        .unwrap_or(host)
+        .trim_start_matches('[')
+        .trim_end_matches(']');
+    ServerName::try_from(name.to_string())
+        .unwrap_or_else(|_| ServerName::try_from(FALLBACK_SERVER_NAME).expect("valid dns name"))
+}
+
+pub(crate) fn pinned_connector(fingerprint: &str) -> Result<TlsConnector, String> {
+    let provider = Arc::new(crypto::ring::default_provider());
+    let verifier = PinnedCertVerifier {
+        fingerprint: normalize_fingerprint(fingerprint)?,
+        provider: Arc::clone(&provider),
+    };
+    let config = ClientConfig::builder_with_provider(provider)
+        .with_safe_default_protocol_versions()
+        .map_err(|err| format!("Failed to configure TLS: {err}"))?
+        .dangerous()
+        .with_custom_certificate_verifier(Arc::new(verifier))
+        .with_no_client_auth();
+    Ok(TlsConnector::from(Arc::new(config)))
+}
+
+#[derive(Debug)]
+struct PinnedCertVerifier {
+    fingerprint: String,
+    provider: Arc<CryptoProvider>,
+}
+
+impl ServerCertVerifier for PinnedCertVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &CertificateDer<'_>,
+        _intermediates: &[CertificateDer<'_>],
+        _server_name: &ServerName<'_>,
+        _ocsp_response: &[u8],
+        _now: UnixTime,
+    ) -> Result<ServerCertVerified, Error> {
+        let actual = certificate_fingerprint(end_entity.as_ref());
+        if normalize_fingerprint(&actual).as_deref() == Ok(self.fingerprint.as_str()) {
+            return Ok(ServerCertVerified::assertion());
+        }
+        Err(Error::General(format!(
+            "daemon certificate fingerprint {actual} does not match the pinned fingerprint"
+        )))
+    }
+
+    fn verify_tls12_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        crypto::verify_tls12_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn verify_tls13_signature(
+        &self,
+        message: &[u8],
+        cert: &CertificateDer<'_>,
+        dss: &DigitallySignedStruct,
+    ) -> Result<HandshakeSignatureValid, Error> {
+        crypto::verify_tls13_signature(
+            message,
+            cert,
+            dss,
+            &self.provider.signature_verification_algorithms,
+        )
+    }
+
+    fn supported_verify_schemes(&self) -> Vec<SignatureScheme> {
+        self.provider
+            .signature_verification_algorithms
+            .supported_schemes()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{certificate_fingerprint, normalize_fingerprint, server_name_for_host};
+
+    #[test]
+    fn fingerprints_normalize_across_common_formats() {
+        let fingerprint = certificate_fingerprint(b"certificate");
+        assert_eq!(fingerprint.len(), 95);
+        let bare = normalize_fingerprint(&fingerprint).expect("daemon format");
+        assert_eq!(bare.len(), 64);
+        assert_eq!(
+            normalize_fingerprint(&format!("sha256 Fingerprint={fingerprint}")),
+            Ok(bare.clone())
+        );
+        assert_eq!(normalize_fingerprint(&bare.to_uppercase()), Ok(bare));
+        assert!(normalize_fingerprint("AB:CD").is_err());
+    }
+
+    #[test]
+    fn server_name_strips_port_and_brackets() {
+        assert_eq!(
+            server_name_for_host("192.168.1.20:4732").to_str(),
+            "192.168.1.20"
+        );
+        assert_eq!(server_name_for_host("[::1]:4732").to_str(), "::1");
+        assert_eq!(
+            server_name_for_host("devbox.local:4732").to_str(),
+            "devbox.local"
+        );
+    }
+}
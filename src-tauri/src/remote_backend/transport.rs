@@ -20,17 +20,23 @@ pub(crate) enum RemoteTransportConfig {
     Tcp {
         host: String,
         auth_token: Option<String>,
+        tls_fingerprint: Option<String>,
     },
     OrbitWs {
         ws_url: String,
         auth_token: Option<String>,
     },
+    Unix {
+        socket_path: String,
+        auth_token: Option<String>,
+    },
 }
 
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
 pub(crate) enum RemoteTransportKind {
     Tcp,
     OrbitWs,
+    Unix,
 }
 
 impl RemoteTransportConfig {
@@ -38,6 +44,7 @@ impl RemoteTransportConfig {
         match self {
             RemoteTransportConfig::Tcp { .. } => RemoteTransportKind::Tcp,
             RemoteTransportConfig::OrbitWs { .. } => RemoteTransportKind::OrbitWs,
+            RemoteTransportConfig::Unix { .. } => RemoteTransportKind::Unix,
         }
     }
 
@@ -45,6 +52,7 @@ impl RemoteTransportConfig {
         match self {
             RemoteTransportConfig::Tcp { auth_token, .. } => auth_token.as_deref(),
             RemoteTransportConfig::OrbitWs { auth_token, .. } => auth_token.as_deref(),
+            RemoteTransportConfig::Unix { auth_token, .. } => auth_token.as_deref(),
         }
     }
 }
@@ -182,6 +190,11 @@ pub(crate) async fn dispatch_incoming_line(
                     eprintln!("failed to emit terminal-exit from remote backend: {err}");
                 }
             }
+            "git-status-changed" => {
+                if let Err(err) = app.emit("git-status-changed", params) {
+                    eprintln!("failed to emit git-status-changed from remote backend: {err}");
+                }
+            }
             _ => {
                 eprintln!("ignoring unsupported remote notification method: {method}");
             }
@@ -0,0 +1,38 @@
+use tauri::AppHandle;
+
+use super::transport::{RemoteTransport, RemoteTransportConfig, TransportFuture};
+
+pub(crate) struct UnixTransport;
+
+impl RemoteTransport for UnixTransport {
+    fn connect(&self, app: AppHandle, config: RemoteTransportConfig) -> TransportFuture {
+        Box::pin(async move {
+            let RemoteTransportConfig::Unix { socket_path, .. } = config else {
+                return Err("invalid transport config for unix transport".to_string());
+            };
+            connect_unix(app, socket_path).await
+        })
+    }
+}
+
+#[cfg(unix)]
+async fn connect_unix(
+    app: AppHandle,
+    socket_path: String,
+) -> Result<super::transport::TransportConnection, String> {
+    let stream = tokio::net::UnixStream::connect(&socket_path)
+        .await
+        .map_err(|err| {
+            format!("Failed to connect to remote backend at unix:{socket_path}: {err}")
+        })?;
+    let (reader, writer) = stream.into_split();
+    Ok(super::transport::spawn_transport_io(app, reader, writer))
+}
+
+#[cfg(not(unix))]
+async fn connect_unix(
+    _app: AppHandle,
+    _socket_path: String,
+) -> Result<super::transport::TransportConnection, String> {
+    Err("Unix socket transport is not supported on this platform".to_string())
+}
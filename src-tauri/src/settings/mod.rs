@@ -53,6 +53,8 @@ fn should_reset_remote_backend(previous: &AppSettings, updated: &AppSettings) ->
     backend_mode_changed
         || previous.remote_backend_provider != updated.remote_backend_provider
         || previous.remote_backend_host != updated.remote_backend_host
+        || previous.remote_backend_socket_path != updated.remote_backend_socket_path
+        || previous.remote_backend_tls_fingerprint != updated.remote_backend_tls_fingerprint
         || previous.remote_backend_token != updated.remote_backend_token
         || previous.orbit_ws_url != updated.orbit_ws_url
 }
@@ -74,6 +76,8 @@ async fn ensure_remote_runtime_for_settings(settings: &AppSettings, state: State
                 let _ = crate::orbit::orbit_runner_start(state).await;
             }
         }
+        // The socket daemon is expected to be managed outside the app (e.g. systemd).
+        RemoteBackendProvider::Unix => {}
     }
 }
 
@@ -98,6 +102,14 @@ mod tests {
         assert!(should_reset_remote_backend(&previous, &updated));
     }
 
+    #[test]
+    fn should_reset_remote_backend_when_socket_path_changes() {
+        let previous = AppSettings::default();
+        let mut updated = previous.clone();
+        updated.remote_backend_socket_path = Some("/run/codex-monitor.sock".to_string());
+        assert!(should_reset_remote_backend(&previous, &updated));
+    }
+
     #[test]
     fn should_not_reset_remote_backend_for_non_transport_setting_changes() {
         let previous = AppSettings::default();
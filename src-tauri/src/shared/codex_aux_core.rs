@@ -1,14 +1,18 @@
-use serde_json::{json, Value};
+use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 use std::io::ErrorKind;
 use std::sync::Arc;
 use std::time::Duration;
-use tokio::sync::{mpsc, Mutex};
+use tokio::sync::Mutex;
 use tokio::time::timeout;
 
 use crate::backend::app_server::{
     build_codex_command_with_bin, build_codex_path_env, check_codex_installation, WorkspaceSession,
-    BACKGROUND_THREAD_EVENT_BUFFER,
+};
+use crate::backend::background_callbacks::background_thread_channel;
+use crate::backend::protocol::{
+    response_error, thread_id_from_response, ClientRequest, ServerNotification, ThreadIdParams,
+    ThreadStartParams, TurnStartParams,
 };
 use crate::shared::process_core::tokio_command;
 use crate::types::AppSettings;
@@ -275,56 +279,48 @@ where
             .clone()
     };
 
-    let thread_params = json!({
-        "cwd": session.entry.path,
-        "approvalPolicy": "never"
-    });
-    let thread_result = session.send_request("thread/start", thread_params).await?;
-
-    if let Some(error) = thread_result.get("error") {
-        let error_msg = error
-            .get("message")
-            .and_then(|m| m.as_str())
-            .unwrap_or("Unknown error starting thread");
-        return Err(error_msg.to_string());
+    let mut thread_policy = Map::new();
+    thread_policy.insert("approvalPolicy".to_string(), json!("never"));
+    let thread_result = session
+        .send_typed(ClientRequest::ThreadStart(ThreadStartParams {
+            cwd: session.workspace_path(&workspace_id),
+            execution_policy: thread_policy,
+        }))
+        .await?;
+
+    if let Some(error) = response_error(&thread_result) {
+        return Err(error);
     }
 
-    let thread_id = thread_result
-        .get("result")
-        .and_then(|r| r.get("threadId"))
-        .or_else(|| {
+    let thread_id = thread_id_from_response(&thread_result).ok_or_else(|| {
+        format!(
+            "Failed to get threadId from thread/start response: {:?}",
             thread_result
-                .get("result")
-                .and_then(|r| r.get("thread"))
-                .and_then(|t| t.get("id"))
-        })
-        .or_else(|| thread_result.get("threadId"))
-        .or_else(|| thread_result.get("thread").and_then(|t| t.get("id")))
-        .and_then(|t| t.as_str())
-        .ok_or_else(|| {
-            format!(
-                "Failed to get threadId from thread/start response: {:?}",
-                thread_result
-            )
-        })?
-        .to_string();
+        )
+    })?;
 
+    session.bind_thread(&workspace_id, &thread_id);
     on_hide_thread(&workspace_id, &thread_id);
 
-    let (tx, mut rx) = mpsc::channel::<Value>(BACKGROUND_THREAD_EVENT_BUFFER);
+    let (tx, mut rx) = background_thread_channel();
     {
         let mut callbacks = session.background_thread_callbacks.lock().await;
         callbacks.insert(thread_id.clone(), tx);
     }
 
-    let turn_params = json!({
-        "threadId": thread_id,
-        "input": [{ "type": "text", "text": prompt }],
-        "cwd": session.entry.path,
-        "approvalPolicy": "never",
-        "sandboxPolicy": { "type": "readOnly" },
-    });
-    let turn_result = session.send_request("turn/start", turn_params).await;
+    let mut turn_policy = Map::new();
+    turn_policy.insert("approvalPolicy".to_string(), json!("never"));
+    turn_policy.insert("sandboxPolicy".to_string(), json!({ "type": "readOnly" }));
+    let turn_params = TurnStartParams {
+        thread_id: thread_id.clone(),
+        input: vec![json!({ "type": "text", "text": prompt })],
+        cwd: session.workspace_path(&workspace_id),
+        execution_policy: turn_policy,
+        ..TurnStartParams::default()
+    };
+    let turn_result = session
+        .send_typed(ClientRequest::TurnStart(turn_params))
+        .await;
     let turn_result = match turn_result {
         Ok(result) => result,
         Err(error) => {
@@ -332,46 +328,37 @@ where
                 let mut callbacks = session.background_thread_callbacks.lock().await;
                 callbacks.remove(&thread_id);
             }
-            let archive_params = json!({ "threadId": thread_id.as_str() });
-            let _ = session.send_request("thread/archive", archive_params).await;
+            archive_background_thread(&session, &thread_id).await;
             return Err(error);
         }
     };
 
-    if let Some(error) = turn_result.get("error") {
-        let error_msg = error
-            .get("message")
-            .and_then(|m| m.as_str())
-            .unwrap_or(turn_error_fallback);
+    if turn_result.get("error").is_some() {
+        let error_msg = response_error(&turn_result)
+            .filter(|message| !message.is_empty())
+            .unwrap_or_else(|| turn_error_fallback.to_string());
         {
             let mut callbacks = session.background_thread_callbacks.lock().await;
             callbacks.remove(&thread_id);
         }
-        let archive_params = json!({ "threadId": thread_id.as_str() });
-        let _ = session.send_request("thread/archive", archive_params).await;
-        return Err(error_msg.to_string());
+        archive_background_thread(&session, &thread_id).await;
+        return Err(error_msg);
     }
 
     let mut response_text = String::new();
     let collect_result = timeout(Duration::from_secs(60), async {
         while let Some(event) = rx.recv().await {
-            let method = event.get("method").and_then(|m| m.as_str()).unwrap_or("");
-            match method {
-                "item/agentMessage/delta" => {
-                    if let Some(params) = event.get("params") {
-                        if let Some(delta) = params.get("delta").and_then(|d| d.as_str()) {
-                            response_text.push_str(delta);
-                        }
-                    }
+            match ServerNotification::parse(&event) {
+                Some(ServerNotification::AgentMessageDelta { delta, .. }) => {
+                    response_text.push_str(&delta);
                 }
-                "turn/completed" => break,
-                "turn/error" => {
-                    let error_msg = event
-                        .get("params")
-                        .and_then(|p| p.get("error"))
-                        .and_then(|e| e.as_str())
-                        .unwrap_or(turn_error_fallback);
-                    return Err(error_msg.to_string());
+                Some(ServerNotification::TurnCompleted { .. }) => break,
+                Some(ServerNotification::TurnError {
+                    message,
+                    will_retry: false,
+                    ..
+                }) => {
+                    return Err(message.unwrap_or_else(|| turn_error_fallback.to_string()));
                 }
                 _ => {}
             }
@@ -385,8 +372,7 @@ where
         callbacks.remove(&thread_id);
     }
 
-    let archive_params = json!({ "threadId": thread_id });
-    let _ = session.send_request("thread/archive", archive_params).await;
+    archive_background_thread(&session, &thread_id).await;
 
     match collect_result {
         Ok(Ok(())) => {}
@@ -402,6 +388,13 @@ where
     Ok(trimmed)
 }
 
+async fn archive_background_thread(session: &WorkspaceSession, thread_id: &str) {
+    let request = ClientRequest::ThreadArchive(ThreadIdParams {
+        thread_id: thread_id.to_string(),
+    });
+    let _ = session.send_typed(request).await;
+}
+
 pub(crate) async fn generate_commit_message_core<F>(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
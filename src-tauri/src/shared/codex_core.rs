@@ -11,6 +11,11 @@ use tokio::time::timeout;
 use tokio::time::Instant;
 
 use crate::backend::app_server::WorkspaceSession;
+use crate::backend::protocol::{
+    is_method_not_found_error, parse_response, thread_id_from_response, ClientRequest, EmptyParams,
+    LoginCancelParams, LoginStartParams, LoginStartResponse, ThreadIdParams, ThreadListParams,
+    ThreadStartParams, TurnInterruptParams, TurnStartParams, TurnSteerParams,
+};
 use crate::codex::config as codex_config;
 use crate::codex::home::{resolve_default_codex_home, resolve_workspace_codex_home};
 use crate::rules;
@@ -78,6 +83,41 @@ fn sandbox_policy_param_from_mode(mode: &str) -> Option<Value> {
     Some(json!({ "type": policy_type }))
 }
 
+/// Explicit `sandboxPolicy`/`approvalPolicy` for a composer access mode.
+/// `Ok(None)` means the mode defers to the next layer ("current").
+fn execution_policy_from_access_mode(
+    mode: &str,
+) -> Result<Option<(Value, Option<&'static str>)>, String> {
+    let normalized = mode.trim().to_ascii_lowercase();
+    let sandbox_mode = match normalized.as_str() {
+        "" | "current" => return Ok(None),
+        "full-access" | "full_access" | "fullaccess" => "danger-full-access",
+        other => other,
+    };
+    let sandbox_policy = sandbox_policy_param_from_mode(sandbox_mode)
+        .ok_or_else(|| format!("unsupported access mode: {}", mode.trim()))?;
+    let approval_policy = match sandbox_policy.get("type").and_then(Value::as_str) {
+        Some("readOnly") | Some("workspaceWrite") => Some("on-request"),
+        Some("dangerFullAccess") => Some("never"),
+        _ => None,
+    };
+    Ok(Some((sandbox_policy, approval_policy)))
+}
+
+fn apply_execution_policy_from_access_mode(
+    params: &mut Map<String, Value>,
+    mode: &str,
+) -> Result<bool, String> {
+    let Some((sandbox_policy, approval_policy)) = execution_policy_from_access_mode(mode)? else {
+        return Ok(false);
+    };
+    params.insert("sandboxPolicy".to_string(), sandbox_policy);
+    if let Some(approval_policy) = approval_policy {
+        params.insert("approvalPolicy".to_string(), json!(approval_policy));
+    }
+    Ok(true)
+}
+
 fn apply_execution_policy_from_config(params: &mut Map<String, Value>) {
     let Ok((sandbox_mode, approval_policy)) = codex_config::read_execution_policy(None) else {
         return;
@@ -96,15 +136,27 @@ pub(crate) async fn start_thread_core(
     workspace_id: String,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let mut params = Map::new();
-    params.insert("cwd".to_string(), json!(session.entry.path));
+    let mut execution_policy = Map::new();
     // Compatibility path: read policies from ~/.codex/config.toml and pass
     // them explicitly for app-server builds that don't reliably derive policy
     // from config in all event/approval flows.
-    apply_execution_policy_from_config(&mut params);
-    session
-        .send_request("thread/start", Value::Object(params))
-        .await
+    apply_execution_policy_from_config(&mut execution_policy);
+    let response = session
+        .send_typed(ClientRequest::ThreadStart(ThreadStartParams {
+            cwd: session.workspace_path(&workspace_id),
+            execution_policy,
+        }))
+        .await?;
+    bind_response_thread(&session, &workspace_id, &response);
+    Ok(response)
+}
+
+/// Route a pooled app-server's events for the thread in `response` to
+/// `workspace_id`.
+fn bind_response_thread(session: &WorkspaceSession, workspace_id: &str, response: &Value) {
+    if let Some(thread_id) = thread_id_from_response(response) {
+        session.bind_thread(workspace_id, &thread_id);
+    }
 }
 
 pub(crate) async fn resume_thread_core(
@@ -113,29 +165,35 @@ pub(crate) async fn resume_thread_core(
     thread_id: String,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let params = json!({ "threadId": thread_id });
+    session.bind_thread(&workspace_id, &thread_id);
+    let params = ThreadIdParams { thread_id };
 
     // Prefer thread/resume over thread/read: thread/resume registers the
     // thread as active in the app-server so that subsequent turn/start calls
     // can find it.  thread/read is lighter but does NOT activate the thread,
     // which causes "thread not found" errors on turn/start.
-    match session.send_request("thread/resume", params.clone()).await {
-        Ok(response) => Ok(response),
-        Err(resume_error) => {
-            let normalized = resume_error.to_lowercase();
-            let should_fallback = normalized.contains("method not found")
-                || normalized.contains("unknown method")
-                || normalized.contains("thread/resume");
-            if !should_fallback {
-                return Err(resume_error);
+    if session.capabilities().supports_method("thread/resume") {
+        match session
+            .send_typed(ClientRequest::ThreadResume(params.clone()))
+            .await
+        {
+            Ok(response) => return Ok(response),
+            Err(resume_error) => {
+                if !is_method_not_found_error(&resume_error) {
+                    return Err(resume_error);
+                }
+                // Remember so later resumes skip straight to thread/read.
+                session.mark_method_unsupported("thread/resume");
             }
-            // Older codex versions may not support thread/resume — fall back
-            // to thread/read which is available everywhere.
-            session.send_request("thread/read", params).await
         }
     }
+    // Older codex versions may not support thread/resume — fall back
+    // to thread/read which is available everywhere.
+    session.send_typed(ClientRequest::ThreadRead(params)).await
 }
 
+/// Validates a live-thread subscription. Per-client filtering is done by the
+/// daemon; the desktop webview is a single client and receives everything.
 pub(crate) async fn thread_live_subscribe_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
     workspace_id: String,
@@ -166,8 +224,11 @@ pub(crate) async fn fork_thread_core(
     thread_id: String,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let params = json!({ "threadId": thread_id });
-    session.send_request("thread/fork", params).await
+    let response = session
+        .send_typed(ClientRequest::ThreadFork(ThreadIdParams { thread_id }))
+        .await?;
+    bind_response_thread(&session, &workspace_id, &response);
+    Ok(response)
 }
 
 pub(crate) async fn list_threads_core(
@@ -179,16 +240,19 @@ pub(crate) async fn list_threads_core(
     cwd: Option<String>,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let params = json!({
-        "cursor": cursor,
-        "limit": limit,
-        "sortKey": sort_key,
-        "cwd": cwd,
+    let params = ThreadListParams {
+        cursor,
+        limit,
+        sort_key,
+        cwd,
         // Keep spawned sub-agent sessions visible in thread/list so UI refreshes
         // do not drop parent -> child sidebar relationships.
-        "sourceKinds": ["cli", "vscode", "subAgentThreadSpawn"]
-    });
-    session.send_request("thread/list", params).await
+        source_kinds: ["cli", "vscode", "subAgentThreadSpawn"]
+            .into_iter()
+            .map(str::to_string)
+            .collect(),
+    };
+    session.send_typed(ClientRequest::ThreadList(params)).await
 }
 
 pub(crate) async fn list_mcp_server_status_core(
@@ -208,8 +272,9 @@ pub(crate) async fn archive_thread_core(
     thread_id: String,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let params = json!({ "threadId": thread_id });
-    session.send_request("thread/archive", params).await
+    session
+        .send_typed(ClientRequest::ThreadArchive(ThreadIdParams { thread_id }))
+        .await
 }
 
 fn build_archive_threads_result(ok_ids: Vec<String>, failed: Vec<(String, String)>) -> Value {
@@ -256,8 +321,10 @@ pub(crate) async fn archive_threads_core(
     let mut failed = Vec::new();
 
     for thread_id in normalized_thread_ids {
-        let params = json!({ "threadId": thread_id.clone() });
-        match session.send_request("thread/archive", params).await {
+        let request = ClientRequest::ThreadArchive(ThreadIdParams {
+            thread_id: thread_id.clone(),
+        });
+        match session.send_typed(request).await {
             Ok(_) => ok_ids.push(thread_id),
             Err(error) => failed.push((thread_id, error)),
         }
@@ -272,8 +339,11 @@ pub(crate) async fn compact_thread_core(
     thread_id: String,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let params = json!({ "threadId": thread_id });
-    session.send_request("thread/compact/start", params).await
+    session
+        .send_typed(ClientRequest::ThreadCompactStart(ThreadIdParams {
+            thread_id,
+        }))
+        .await
 }
 
 pub(crate) async fn set_thread_name_core(
@@ -377,12 +447,13 @@ fn build_turn_input_items(
 
 pub(crate) async fn send_user_message_core(
     sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     thread_id: String,
     text: String,
     model: Option<String>,
     effort: Option<String>,
-    _access_mode: Option<String>,
+    access_mode: Option<String>,
     images: Option<Vec<String>>,
     app_mentions: Option<Vec<Value>>,
     skill_mentions: Option<Vec<Value>>,
@@ -392,26 +463,41 @@ pub(crate) async fn send_user_message_core(
 
     let input = build_turn_input_items(text, images, app_mentions, skill_mentions)?;
 
-    let mut params = Map::new();
-    params.insert("threadId".to_string(), json!(thread_id));
-    params.insert("input".to_string(), json!(input));
-    params.insert("cwd".to_string(), json!(session.entry.path));
-
-    // Keep _access_mode ignored for backward compatibility, but explicitly pass
-    // policy values from ~/.codex/config.toml to avoid policy drift across
-    // mixed app-server versions.
-    apply_execution_policy_from_config(&mut params);
-
-    params.insert("model".to_string(), json!(model));
-    params.insert("effort".to_string(), json!(effort));
-    if let Some(mode) = collaboration_mode {
-        if !mode.is_null() {
-            params.insert("collaborationMode".to_string(), mode);
+    let mut execution_policy = Map::new();
+
+    // Precedence: per-turn access mode, then the workspace default, then
+    // ~/.codex/config.toml. The config values are always passed explicitly to
+    // avoid policy drift across mixed app-server versions.
+    apply_execution_policy_from_config(&mut execution_policy);
+    let applied_turn_mode = match access_mode.as_deref() {
+        Some(mode) => apply_execution_policy_from_access_mode(&mut execution_policy, mode)?,
+        None => false,
+    };
+    if !applied_turn_mode {
+        let workspace_mode = match resolve_workspace_and_parent(workspaces, &workspace_id).await {
+            Ok((entry, parent)) => entry
+                .settings
+                .access_mode
+                .or_else(|| parent.and_then(|parent| parent.settings.access_mode)),
+            Err(_) => session.workspace_entry(&workspace_id).settings.access_mode,
+        };
+        if let Some(mode) = workspace_mode {
+            // A stale or hand-edited workspace default should not block sending.
+            let _ = apply_execution_policy_from_access_mode(&mut execution_policy, &mode);
         }
     }
-    session
-        .send_request("turn/start", Value::Object(params))
-        .await
+
+    session.bind_thread(&workspace_id, &thread_id);
+    let request = ClientRequest::TurnStart(TurnStartParams {
+        thread_id,
+        input,
+        cwd: session.workspace_path(&workspace_id),
+        model,
+        effort,
+        collaboration_mode: collaboration_mode.filter(|mode| !mode.is_null()),
+        execution_policy,
+    });
+    session.send_typed(request).await
 }
 
 pub(crate) async fn turn_steer_core(
@@ -429,12 +515,12 @@ pub(crate) async fn turn_steer_core(
     }
     let session = get_session_clone(sessions, &workspace_id).await?;
     let input = build_turn_input_items(text, images, app_mentions, skill_mentions)?;
-    let params = json!({
-        "threadId": thread_id,
-        "expectedTurnId": turn_id,
-        "input": input
-    });
-    session.send_request("turn/steer", params).await
+    let params = TurnSteerParams {
+        thread_id,
+        expected_turn_id: turn_id,
+        input,
+    };
+    session.send_typed(ClientRequest::TurnSteer(params)).await
 }
 
 pub(crate) async fn collaboration_mode_list_core(
@@ -454,8 +540,10 @@ pub(crate) async fn turn_interrupt_core(
     turn_id: String,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let params = json!({ "threadId": thread_id, "turnId": turn_id });
-    session.send_request("turn/interrupt", params).await
+    let params = TurnInterruptParams { thread_id, turn_id };
+    session
+        .send_typed(ClientRequest::TurnInterrupt(params))
+        .await
 }
 
 pub(crate) async fn start_review_core(
@@ -482,7 +570,9 @@ pub(crate) async fn model_list_core(
     workspace_id: String,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    session.send_request("model/list", json!({})).await
+    session
+        .send_typed(ClientRequest::ModelList(EmptyParams::default()))
+        .await
 }
 
 pub(crate) async fn experimental_feature_list_core(
@@ -556,19 +646,24 @@ pub(crate) async fn codex_login_core(
 
     let start = Instant::now();
     let mut cancel_rx = cancel_rx;
-    let mut login_request: Pin<Box<_>> =
-        Box::pin(session.send_request("account/login/start", json!({ "type": "chatgpt" })));
+    let login_start = ClientRequest::AccountLoginStart(LoginStartParams {
+        kind: "chatgpt".to_string(),
+    });
+    let handle = match session.begin_typed(login_start).await {
+        Ok(handle) => handle,
+        Err(error) => {
+            codex_login_cancels.lock().await.remove(&workspace_id);
+            return Err(error);
+        }
+    };
+    let request_id = handle.id;
+    let mut login_request: Pin<Box<_>> = Box::pin(session.wait_request(handle));
 
     let response = loop {
         match cancel_rx.try_recv() {
-            Ok(_) => {
-                let mut cancels = codex_login_cancels.lock().await;
-                cancels.remove(&workspace_id);
-                return Err("Codex login canceled.".to_string());
-            }
-            Err(TryRecvError::Closed) => {
-                let mut cancels = codex_login_cancels.lock().await;
-                cancels.remove(&workspace_id);
+            Ok(_) | Err(TryRecvError::Closed) => {
+                codex_login_cancels.lock().await.remove(&workspace_id);
+                session.cancel_request(request_id).await;
                 return Err("Codex login canceled.".to_string());
             }
             Err(TryRecvError::Empty) => {}
@@ -576,8 +671,8 @@ pub(crate) async fn codex_login_core(
 
         let elapsed = start.elapsed();
         if elapsed >= LOGIN_START_TIMEOUT {
-            let mut cancels = codex_login_cancels.lock().await;
-            cancels.remove(&workspace_id);
+            codex_login_cancels.lock().await.remove(&workspace_id);
+            session.cancel_request(request_id).await;
             return Err("Codex login start timed out.".to_string());
         }
 
@@ -591,19 +686,8 @@ pub(crate) async fn codex_login_core(
         }
     };
 
-    let payload = response.get("result").unwrap_or(&response);
-    let login_id = payload
-        .get("loginId")
-        .or_else(|| payload.get("login_id"))
-        .and_then(Value::as_str)
-        .map(str::to_string)
-        .ok_or_else(|| "missing login id in account/login/start response".to_string())?;
-    let auth_url = payload
-        .get("authUrl")
-        .or_else(|| payload.get("auth_url"))
-        .and_then(Value::as_str)
-        .map(str::to_string)
-        .ok_or_else(|| "missing auth url in account/login/start response".to_string())?;
+    let LoginStartResponse { login_id, auth_url } = parse_response::<LoginStartResponse>(&response)
+        .map_err(|err| format!("invalid account/login/start response: {err}"))?;
 
     {
         let mut cancels = codex_login_cancels.lock().await;
@@ -645,12 +729,9 @@ pub(crate) async fn codex_login_cancel_core(
         CodexLoginCancelState::LoginId(login_id) => {
             let session = get_session_clone(sessions, &workspace_id).await?;
             let response = session
-                .send_request(
-                    "account/login/cancel",
-                    json!({
-                        "loginId": login_id,
-                    }),
-                )
+                .send_typed(ClientRequest::AccountLoginCancel(LoginCancelParams {
+                    login_id,
+                }))
                 .await?;
 
             let payload = response.get("result").unwrap_or(&response);
@@ -674,7 +755,7 @@ pub(crate) async fn skills_list_core(
     workspace_id: String,
 ) -> Result<Value, String> {
     let session = get_session_clone(sessions, &workspace_id).await?;
-    let params = json!({ "cwd": session.entry.path });
+    let params = json!({ "cwd": session.workspace_path(&workspace_id) });
     session.send_request("skills/list", params).await
 }
 
@@ -736,7 +817,8 @@ pub(crate) async fn get_config_model_core(
 #[cfg(test)]
 mod tests {
     use super::{
-        build_archive_threads_result, build_turn_input_items, sandbox_policy_param_from_mode,
+        build_archive_threads_result, build_turn_input_items, execution_policy_from_access_mode,
+        sandbox_policy_param_from_mode,
     };
     use serde_json::json;
 
@@ -861,4 +943,29 @@ mod tests {
         .expect_err("empty skill names should be rejected");
         assert_eq!(error, "invalid skill mention name");
     }
+
+    #[test]
+    fn execution_policy_from_access_mode_maps_composer_modes() {
+        assert_eq!(execution_policy_from_access_mode("current"), Ok(None));
+        assert_eq!(execution_policy_from_access_mode("  "), Ok(None));
+        assert_eq!(
+            execution_policy_from_access_mode("read-only"),
+            Ok(Some((json!({ "type": "readOnly" }), Some("on-request"))))
+        );
+        assert_eq!(
+            execution_policy_from_access_mode("workspace-write"),
+            Ok(Some((
+                json!({ "type": "workspaceWrite" }),
+                Some("on-request")
+            )))
+        );
+        assert_eq!(
+            execution_policy_from_access_mode("full-access"),
+            Ok(Some((json!({ "type": "dangerFullAccess" }), Some("never"))))
+        );
+        assert_eq!(
+            execution_policy_from_access_mode("yolo"),
+            Err("unsupported access mode: yolo".to_string())
+        );
+    }
 }
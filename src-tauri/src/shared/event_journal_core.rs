@@ -0,0 +1,634 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc;
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::shared::logging_core::{jsonl_file_path, rotate_jsonl_file_if_needed};
+
+const JOURNAL_DIR_NAME: &str = "journal";
+const JOURNAL_FILE_BASENAME: &str = "events";
+const MAX_JOURNAL_FILE_BYTES: u64 = 8 * 1024 * 1024;
+const MAX_JOURNAL_FILES_TOTAL: usize = 4;
+const DEFAULT_REPLAY_LIMIT: u32 = 1000;
+pub(crate) const MAX_REPLAY_LIMIT: u32 = 5000;
+/// First window read from the end of a segment when recovering the last
+/// sequence number; doubled until a complete record is found.
+const SEQ_RECOVERY_TAIL_BYTES: u64 = 64 * 1024;
+
+static EVENT_JOURNAL: OnceLock<EventJournal> = OnceLock::new();
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum JournalDirection {
+    /// Read from the app-server's stdout.
+    Inbound,
+    /// Written to the app-server's stdin.
+    Outbound,
+    /// Synthesized by the backend (connect/disconnect/restart notices).
+    Local,
+}
+
+impl JournalDirection {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Inbound => "inbound",
+            Self::Outbound => "outbound",
+            Self::Local => "local",
+        }
+    }
+}
+
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+struct JournalRecord<'a> {
+    seq: u64,
+    timestamp_ms: u128,
+    direction: &'a str,
+    message: &'a Value,
+}
+
+struct WorkspaceJournal {
+    dir: PathBuf,
+    /// `None` until the first append recovers the last sequence number.
+    next_seq: StdMutex<Option<u64>>,
+    /// Locked by the writer thread and by replay while it opens segments, so
+    /// rotation never renames a file between listing and opening it.
+    file: StdMutex<JournalFile>,
+}
+
+#[derive(Default)]
+struct JournalFile {
+    file: Option<File>,
+    bytes_written: u64,
+}
+
+enum JournalCommand {
+    Append {
+        journal: Arc<WorkspaceJournal>,
+        line: String,
+    },
+    Remove {
+        journal: Arc<WorkspaceJournal>,
+    },
+    Prune {
+        workspace_ids: HashSet<String>,
+    },
+    Flush(mpsc::SyncSender<()>),
+}
+
+/// Append-only, per-workspace JSONL journal of app-server traffic. Each
+/// workspace gets its own directory and rotation set so replay never has to
+/// scan other workspaces' history.
+///
+/// Sequence numbers are assigned on the caller's thread; file IO happens on a
+/// dedicated writer thread so app-server reader tasks never block on disk.
+pub(crate) struct EventJournal {
+    root: PathBuf,
+    workspaces: StdMutex<HashMap<String, Arc<WorkspaceJournal>>>,
+    sender: Option<mpsc::Sender<JournalCommand>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl EventJournal {
+    pub(crate) fn new(root: PathBuf) -> Self {
+        let (sender, receiver) = mpsc::channel();
+        let writer_root = root.clone();
+        let writer = thread::Builder::new()
+            .name("event-journal".to_string())
+            .spawn(move || run_journal_writer(&writer_root, receiver))
+            .map_err(|err| eprintln!("failed to start event journal writer: {err}"))
+            .ok();
+        Self {
+            root,
+            workspaces: StdMutex::new(HashMap::new()),
+            sender: Some(sender),
+            writer,
+        }
+    }
+
+    pub(crate) fn append(
+        &self,
+        workspace_id: &str,
+        direction: JournalDirection,
+        message: &Value,
+    ) -> Result<u64, String> {
+        let journal = self.workspace(workspace_id)?;
+        // Held until the record is queued so the writer sees seqs in order.
+        let mut next_seq = journal
+            .next_seq
+            .lock()
+            .map_err(|_| "event journal lock poisoned".to_string())?;
+        let seq = match *next_seq {
+            Some(seq) => seq,
+            None => recover_last_seq(&journal.dir) + 1,
+        };
+        let record = JournalRecord {
+            seq,
+            timestamp_ms: now_millis(),
+            direction: direction.as_str(),
+            message,
+        };
+        let mut line = serde_json::to_string(&record)
+            .map_err(|err| format!("failed to serialize event journal record: {err}"))?;
+        line.push('\n');
+        self.send(JournalCommand::Append {
+            journal: Arc::clone(&journal),
+            line,
+        })?;
+        *next_seq = Some(seq + 1);
+        Ok(seq)
+    }
+
+    /// Records with `seq > since_seq`, oldest first. `gap` is set when the
+    /// requested range has already been rotated out.
+    pub(crate) fn replay(
+        &self,
+        workspace_id: &str,
+        since_seq: u64,
+        limit: u32,
+    ) -> Result<Value, String> {
+        let journal = self.workspace(workspace_id)?;
+        let latest_seq = match *journal
+            .next_seq
+            .lock()
+            .map_err(|_| "event journal lock poisoned".to_string())?
+        {
+            Some(next_seq) => next_seq.saturating_sub(1),
+            None => recover_last_seq(&journal.dir),
+        };
+        self.flush()?;
+        let segments: Vec<File> = {
+            let _file = journal
+                .file
+                .lock()
+                .map_err(|_| "event journal lock poisoned".to_string())?;
+            journal_files_oldest_first(&journal.dir)
+                .iter()
+                .filter_map(|path| File::open(path).ok())
+                .collect()
+        };
+
+        let limit = limit as usize;
+        let mut events = Vec::new();
+        let mut oldest_seq: Option<u64> = None;
+        let mut has_more = false;
+        'segments: for file in segments {
+            for line in BufReader::new(file).lines() {
+                let Ok(line) = line else {
+                    break;
+                };
+                let Some((seq, record)) = parse_record(&line) else {
+                    continue;
+                };
+                oldest_seq.get_or_insert(seq);
+                if seq <= since_seq {
+                    continue;
+                }
+                if seq > latest_seq {
+                    break 'segments;
+                }
+                if events.len() >= limit {
+                    has_more = true;
+                    break 'segments;
+                }
+                events.push(record);
+            }
+        }
+
+        let gap = oldest_seq.is_some_and(|oldest| since_seq.saturating_add(1) < oldest);
+        Ok(json!({
+            "workspaceId": workspace_id,
+            "events": events,
+            "latestSeq": latest_seq,
+            "oldestSeq": oldest_seq,
+            "hasMore": has_more,
+            "gap": gap,
+        }))
+    }
+
+    /// Forget a workspace and delete its journal directory once queued
+    /// records have been written.
+    pub(crate) fn remove(&self, workspace_id: &str) -> Result<(), String> {
+        let journal = self
+            .workspaces
+            .lock()
+            .map_err(|_| "event journal lock poisoned".to_string())?
+            .remove(workspace_id)
+            .unwrap_or_else(|| Arc::new(WorkspaceJournal::new(self.workspace_dir(workspace_id))));
+        self.send(JournalCommand::Remove { journal })
+    }
+
+    /// Delete journals for workspaces that are no longer registered.
+    pub(crate) fn prune(&self, workspace_ids: HashSet<String>) -> Result<(), String> {
+        self.send(JournalCommand::Prune { workspace_ids })
+    }
+
+    /// Block until every record queued so far is on disk.
+    fn flush(&self) -> Result<(), String> {
+        let (done_tx, done_rx) = mpsc::sync_channel(1);
+        self.send(JournalCommand::Flush(done_tx))?;
+        done_rx
+            .recv()
+            .map_err(|_| "event journal writer stopped".to_string())
+    }
+
+    fn send(&self, command: JournalCommand) -> Result<(), String> {
+        self.sender
+            .as_ref()
+            .ok_or_else(|| "event journal writer stopped".to_string())?
+            .send(command)
+            .map_err(|_| "event journal writer stopped".to_string())
+    }
+
+    fn workspace(&self, workspace_id: &str) -> Result<Arc<WorkspaceJournal>, String> {
+        let mut workspaces = self
+            .workspaces
+            .lock()
+            .map_err(|_| "event journal lock poisoned".to_string())?;
+        let journal = workspaces
+            .entry(workspace_id.to_string())
+            .or_insert_with(|| Arc::new(WorkspaceJournal::new(self.workspace_dir(workspace_id))));
+        Ok(Arc::clone(journal))
+    }
+
+    fn workspace_dir(&self, workspace_id: &str) -> PathBuf {
+        self.root.join(encode_workspace_id(workspace_id))
+    }
+}
+
+impl Drop for EventJournal {
+    fn drop(&mut self) {
+        // Closing the channel lets the writer drain what is queued and exit.
+        drop(self.sender.take());
+        if let Some(writer) = self.writer.take() {
+            let _ = writer.join();
+        }
+    }
+}
+
+impl WorkspaceJournal {
+    fn new(dir: PathBuf) -> Self {
+        Self {
+            dir,
+            next_seq: StdMutex::new(None),
+            file: StdMutex::new(JournalFile::default()),
+        }
+    }
+
+    fn write_line(&self, line: &str) -> Result<(), String> {
+        let mut state = self
+            .file
+            .lock()
+            .map_err(|_| "event journal lock poisoned".to_string())?;
+        if state.bytes_written >= MAX_JOURNAL_FILE_BYTES {
+            state.file = None;
+            rotate_jsonl_file_if_needed(
+                &self.dir,
+                JOURNAL_FILE_BASENAME,
+                MAX_JOURNAL_FILE_BYTES,
+                MAX_JOURNAL_FILES_TOTAL,
+            )?;
+        }
+        if state.file.is_none() {
+            fs::create_dir_all(&self.dir)
+                .map_err(|err| format!("failed to create event journal dir: {err}"))?;
+            let path = jsonl_file_path(&self.dir, JOURNAL_FILE_BASENAME, None);
+            let file = OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(&path)
+                .map_err(|err| format!("failed to open event journal: {err}"))?;
+            state.bytes_written = file.metadata().map(|meta| meta.len()).unwrap_or(0);
+            state.file = Some(file);
+        }
+        let Some(file) = state.file.as_mut() else {
+            return Err("event journal file unavailable".to_string());
+        };
+        file.write_all(line.as_bytes())
+            .map_err(|err| format!("failed to write event journal record: {err}"))?;
+        state.bytes_written += line.len() as u64;
+        Ok(())
+    }
+
+    fn delete(&self) -> Result<(), String> {
+        if let Ok(mut state) = self.file.lock() {
+            *state = JournalFile::default();
+        }
+        match fs::remove_dir_all(&self.dir) {
+            Ok(()) => Ok(()),
+            Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(()),
+            Err(err) => Err(format!("failed to remove event journal: {err}")),
+        }
+    }
+}
+
+fn run_journal_writer(root: &Path, receiver: mpsc::Receiver<JournalCommand>) {
+    for command in receiver {
+        let result = match command {
+            JournalCommand::Append { journal, line } => journal.write_line(&line),
+            JournalCommand::Remove { journal } => journal.delete(),
+            JournalCommand::Prune { workspace_ids } => prune_journal_dirs(root, &workspace_ids),
+            JournalCommand::Flush(done) => {
+                let _ = done.send(());
+                Ok(())
+            }
+        };
+        if let Err(err) = result {
+            eprintln!("event journal: {err}");
+        }
+    }
+}
+
+fn prune_journal_dirs(root: &Path, workspace_ids: &HashSet<String>) -> Result<(), String> {
+    let entries = match fs::read_dir(root) {
+        Ok(entries) => entries,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(()),
+        Err(err) => return Err(format!("failed to read event journal dir: {err}")),
+    };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_dir() {
+            continue;
+        }
+        let known = entry
+            .file_name()
+            .to_str()
+            .and_then(decode_workspace_id)
+            .is_some_and(|workspace_id| workspace_ids.contains(&workspace_id));
+        if !known {
+            if let Err(err) = fs::remove_dir_all(&path) {
+                eprintln!("failed to prune event journal {}: {err}", path.display());
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Open the journal under `data_dir` and drop journals left behind by
+/// workspaces that are no longer registered.
+pub(crate) fn configure_event_journal(data_dir: &Path, workspace_ids: HashSet<String>) {
+    if EVENT_JOURNAL
+        .set(EventJournal::new(data_dir.join(JOURNAL_DIR_NAME)))
+        .is_err()
+    {
+        return;
+    }
+    if let Some(journal) = EVENT_JOURNAL.get() {
+        if let Err(err) = journal.prune(workspace_ids) {
+            eprintln!("failed to prune event journals: {err}");
+        }
+    }
+}
+
+/// Best-effort append; journaling failures must never break the session.
+pub(crate) fn record_app_server_message(
+    workspace_id: &str,
+    direction: JournalDirection,
+    message: &Value,
+) -> Option<u64> {
+    let journal = EVENT_JOURNAL.get()?;
+    match journal.append(workspace_id, direction, message) {
+        Ok(seq) => Some(seq),
+        Err(err) => {
+            eprintln!("failed to journal app-server message for {workspace_id}: {err}");
+            None
+        }
+    }
+}
+
+/// Best-effort removal of a deleted workspace's journal.
+pub(crate) fn remove_workspace_journal(workspace_id: &str) {
+    let Some(journal) = EVENT_JOURNAL.get() else {
+        return;
+    };
+    if let Err(err) = journal.remove(workspace_id) {
+        eprintln!("failed to remove event journal for {workspace_id}: {err}");
+    }
+}
+
+pub(crate) async fn replay_events_core(
+    workspace_id: String,
+    since_seq: u64,
+    limit: Option<u32>,
+) -> Result<Value, String> {
+    let limit = limit
+        .unwrap_or(DEFAULT_REPLAY_LIMIT)
+        .clamp(1, MAX_REPLAY_LIMIT);
+    tokio::task::spawn_blocking(move || {
+        let journal = EVENT_JOURNAL
+            .get()
+            .ok_or_else(|| "event journal is not configured".to_string())?;
+        journal.replay(&workspace_id, since_seq, limit)
+    })
+    .await
+    .map_err(|err| format!("failed to join event replay task: {err}"))?
+}
+
+/// Directory name for a workspace. UUID ids pass through unchanged; every
+/// other byte is written as `_xx` so distinct ids never share a directory.
+fn encode_workspace_id(workspace_id: &str) -> String {
+    let mut encoded = String::with_capacity(workspace_id.len());
+    for byte in workspace_id.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' {
+            encoded.push(byte as char);
+        } else {
+            encoded.push_str(&format!("_{byte:02x}"));
+        }
+    }
+    if encoded.is_empty() {
+        "_".to_string()
+    } else {
+        encoded
+    }
+}
+
+fn decode_workspace_id(name: &str) -> Option<String> {
+    let mut bytes = Vec::with_capacity(name.len());
+    let mut index = 0;
+    while index < name.len() {
+        if name.as_bytes()[index] == b'_' {
+            let hex = name.get(index + 1..index + 3)?;
+            bytes.push(u8::from_str_radix(hex, 16).ok()?);
+            index += 3;
+        } else {
+            bytes.push(name.as_bytes()[index]);
+            index += 1;
+        }
+    }
+    String::from_utf8(bytes).ok()
+}
+
+fn journal_files_oldest_first(dir: &Path) -> Vec<PathBuf> {
+    let mut files: Vec<PathBuf> = (1..MAX_JOURNAL_FILES_TOTAL)
+        .rev()
+        .map(|index| jsonl_file_path(dir, JOURNAL_FILE_BASENAME, Some(index)))
+        .collect();
+    files.push(jsonl_file_path(dir, JOURNAL_FILE_BASENAME, None));
+    files.retain(|path| path.exists());
+    files
+}
+
+fn parse_record(line: &str) -> Option<(u64, Value)> {
+    let record: Value = serde_json::from_str(line.trim()).ok()?;
+    let seq = record.get("seq")?.as_u64()?;
+    Some((seq, record))
+}
+
+fn recover_last_seq(dir: &Path) -> u64 {
+    journal_files_oldest_first(dir)
+        .iter()
+        .rev()
+        .find_map(|path| last_seq_in_file(path))
+        .unwrap_or(0)
+}
+
+/// Scan backwards from the end of a segment instead of reading it whole.
+fn last_seq_in_file(path: &Path) -> Option<u64> {
+    let mut file = File::open(path).ok()?;
+    let len = file.metadata().ok()?.len();
+    let mut window = SEQ_RECOVERY_TAIL_BYTES.min(len);
+    while window > 0 {
+        file.seek(SeekFrom::Start(len - window)).ok()?;
+        let mut buffer = Vec::with_capacity(window as usize);
+        (&mut file).take(window).read_to_end(&mut buffer).ok()?;
+        let text = String::from_utf8_lossy(&buffer);
+        // The first line is cut off unless the window covers the whole file.
+        let skip = usize::from(window < len);
+        let lines: Vec<&str> = text.lines().skip(skip).collect();
+        if let Some(seq) = lines
+            .iter()
+            .rev()
+            .find_map(|line| parse_record(line).map(|(seq, _)| seq))
+        {
+            return Some(seq);
+        }
+        if window == len {
+            break;
+        }
+        window = window.saturating_mul(2).min(len);
+    }
+    None
+}
+
+fn now_millis() -> u128 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_workspace_id, encode_workspace_id, EventJournal, JournalDirection};
+    use serde_json::json;
+    use std::collections::HashSet;
+    use uuid::Uuid;
+
+    fn temp_journal() -> (EventJournal, std::path::PathBuf) {
+        let root = std::env::temp_dir().join(format!("codex-monitor-journal-{}", Uuid::new_v4()));
+        (EventJournal::new(root.clone()), root)
+    }
+
+    #[test]
+    fn replay_returns_records_after_since_seq() {
+        let (journal, root) = temp_journal();
+        for index in 0..5 {
+            let seq = journal
+                .append(
+                    "ws-1",
+                    JournalDirection::Inbound,
+                    &json!({ "method": "item/agentMessage/delta", "params": { "index": index } }),
+                )
+                .expect("append");
+            assert_eq!(seq, index + 1);
+        }
+
+        let replay = journal.replay("ws-1", 3, 10).expect("replay");
+        let events = replay["events"].as_array().expect("events");
+        assert_eq!(events.len(), 2);
+        assert_eq!(events[0]["seq"], 4);
+        assert_eq!(events[0]["direction"], "inbound");
+        assert_eq!(replay["latestSeq"], 5);
+        assert_eq!(replay["hasMore"], false);
+        assert_eq!(replay["gap"], false);
+
+        let limited = journal.replay("ws-1", 0, 2).expect("replay");
+        assert_eq!(limited["events"].as_array().map(Vec::len), Some(2));
+        assert_eq!(limited["hasMore"], true);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn sequence_resumes_after_reopen() {
+        let (journal, root) = temp_journal();
+        journal
+            .append("ws-1", JournalDirection::Outbound, &json!({ "id": 1 }))
+            .expect("append");
+        journal
+            .append("ws-1", JournalDirection::Outbound, &json!({ "id": 2 }))
+            .expect("append");
+        drop(journal);
+
+        let reopened = EventJournal::new(root.clone());
+        let seq = reopened
+            .append(
+                "ws-1",
+                JournalDirection::Local,
+                &json!({ "method": "codex/connected" }),
+            )
+            .expect("append");
+        assert_eq!(seq, 3);
+        let other = reopened
+            .append(
+                "ws-2",
+                JournalDirection::Inbound,
+                &json!({ "method": "turn/started" }),
+            )
+            .expect("append");
+        assert_eq!(other, 1);
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn workspace_dir_names_are_distinct_and_reversible() {
+        let ids = ["a.b", "a_b", "a/b", "3f2c-uuid"];
+        let encoded: HashSet<String> = ids.iter().map(|id| encode_workspace_id(id)).collect();
+        assert_eq!(encoded.len(), ids.len());
+        assert_eq!(encode_workspace_id("3f2c-uuid"), "3f2c-uuid");
+        for id in ids {
+            assert_eq!(
+                decode_workspace_id(&encode_workspace_id(id)).as_deref(),
+                Some(id)
+            );
+        }
+    }
+
+    #[test]
+    fn removed_and_unknown_workspaces_are_pruned() {
+        let (journal, root) = temp_journal();
+        for workspace_id in ["ws-1", "ws-2", "ws-3"] {
+            journal
+                .append(workspace_id, JournalDirection::Inbound, &json!({ "id": 1 }))
+                .expect("append");
+        }
+
+        journal.remove("ws-1").expect("remove");
+        journal
+            .prune(HashSet::from(["ws-1".to_string(), "ws-2".to_string()]))
+            .expect("prune");
+        drop(journal);
+
+        assert!(!root.join("ws-1").exists());
+        assert!(root.join("ws-2").exists());
+        assert!(!root.join("ws-3").exists());
+
+        let _ = std::fs::remove_dir_all(root);
+    }
+}
@@ -0,0 +1,620 @@
+//! Per-workspace file watchers that keep the git status cache current and
+//! push `git-status-changed` events, so clients do not have to poll
+//! `get_git_status`.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::Duration;
+
+use git2::{Repository, Status};
+use ignore::gitignore::{Gitignore, GitignoreBuilder};
+use ignore::{Match, WalkBuilder};
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use serde_json::Value;
+use tokio::sync::{mpsc, Mutex};
+use tokio::task::JoinHandle;
+use tokio::time::Instant;
+
+use crate::backend::events::{EventSink, GitStatusChanged};
+use crate::git_utils::resolve_git_root;
+use crate::shared::git_ui_core::{
+    git_branch_name, git_status_value, invalidate_cached_git_status, read_git_status_entries,
+    set_git_status_watched, store_cached_git_status,
+};
+use crate::types::WorkspaceEntry;
+
+/// Quiet period before a burst of file events is turned into a status scan.
+const DEBOUNCE: Duration = Duration::from_millis(300);
+/// Longest a steady stream of events can hold back a scan.
+const MAX_DEBOUNCE: Duration = Duration::from_secs(2);
+/// Batches touching more paths than this are rescanned in full.
+const MAX_INCREMENTAL_PATHS: usize = 256;
+/// FSEvents and ReadDirectoryChangesW watch a whole tree with one handle;
+/// inotify needs one per folder, so there only folders git tracks are watched.
+const WATCH_RECURSIVELY: bool = cfg!(any(target_os = "macos", target_os = "windows"));
+/// Files in the git directory whose changes can alter the status.
+const GIT_DIR_TRIGGERS: &[&str] = &[
+    "index",
+    "HEAD",
+    "packed-refs",
+    "MERGE_HEAD",
+    "CHERRY_PICK_HEAD",
+    "REVERT_HEAD",
+    "info/exclude",
+    "refs",
+];
+
+type WatcherMap = StdMutex<HashMap<String, WorkspaceWatcher>>;
+
+struct WorkspaceWatcher {
+    refs: usize,
+    task: JoinHandle<()>,
+}
+
+impl Drop for WorkspaceWatcher {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Running watchers, one per workspace, shared by every client.
+#[derive(Clone, Default)]
+pub(crate) struct GitStatusWatchers {
+    watchers: Arc<WatcherMap>,
+}
+
+/// Keeps a workspace's watcher running; dropping the last one stops it.
+pub(crate) struct GitStatusWatch {
+    watchers: Arc<WatcherMap>,
+    workspace_id: String,
+}
+
+impl Drop for GitStatusWatch {
+    fn drop(&mut self) {
+        let Ok(mut watchers) = self.watchers.lock() else {
+            return;
+        };
+        let Some(watcher) = watchers.get_mut(&self.workspace_id) else {
+            return;
+        };
+        watcher.refs = watcher.refs.saturating_sub(1);
+        if watcher.refs == 0 {
+            watchers.remove(&self.workspace_id);
+            set_git_status_watched(&self.workspace_id, false);
+        }
+    }
+}
+
+/// Watches held by one client. A workspace can be watched more than once;
+/// each `unwatch` releases one of them.
+#[derive(Default)]
+pub(crate) struct GitStatusWatchSet {
+    watches: HashMap<String, Vec<GitStatusWatch>>,
+}
+
+impl GitStatusWatchSet {
+    fn insert(&mut self, watch: GitStatusWatch) {
+        self.watches
+            .entry(watch.workspace_id.clone())
+            .or_default()
+            .push(watch);
+    }
+
+    fn release(&mut self, workspace_id: &str) -> bool {
+        let Some(watches) = self.watches.get_mut(workspace_id) else {
+            return false;
+        };
+        let released = watches.pop().is_some();
+        if watches.is_empty() {
+            self.watches.remove(workspace_id);
+        }
+        released
+    }
+}
+
+/// Status of one repository, updated from batches of changed paths.
+struct GitStatusTracker {
+    repo: Repository,
+    root: PathBuf,
+    git_dirs: Vec<PathBuf>,
+    branch_name: String,
+    entries: BTreeMap<String, Status>,
+    /// `.gitignore` matcher per directory (relative to `root`), if it has one.
+    ignores: HashMap<PathBuf, Option<Gitignore>>,
+    last_value: Value,
+}
+
+impl GitStatusTracker {
+    fn open(repo_root: &Path) -> Result<Self, String> {
+        let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+        let root = canonical(repo_root);
+        let mut git_dirs = vec![canonical(repo.path())];
+        let common_dir = canonical(repo.commondir());
+        if !git_dirs.contains(&common_dir) {
+            git_dirs.push(common_dir);
+        }
+        let mut tracker = Self {
+            repo,
+            root,
+            git_dirs,
+            branch_name: String::new(),
+            entries: BTreeMap::new(),
+            ignores: HashMap::new(),
+            last_value: Value::Null,
+        };
+        tracker.refresh_all()?;
+        tracker.last_value = tracker.value();
+        Ok(tracker)
+    }
+
+    fn value(&self) -> Value {
+        git_status_value(&self.branch_name, self.entries.iter())
+    }
+
+    fn refresh_all(&mut self) -> Result<(), String> {
+        self.branch_name = git_branch_name(&self.repo);
+        self.entries = read_git_status_entries(&self.repo, &[])?
+            .into_iter()
+            .collect();
+        Ok(())
+    }
+
+    /// Rescans the status for `paths`. Returns the new payload when it
+    /// differs from the last one.
+    fn apply(&mut self, paths: HashSet<PathBuf>) -> Result<Option<Value>, String> {
+        let mut full = false;
+        let mut changed = Vec::new();
+        for path in paths {
+            if let Some(relative) = self.git_dir_relative(&path) {
+                full |= GIT_DIR_TRIGGERS
+                    .iter()
+                    .any(|trigger| relative.starts_with(trigger));
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&self.root) else {
+                continue;
+            };
+            if relative.as_os_str().is_empty()
+                || relative.components().any(|part| part.as_os_str() == ".git")
+            {
+                continue;
+            }
+            if relative
+                .file_name()
+                .is_some_and(|name| name == ".gitignore")
+            {
+                self.ignores.clear();
+                full = true;
+                continue;
+            }
+            if self.is_ignored(relative, path.is_dir()) {
+                continue;
+            }
+            changed.push(relative.to_path_buf());
+        }
+
+        if full || changed.len() > MAX_INCREMENTAL_PATHS || !self.can_refresh_paths(&changed)? {
+            self.refresh_all()?;
+        } else if !changed.is_empty() {
+            self.refresh_paths(&changed)?;
+        }
+
+        let value = self.value();
+        if value == self.last_value {
+            return Ok(None);
+        }
+        self.last_value = value.clone();
+        Ok(Some(value))
+    }
+
+    fn git_dir_relative(&self, path: &Path) -> Option<PathBuf> {
+        self.git_dirs
+            .iter()
+            .find_map(|git_dir| path.strip_prefix(git_dir).ok())
+            .map(Path::to_path_buf)
+    }
+
+    /// A pathspec scan cannot reproduce renames or the single `dir/` entry
+    /// git reports for an untracked folder, so those need a full rescan.
+    fn can_refresh_paths(&self, changed: &[PathBuf]) -> Result<bool, String> {
+        if self
+            .entries
+            .values()
+            .any(|status| status.intersects(Status::INDEX_RENAMED | Status::WT_RENAMED))
+        {
+            return Ok(false);
+        }
+        let mut index = self.repo.index().map_err(|e| e.to_string())?;
+        index.read(false).map_err(|e| e.to_string())?;
+        let is_tracked_dir =
+            |dir: &str| dir.is_empty() || index.find_prefix(format!("{dir}/")).is_ok();
+        Ok(changed.iter().all(|path| {
+            let parent = path.parent().map(pathspec).unwrap_or_default();
+            is_tracked_dir(&parent)
+                && (!self.root.join(path).is_dir() || is_tracked_dir(&pathspec(path)))
+        }))
+    }
+
+    fn refresh_paths(&mut self, changed: &[PathBuf]) -> Result<(), String> {
+        let pathspecs: Vec<String> = changed.iter().map(|path| pathspec(path)).collect();
+        self.entries.retain(|key, _| {
+            !pathspecs.iter().any(|path| {
+                key == path
+                    || key
+                        .strip_prefix(path.as_str())
+                        .is_some_and(|rest| rest.starts_with('/'))
+            })
+        });
+        self.entries
+            .extend(read_git_status_entries(&self.repo, &pathspecs)?);
+        Ok(())
+    }
+
+    /// The nearest `.gitignore` with a matching rule decides, as in git.
+    fn is_ignored(&mut self, relative: &Path, is_dir: bool) -> bool {
+        let path = self.root.join(relative);
+        let Some(parent) = relative.parent() else {
+            return false;
+        };
+        for dir in parent.ancestors() {
+            let Some(matcher) = self.ignore_matcher(dir) else {
+                continue;
+            };
+            match matcher.matched_path_or_any_parents(&path, is_dir) {
+                Match::Ignore(_) => return true,
+                Match::Whitelist(_) => return false,
+                Match::None => {}
+            }
+        }
+        false
+    }
+
+    fn ignore_matcher(&mut self, dir: &Path) -> Option<&Gitignore> {
+        if !self.ignores.contains_key(dir) {
+            let absolute = self.root.join(dir);
+            let mut builder = GitignoreBuilder::new(&absolute);
+            let mut has_rules = builder.add(absolute.join(".gitignore")).is_none();
+            if dir.as_os_str().is_empty() {
+                let exclude = self.git_dirs[0].join("info").join("exclude");
+                has_rules |= builder.add(exclude).is_none();
+            }
+            let matcher = if has_rules {
+                builder.build().ok()
+            } else {
+                None
+            };
+            self.ignores.insert(dir.to_path_buf(), matcher);
+        }
+        self.ignores.get(dir).and_then(Option::as_ref)
+    }
+}
+
+fn canonical(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+fn pathspec(path: &Path) -> String {
+    path.to_string_lossy().replace('\\', "/")
+}
+
+struct WatchState {
+    tracker: GitStatusTracker,
+    watcher: RecommendedWatcher,
+    /// Folders with their own watch when not watching recursively.
+    watched_dirs: HashSet<PathBuf>,
+}
+
+impl WatchState {
+    fn start(repo_root: PathBuf) -> Result<(Self, mpsc::UnboundedReceiver<Vec<PathBuf>>), String> {
+        let tracker = GitStatusTracker::open(&repo_root)?;
+        let (tx, rx) = mpsc::unbounded_channel();
+        let watcher = notify::recommended_watcher(move |result: notify::Result<notify::Event>| {
+            if let Ok(event) = result {
+                if !matches!(event.kind, EventKind::Access(_)) {
+                    let _ = tx.send(event.paths);
+                }
+            }
+        })
+        .map_err(|e| e.to_string())?;
+        let mut state = Self {
+            tracker,
+            watcher,
+            watched_dirs: HashSet::new(),
+        };
+        let root = state.tracker.root.clone();
+        if WATCH_RECURSIVELY {
+            state
+                .watcher
+                .watch(&root, RecursiveMode::Recursive)
+                .map_err(|e| e.to_string())?;
+        } else {
+            state.watch_tree(&root);
+        }
+        state.watch_git_dirs()?;
+        Ok((state, rx))
+    }
+
+    /// Watches every folder under `dir` that git does not ignore, so trees
+    /// such as `node_modules` do not use up inotify watches.
+    fn watch_tree(&mut self, dir: &Path) {
+        let walker = WalkBuilder::new(dir)
+            .hidden(false)
+            .filter_entry(|entry| entry.file_name() != ".git")
+            .build();
+        for entry in walker.flatten() {
+            if !entry.file_type().is_some_and(|kind| kind.is_dir())
+                || self.watched_dirs.contains(entry.path())
+            {
+                continue;
+            }
+            if self
+                .watcher
+                .watch(entry.path(), RecursiveMode::NonRecursive)
+                .is_ok()
+            {
+                self.watched_dirs.insert(entry.into_path());
+            }
+        }
+    }
+
+    fn watch_git_dirs(&mut self) -> Result<(), String> {
+        for git_dir in &self.tracker.git_dirs {
+            if WATCH_RECURSIVELY && git_dir.starts_with(&self.tracker.root) {
+                continue;
+            }
+            self.watcher
+                .watch(git_dir, RecursiveMode::NonRecursive)
+                .map_err(|e| e.to_string())?;
+            for nested in ["refs", "info"] {
+                let path = git_dir.join(nested);
+                if path.is_dir() {
+                    let _ = self.watcher.watch(&path, RecursiveMode::Recursive);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Follows folders created or removed since the last batch.
+    fn update_watched_dirs(&mut self, paths: &HashSet<PathBuf>) {
+        for path in paths {
+            if self.tracker.git_dir_relative(path).is_some() {
+                continue;
+            }
+            let Ok(relative) = path.strip_prefix(&self.tracker.root) else {
+                continue;
+            };
+            if path.is_dir() {
+                if !self.watched_dirs.contains(path) && !self.tracker.is_ignored(relative, true) {
+                    self.watch_tree(path);
+                }
+            } else if self.watched_dirs.contains(path) {
+                self.watched_dirs.retain(|dir| !dir.starts_with(path));
+            }
+        }
+    }
+
+    fn apply(&mut self, paths: HashSet<PathBuf>) -> Result<Option<Value>, String> {
+        if !WATCH_RECURSIVELY {
+            self.update_watched_dirs(&paths);
+        }
+        self.tracker.apply(paths)
+    }
+}
+
+async fn run_watch_loop<E: EventSink>(
+    workspace_id: String,
+    mut state: WatchState,
+    mut rx: mpsc::UnboundedReceiver<Vec<PathBuf>>,
+    event_sink: E,
+) {
+    while let Some(first) = rx.recv().await {
+        let mut paths: HashSet<PathBuf> = first.into_iter().collect();
+        let deadline = Instant::now() + MAX_DEBOUNCE;
+        loop {
+            let wait = DEBOUNCE.min(deadline.saturating_duration_since(Instant::now()));
+            match tokio::time::timeout(wait, rx.recv()).await {
+                Ok(Some(batch)) => paths.extend(batch),
+                Ok(None) => return,
+                Err(_) => break,
+            }
+        }
+        let scan = tokio::task::spawn_blocking(move || {
+            let result = state.apply(paths);
+            (state, result)
+        })
+        .await;
+        let Ok((next, result)) = scan else {
+            return;
+        };
+        state = next;
+        match result {
+            Ok(Some(status)) => {
+                store_cached_git_status(&workspace_id, &status);
+                event_sink.emit_git_status_changed(GitStatusChanged {
+                    workspace_id: workspace_id.clone(),
+                    status,
+                });
+            }
+            Ok(None) => {}
+            Err(err) => {
+                invalidate_cached_git_status(&workspace_id);
+                eprintln!("[git-watch] failed to refresh status for {workspace_id}: {err}");
+            }
+        }
+    }
+}
+
+fn add_watch_ref(watchers: &GitStatusWatchers, workspace_id: &str) -> Option<GitStatusWatch> {
+    let mut running = watchers.watchers.lock().ok()?;
+    let watcher = running.get_mut(workspace_id)?;
+    watcher.refs += 1;
+    Some(GitStatusWatch {
+        watchers: Arc::clone(&watchers.watchers),
+        workspace_id: workspace_id.to_string(),
+    })
+}
+
+async fn start_watch<E: EventSink>(
+    watchers: &GitStatusWatchers,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: &str,
+    event_sink: E,
+) -> Result<GitStatusWatch, String> {
+    if let Some(watch) = add_watch_ref(watchers, workspace_id) {
+        return Ok(watch);
+    }
+    let entry = workspaces
+        .lock()
+        .await
+        .get(workspace_id)
+        .cloned()
+        .ok_or_else(|| "workspace not found".to_string())?;
+    let repo_root = resolve_git_root(&entry)?;
+    let (state, rx) = tokio::task::spawn_blocking(move || WatchState::start(repo_root))
+        .await
+        .map_err(|e| e.to_string())??;
+    let status = state.tracker.last_value.clone();
+
+    let mut running = watchers
+        .watchers
+        .lock()
+        .map_err(|_| "git status watchers lock poisoned".to_string())?;
+    // Another client may have started one while this one was scanning.
+    if let Some(watcher) = running.get_mut(workspace_id) {
+        watcher.refs += 1;
+    } else {
+        store_cached_git_status(workspace_id, &status);
+        set_git_status_watched(workspace_id, true);
+        let task = tokio::spawn(run_watch_loop(
+            workspace_id.to_string(),
+            state,
+            rx,
+            event_sink,
+        ));
+        running.insert(workspace_id.to_string(), WorkspaceWatcher { refs: 1, task });
+    }
+    Ok(GitStatusWatch {
+        watchers: Arc::clone(&watchers.watchers),
+        workspace_id: workspace_id.to_string(),
+    })
+}
+
+pub(crate) async fn watch_git_status_core<E: EventSink>(
+    watchers: &GitStatusWatchers,
+    watch_set: &Mutex<GitStatusWatchSet>,
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    event_sink: E,
+) -> Result<(), String> {
+    let watch = start_watch(watchers, workspaces, &workspace_id, event_sink).await?;
+    watch_set.lock().await.insert(watch);
+    Ok(())
+}
+
+pub(crate) async fn unwatch_git_status_core(
+    watch_set: &Mutex<GitStatusWatchSet>,
+    workspace_id: String,
+) -> Result<(), String> {
+    watch_set.lock().await.release(&workspace_id);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn create_temp_repo() -> (PathBuf, Repository) {
+        let root =
+            std::env::temp_dir().join(format!("codex-monitor-test-{}", uuid::Uuid::new_v4()));
+        fs::create_dir_all(&root).expect("create temp repo root");
+        let repo = Repository::init(&root).expect("init repo");
+        fs::write(root.join(".gitignore"), "target/\n").expect("write gitignore");
+        fs::create_dir_all(root.join("src")).expect("create src");
+        fs::write(root.join("src/main.rs"), "fn main() {}\n").expect("write file");
+        let mut index = repo.index().expect("repo index");
+        index
+            .add_path(Path::new(".gitignore"))
+            .expect("add gitignore");
+        index.add_path(Path::new("src/main.rs")).expect("add path");
+        index.write().expect("write index");
+        let tree_id = index.write_tree().expect("write tree");
+        {
+            let tree = repo.find_tree(tree_id).expect("find tree");
+            let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+            repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+                .expect("commit");
+        }
+        (canonical(&root), repo)
+    }
+
+    fn unstaged_paths(value: &Value) -> Vec<String> {
+        value["unstagedFiles"]
+            .as_array()
+            .expect("unstaged files")
+            .iter()
+            .map(|file| file["path"].as_str().unwrap_or_default().to_string())
+            .collect()
+    }
+
+    #[test]
+    fn tracker_updates_changed_paths_and_skips_ignored_ones() {
+        let (root, _repo) = create_temp_repo();
+        let mut tracker = GitStatusTracker::open(&root).expect("tracker");
+        assert!(unstaged_paths(&tracker.last_value).is_empty());
+
+        fs::create_dir_all(root.join("target")).expect("create target");
+        fs::write(root.join("target/out.o"), "bin").expect("write ignored");
+        let ignored = tracker
+            .apply(HashSet::from([root.join("target/out.o")]))
+            .expect("apply ignored");
+        assert!(ignored.is_none());
+
+        fs::write(root.join("src/main.rs"), "fn main() { run(); }\n").expect("edit file");
+        let changed = tracker
+            .apply(HashSet::from([root.join("src/main.rs")]))
+            .expect("apply change")
+            .expect("status changed");
+        assert_eq!(unstaged_paths(&changed), vec!["src/main.rs".to_string()]);
+
+        let unchanged = tracker
+            .apply(HashSet::from([root.join("src/main.rs")]))
+            .expect("apply again");
+        assert!(unchanged.is_none());
+
+        fs::write(root.join("src/main.rs"), "fn main() {}\n").expect("restore file");
+        let restored = tracker
+            .apply(HashSet::from([root.join("src/main.rs")]))
+            .expect("apply restore")
+            .expect("status changed");
+        assert!(unstaged_paths(&restored).is_empty());
+    }
+
+    #[test]
+    fn watch_set_releases_one_watch_at_a_time() {
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .build()
+            .expect("runtime");
+        runtime.block_on(async {
+            let watchers = GitStatusWatchers::default();
+            watchers.watchers.lock().unwrap().insert(
+                "ws-1".to_string(),
+                WorkspaceWatcher {
+                    refs: 0,
+                    task: tokio::spawn(async {}),
+                },
+            );
+            let mut set = GitStatusWatchSet::default();
+            set.insert(add_watch_ref(&watchers, "ws-1").expect("first"));
+            set.insert(add_watch_ref(&watchers, "ws-1").expect("second"));
+
+            assert!(set.release("ws-1"));
+            assert!(watchers.watchers.lock().unwrap().contains_key("ws-1"));
+            assert!(set.release("ws-1"));
+            assert!(!watchers.watchers.lock().unwrap().contains_key("ws-1"));
+            assert!(!set.release("ws-1"));
+        });
+    }
+}
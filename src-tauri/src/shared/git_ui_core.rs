@@ -1,11 +1,15 @@
-use std::collections::HashMap;
+use std::collections::hash_map::Entry;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 use std::sync::{Mutex as StdMutex, OnceLock};
 use std::time::{Duration, Instant};
 
 use base64::{engine::general_purpose::STANDARD, Engine as _};
-use git2::{BranchType, DiffOptions, Repository, Sort, Status, StatusOptions};
+use git2::{
+    ApplyLocation, BranchType, Commit, DiffOptions, FileMode, IndexEntry, Oid, Repository,
+    RepositoryState, Sort, Status, StatusOptions,
+};
 use serde_json::{json, Value};
 use tokio::sync::Mutex;
 
@@ -15,9 +19,11 @@ use crate::git_utils::{
 };
 use crate::shared::process_core::tokio_command;
 use crate::types::{
-    AppSettings, BranchInfo, GitCommitDiff, GitFileDiff, GitFileStatus, GitHubIssue,
-    GitHubIssuesResponse, GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff,
-    GitHubPullRequestsResponse, GitLogResponse, WorkspaceEntry,
+    AppSettings, BranchInfo, GitCommitDiff, GitConflictFile, GitConflictResolution,
+    GitConflictStatus, GitDiffSide, GitFileDiff, GitFileStatus, GitHubIssue, GitHubIssuesResponse,
+    GitHubPullRequest, GitHubPullRequestComment, GitHubPullRequestDiff, GitHubPullRequestsResponse,
+    GitHunkSelection, GitLineRange, GitLogEntry, GitLogFilter, GitLogGraphRow, GitLogResponse,
+    GitStashEntry, WorkspaceEntry,
 };
 use crate::utils::{git_env_path, normalize_git_path, resolve_git_binary};
 
@@ -25,7 +31,15 @@ const INDEX_SKIP_WORKTREE_FLAG: u16 = 0x4000;
 const MAX_IMAGE_BYTES: usize = 10 * 1024 * 1024;
 const MAX_TEXT_DIFF_BYTES: usize = 2 * 1024 * 1024;
 const GIT_STATUS_CACHE_TTL: Duration = Duration::from_millis(1_500);
+/// Backstop for watched workspaces: a watcher can miss folders it failed to
+/// watch or events the OS dropped, so their status is still rescanned now and
+/// then.
+const WATCHED_GIT_STATUS_CACHE_TTL: Duration = Duration::from_secs(30);
 const GIT_STATUS_CACHE_MAX_ENTRIES: usize = 256;
+const GIT_LOG_SCAN_BUDGET: usize = 10_000;
+const GIT_LOG_DEFAULT_PAGE_SIZE: usize = 40;
+/// Largest page `get_git_log` returns; larger limits are clamped.
+const GIT_LOG_MAX_PAGE_SIZE: usize = 500;
 
 #[derive(Clone)]
 struct GitStatusCacheEntry {
@@ -34,26 +48,56 @@ struct GitStatusCacheEntry {
 }
 
 static GIT_STATUS_CACHE: OnceLock<StdMutex<HashMap<String, GitStatusCacheEntry>>> = OnceLock::new();
+/// Workspaces whose cached status a file watcher keeps current, so they use
+/// `WATCHED_GIT_STATUS_CACHE_TTL`.
+static WATCHED_GIT_STATUS: OnceLock<StdMutex<HashSet<String>>> = OnceLock::new();
 
 fn git_status_cache() -> &'static StdMutex<HashMap<String, GitStatusCacheEntry>> {
     GIT_STATUS_CACHE.get_or_init(|| StdMutex::new(HashMap::new()))
 }
 
+fn watched_git_status() -> &'static StdMutex<HashSet<String>> {
+    WATCHED_GIT_STATUS.get_or_init(|| StdMutex::new(HashSet::new()))
+}
+
+pub(crate) fn set_git_status_watched(workspace_id: &str, watched: bool) {
+    let Ok(mut watched_ids) = watched_git_status().lock() else {
+        return;
+    };
+    if watched {
+        watched_ids.insert(workspace_id.to_string());
+    } else {
+        watched_ids.remove(workspace_id);
+    }
+}
+
+fn is_git_status_watched(workspace_id: &str) -> bool {
+    watched_git_status()
+        .lock()
+        .map(|watched_ids| watched_ids.contains(workspace_id))
+        .unwrap_or(false)
+}
+
 fn load_cached_git_status(workspace_id: &str) -> Option<Value> {
+    let ttl = if is_git_status_watched(workspace_id) {
+        WATCHED_GIT_STATUS_CACHE_TTL
+    } else {
+        GIT_STATUS_CACHE_TTL
+    };
     let Ok(mut cache) = git_status_cache().lock() else {
         return None;
     };
     let Some(entry) = cache.get(workspace_id) else {
         return None;
     };
-    if entry.captured_at.elapsed() > GIT_STATUS_CACHE_TTL {
+    if entry.captured_at.elapsed() > ttl {
         cache.remove(workspace_id);
         return None;
     }
     Some(entry.value.clone())
 }
 
-fn store_cached_git_status(workspace_id: &str, value: &Value) {
+pub(crate) fn store_cached_git_status(workspace_id: &str, value: &Value) {
     let Ok(mut cache) = git_status_cache().lock() else {
         return;
     };
@@ -79,7 +123,7 @@ fn store_cached_git_status(workspace_id: &str, value: &Value) {
     }
 }
 
-fn invalidate_cached_git_status(workspace_id: &str) {
+pub(crate) fn invalidate_cached_git_status(workspace_id: &str) {
     let Ok(mut cache) = git_status_cache().lock() else {
         return;
     };
@@ -568,23 +612,12 @@ async fn resolve_repo_root_for_workspace(
     resolve_git_root(&entry)
 }
 
-async fn get_git_status_inner(
-    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    workspace_id: String,
-) -> Result<Value, String> {
-    if let Some(cached) = load_cached_git_status(&workspace_id) {
-        return Ok(cached);
-    }
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-    let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-
-    let branch_name = repo
-        .head()
-        .ok()
-        .and_then(|head| head.shorthand().map(|s| s.to_string()))
-        .unwrap_or_else(|| "unknown".to_string());
-
+/// Changed paths of a repository with their raw status, in libgit2's order.
+/// Non-empty `pathspecs` limit the scan to those paths.
+pub(crate) fn read_git_status_entries(
+    repo: &Repository,
+    pathspecs: &[String],
+) -> Result<Vec<(String, Status)>, String> {
     let mut status_options = StatusOptions::new();
     status_options
         .include_untracked(true)
@@ -592,6 +625,9 @@ async fn get_git_status_inner(
         .renames_head_to_index(true)
         .renames_index_to_workdir(true)
         .include_ignored(false);
+    for pathspec in pathspecs {
+        status_options.pathspec(pathspec);
+    }
 
     let statuses = repo
         .statuses(Some(&mut status_options))
@@ -599,11 +635,7 @@ async fn get_git_status_inner(
 
     let index = repo.index().ok();
 
-    let mut files = Vec::new();
-    let mut staged_files = Vec::new();
-    let mut unstaged_files = Vec::new();
-    let mut total_additions = 0i64;
-    let mut total_deletions = 0i64;
+    let mut entries = Vec::new();
     for entry in statuses.iter() {
         let path = entry.path().unwrap_or("");
         if path.is_empty() {
@@ -616,8 +648,30 @@ async fn get_git_status_inner(
                 }
             }
         }
-        let status = entry.status();
-        let normalized_path = normalize_git_path(path);
+        entries.push((normalize_git_path(path), entry.status()));
+    }
+    Ok(entries)
+}
+
+pub(crate) fn git_branch_name(repo: &Repository) -> String {
+    repo.head()
+        .ok()
+        .and_then(|head| head.shorthand().map(|s| s.to_string()))
+        .unwrap_or_else(|| "unknown".to_string())
+}
+
+/// The `get_git_status` payload for `entries`.
+pub(crate) fn git_status_value<'a>(
+    branch_name: &str,
+    entries: impl IntoIterator<Item = (&'a String, &'a Status)>,
+) -> Value {
+    let mut files = Vec::new();
+    let mut staged_files = Vec::new();
+    let mut unstaged_files = Vec::new();
+    let mut total_additions = 0i64;
+    let mut total_deletions = 0i64;
+    for (normalized_path, status) in entries {
+        let status = *status;
         let include_index = status.intersects(
             Status::INDEX_NEW
                 | Status::INDEX_MODIFIED
@@ -674,7 +728,7 @@ async fn get_git_status_inner(
                 .or_else(|| status_for_index(status))
                 .unwrap_or("--");
             files.push(GitFileStatus {
-                path: normalized_path,
+                path: normalized_path.clone(),
                 status: status_str.to_string(),
                 additions: combined_additions,
                 deletions: combined_deletions,
@@ -682,14 +736,33 @@ async fn get_git_status_inner(
         }
     }
 
-    let result = json!({
+    json!({
         "branchName": branch_name,
         "files": files,
         "stagedFiles": staged_files,
         "unstagedFiles": unstaged_files,
         "totalAdditions": total_additions,
         "totalDeletions": total_deletions,
-    });
+    })
+}
+
+async fn get_git_status_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Value, String> {
+    if let Some(cached) = load_cached_git_status(&workspace_id) {
+        return Ok(cached);
+    }
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+
+    let branch_name = git_branch_name(&repo);
+    let entries = read_git_status_entries(&repo, &[])?;
+    let result = git_status_value(
+        &branch_name,
+        entries.iter().map(|(path, status)| (path, status)),
+    );
     store_cached_git_status(&workspace_id, &result);
     Ok(result)
 }
@@ -772,6 +845,379 @@ async fn revert_git_all_inner(
     Ok(())
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum HunkAction {
+    Stage,
+    Unstage,
+    Revert,
+}
+
+/// `(start, count)` of one side of a hunk header; a missing count means 1.
+fn parse_hunk_side(raw: &str) -> Option<(u32, u32)> {
+    let (start, count) = match raw.split_once(',') {
+        Some((start, count)) => (start, count.parse().ok()?),
+        None => (raw, 1),
+    };
+    Some((start.parse().ok()?, count))
+}
+
+/// Line ranges covered by a header such as `@@ -3,4 +3,6 @@ fn main`.
+fn hunk_header_ranges(header: &str) -> Result<Vec<GitLineRange>, String> {
+    let invalid = || format!("invalid hunk header: {header}");
+    let rest = header.trim().strip_prefix("@@ ").ok_or_else(invalid)?;
+    let (ranges, _) = rest.split_once(" @@").ok_or_else(invalid)?;
+    let (old, new) = ranges.split_once(' ').ok_or_else(invalid)?;
+    let old = old
+        .strip_prefix('-')
+        .and_then(parse_hunk_side)
+        .ok_or_else(invalid)?;
+    let new = new
+        .strip_prefix('+')
+        .and_then(parse_hunk_side)
+        .ok_or_else(invalid)?;
+    Ok([(GitDiffSide::Old, old), (GitDiffSide::New, new)]
+        .into_iter()
+        .filter(|(_, (_, count))| *count > 0)
+        .map(|(side, (start, count))| GitLineRange {
+            side,
+            start,
+            end: start + count - 1,
+        })
+        .collect())
+}
+
+fn selection_ranges(selection: &GitHunkSelection) -> Result<Vec<GitLineRange>, String> {
+    let mut ranges = Vec::new();
+    for header in &selection.hunks {
+        ranges.extend(hunk_header_ranges(header)?);
+    }
+    for range in &selection.lines {
+        if range.start == 0 || range.end < range.start {
+            return Err(format!("invalid line range {}-{}", range.start, range.end));
+        }
+        ranges.push(range.clone());
+    }
+    if ranges.is_empty() {
+        return Err("Select at least one hunk or line.".to_string());
+    }
+    Ok(ranges)
+}
+
+fn patch_file_mode(mode: FileMode) -> &'static str {
+    match mode {
+        FileMode::BlobExecutable => "100755",
+        FileMode::Link => "120000",
+        _ => "100644",
+    }
+}
+
+fn push_patch_line(out: &mut Vec<u8>, origin: u8, content: &[u8]) {
+    out.push(origin);
+    out.extend_from_slice(content);
+    if !content.ends_with(b"\n") {
+        out.extend_from_slice(b"\n\\ No newline at end of file\n");
+    }
+}
+
+/// Rewrites a single-file patch so it only carries the lines `is_selected`
+/// accepts: unselected additions are dropped and unselected deletions become
+/// context. Returns `None` when nothing in the patch is selected.
+fn build_partial_patch(
+    patch: &git2::Patch,
+    is_selected: impl Fn(&git2::DiffLine<'_>) -> bool,
+) -> Result<Option<Vec<u8>>, String> {
+    let mut hunks = Vec::new();
+    let mut all_selected = true;
+    let mut offset: i64 = 0;
+    for hunk_index in 0..patch.num_hunks() {
+        let (hunk, line_count) = patch.hunk(hunk_index).map_err(|e| e.to_string())?;
+        let mut body = Vec::new();
+        let mut old_count: i64 = 0;
+        let mut new_count: i64 = 0;
+        let mut hunk_selected = false;
+        for line_index in 0..line_count {
+            let line = patch
+                .line_in_hunk(hunk_index, line_index)
+                .map_err(|e| e.to_string())?;
+            match line.origin() {
+                ' ' => {
+                    push_patch_line(&mut body, b' ', line.content());
+                    old_count += 1;
+                    new_count += 1;
+                }
+                '+' if is_selected(&line) => {
+                    push_patch_line(&mut body, b'+', line.content());
+                    new_count += 1;
+                    hunk_selected = true;
+                }
+                '+' => all_selected = false,
+                '-' if is_selected(&line) => {
+                    push_patch_line(&mut body, b'-', line.content());
+                    old_count += 1;
+                    hunk_selected = true;
+                }
+                '-' => {
+                    push_patch_line(&mut body, b' ', line.content());
+                    old_count += 1;
+                    new_count += 1;
+                    all_selected = false;
+                }
+                // End-of-file newline markers are re-emitted by `push_patch_line`.
+                _ => {}
+            }
+        }
+        if !hunk_selected {
+            continue;
+        }
+        let old_start = i64::from(hunk.old_start());
+        let old_before = if old_count == 0 {
+            old_start
+        } else {
+            old_start - 1
+        };
+        let new_before = old_before + offset;
+        let new_start = if new_count == 0 {
+            new_before
+        } else {
+            new_before + 1
+        };
+        offset += new_count - old_count;
+        hunks.extend_from_slice(
+            format!("@@ -{old_start},{old_count} +{new_start},{new_count} @@\n").as_bytes(),
+        );
+        hunks.extend_from_slice(&body);
+    }
+    if hunks.is_empty() {
+        return Ok(None);
+    }
+
+    let delta = patch.delta();
+    let path_of = |file: git2::DiffFile<'_>| {
+        file.path()
+            .map(|path| normalize_git_path(&path.to_string_lossy()))
+    };
+    let old_path = path_of(delta.old_file());
+    let new_path = path_of(delta.new_file());
+    let Some(old_path) = old_path.clone().or_else(|| new_path.clone()) else {
+        return Err("diff has no path".to_string());
+    };
+    let new_path = new_path.unwrap_or_else(|| old_path.clone());
+    let mut out = format!("diff --git a/{old_path} b/{new_path}\n").into_bytes();
+    match delta.status() {
+        git2::Delta::Added | git2::Delta::Untracked => {
+            out.extend_from_slice(
+                format!(
+                    "new file mode {}\n--- /dev/null\n+++ b/{new_path}\n",
+                    patch_file_mode(delta.new_file().mode())
+                )
+                .as_bytes(),
+            );
+        }
+        // A partial deletion leaves the file in place.
+        git2::Delta::Deleted if all_selected => {
+            out.extend_from_slice(
+                format!(
+                    "deleted file mode {}\n--- a/{old_path}\n+++ /dev/null\n",
+                    patch_file_mode(delta.old_file().mode())
+                )
+                .as_bytes(),
+            );
+        }
+        _ => {
+            out.extend_from_slice(format!("--- a/{old_path}\n+++ b/{new_path}\n").as_bytes());
+        }
+    }
+    out.extend_from_slice(&hunks);
+    Ok(Some(out))
+}
+
+/// Line numbers of one file's index version mapped to another version of
+/// it, for the lines a diff from the index leaves unchanged.
+struct IndexLineMap {
+    /// `(old_start, old_count, new_count, unchanged lines)` per hunk.
+    hunks: Vec<(u32, u32, u32, HashMap<u32, u32>)>,
+}
+
+impl IndexLineMap {
+    /// Builds the map from a diff whose old side is the index.
+    fn from_diff(diff: &git2::Diff<'_>) -> Result<Self, String> {
+        let mut hunks = Vec::new();
+        for index in 0..diff.deltas().len() {
+            let Some(patch) = git2::Patch::from_diff(diff, index).map_err(|e| e.to_string())?
+            else {
+                continue;
+            };
+            for hunk_index in 0..patch.num_hunks() {
+                let (hunk, line_count) = patch.hunk(hunk_index).map_err(|e| e.to_string())?;
+                let mut unchanged = HashMap::new();
+                for line_index in 0..line_count {
+                    let line = patch
+                        .line_in_hunk(hunk_index, line_index)
+                        .map_err(|e| e.to_string())?;
+                    if let (' ', Some(old), Some(new)) =
+                        (line.origin(), line.old_lineno(), line.new_lineno())
+                    {
+                        unchanged.insert(old, new);
+                    }
+                }
+                hunks.push((
+                    hunk.old_start(),
+                    hunk.old_lines(),
+                    hunk.new_lines(),
+                    unchanged,
+                ));
+            }
+        }
+        Ok(Self { hunks })
+    }
+
+    /// Where index line `line` sits on the other side, or `None` when the
+    /// diff changes it.
+    fn map(&self, line: u32) -> Option<u32> {
+        let mut shift: i64 = 0;
+        for (old_start, old_count, new_count, unchanged) in &self.hunks {
+            // A pure insertion (`-N,0`) goes after line N, so N keeps the
+            // shift of the hunks before it.
+            if line < *old_start || (*old_count == 0 && line == *old_start) {
+                break;
+            }
+            if line < old_start + old_count {
+                return unchanged.get(&line).copied();
+            }
+            shift += i64::from(*new_count) - i64::from(*old_count);
+        }
+        u32::try_from(i64::from(line) + shift).ok()
+    }
+}
+
+/// Applies the selected part of `path`'s changes: staging copies working-tree
+/// changes into the index, unstaging takes index changes back out, reverting
+/// discards unstaged working-tree changes.
+///
+/// The selection uses the HEAD→working-tree line numbers the diff view shows,
+/// while the patches run between the index and HEAD or the working tree.
+/// Lines on the index side are translated through the unchanged lines of the
+/// index's diffs against HEAD and the working tree.
+fn apply_hunk_selection(
+    repo_root: &Path,
+    path: &str,
+    selection: &GitHunkSelection,
+    action: HunkAction,
+) -> Result<(), String> {
+    let ranges = selection_ranges(selection)?;
+    let repo = Repository::open(repo_root).map_err(|e| e.to_string())?;
+    let head_tree = repo.head().ok().and_then(|head| head.peel_to_tree().ok());
+    let path_options = |reverse: bool| {
+        let mut options = DiffOptions::new();
+        options
+            .pathspec(path)
+            .disable_pathspec_match(true)
+            .reverse(reverse);
+        options
+    };
+
+    let index_to_head = repo
+        .diff_tree_to_index(head_tree.as_ref(), None, Some(&mut path_options(true)))
+        .map_err(|e| e.to_string())
+        .and_then(|diff| IndexLineMap::from_diff(&diff))?;
+    let index_to_workdir = repo
+        .diff_index_to_workdir(None, Some(&mut path_options(false)))
+        .map_err(|e| e.to_string())
+        .and_then(|diff| IndexLineMap::from_diff(&diff))?;
+
+    let mut options = path_options(action != HunkAction::Stage);
+    let (diff, location) = match action {
+        HunkAction::Stage => {
+            options.include_untracked(true).show_untracked_content(true);
+            let diff = repo
+                .diff_index_to_workdir(None, Some(&mut options))
+                .map_err(|e| e.to_string())?;
+            (diff, ApplyLocation::Index)
+        }
+        HunkAction::Unstage => {
+            let diff = repo
+                .diff_tree_to_index(head_tree.as_ref(), None, Some(&mut options))
+                .map_err(|e| e.to_string())?;
+            (diff, ApplyLocation::Index)
+        }
+        HunkAction::Revert => {
+            let diff = repo
+                .diff_index_to_workdir(None, Some(&mut options))
+                .map_err(|e| e.to_string())?;
+            (diff, ApplyLocation::WorkDir)
+        }
+    };
+
+    let in_ranges = |side: GitDiffSide, line: Option<u32>| {
+        line.is_some_and(|line| {
+            ranges
+                .iter()
+                .any(|range| range.side == side && range.start <= line && line <= range.end)
+        })
+    };
+    // Old-side selections number HEAD lines, new-side ones working-tree lines.
+    // Unstage and revert patches are reversed, so their additions come from
+    // HEAD or the index and their deletions from the index or working tree.
+    let is_selected = |line: &git2::DiffLine<'_>| match (action, line.origin()) {
+        (HunkAction::Stage, '+') => in_ranges(GitDiffSide::New, line.new_lineno()),
+        (HunkAction::Stage, '-') => in_ranges(
+            GitDiffSide::Old,
+            line.old_lineno().and_then(|line| index_to_head.map(line)),
+        ),
+        (HunkAction::Unstage, '+') => in_ranges(GitDiffSide::Old, line.new_lineno()),
+        (HunkAction::Unstage, '-') => in_ranges(
+            GitDiffSide::New,
+            line.old_lineno()
+                .and_then(|line| index_to_workdir.map(line)),
+        ),
+        (HunkAction::Revert, '+') => in_ranges(
+            GitDiffSide::Old,
+            line.new_lineno().and_then(|line| index_to_head.map(line)),
+        ),
+        (HunkAction::Revert, '-') => in_ranges(GitDiffSide::New, line.old_lineno()),
+        _ => false,
+    };
+
+    let mut patch_text = Vec::new();
+    for index in 0..diff.deltas().len() {
+        let Some(patch) = git2::Patch::from_diff(&diff, index).map_err(|e| e.to_string())? else {
+            continue;
+        };
+        if patch.delta().flags().is_binary() {
+            return Err(format!("Cannot apply part of binary file {path}."));
+        }
+        if let Some(partial) = build_partial_patch(&patch, is_selected)? {
+            patch_text.extend_from_slice(&partial);
+        }
+    }
+    if patch_text.is_empty() {
+        return Err(format!(
+            "The selection does not match any change in {path}."
+        ));
+    }
+    let partial = git2::Diff::from_buffer(&patch_text).map_err(|e| e.to_string())?;
+    repo.apply(&partial, location, None)
+        .map_err(|e| e.to_string())
+}
+
+async fn apply_hunk_selection_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitHunkSelection,
+    action: HunkAction,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    tokio::task::spawn_blocking(move || {
+        apply_hunk_selection(&repo_root, &path, &selection, action)
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    invalidate_cached_git_status(&workspace_id);
+    Ok(())
+}
+
 async fn commit_git_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -801,9 +1247,9 @@ async fn pull_git_inner(
 ) -> Result<(), String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
-    pull_with_default_strategy(&repo_root).await?;
+    let result = pull_with_default_strategy(&repo_root).await;
     invalidate_cached_git_status(&workspace_id);
-    Ok(())
+    result.map_err(|err| with_conflict_summary(&repo_root, err))
 }
 
 async fn fetch_git_inner(
@@ -823,7 +1269,11 @@ async fn sync_git_inner(
 ) -> Result<(), String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
-    pull_with_default_strategy(&repo_root).await?;
+    let pulled = pull_with_default_strategy(&repo_root).await;
+    if let Err(err) = pulled {
+        invalidate_cached_git_status(&workspace_id);
+        return Err(with_conflict_summary(&repo_root, err));
+    }
     push_with_upstream(&repo_root).await?;
     invalidate_cached_git_status(&workspace_id);
     Ok(())
@@ -984,35 +1434,260 @@ async fn get_git_diffs_inner(
     .map_err(|e| e.to_string())?
 }
 
+struct GitLogQuery {
+    author: Option<String>,
+    grep: Option<String>,
+    path: Option<PathBuf>,
+    since: Option<i64>,
+    until: Option<i64>,
+}
+
+impl GitLogQuery {
+    fn new(filter: &GitLogFilter) -> Self {
+        let lowercase = |value: &Option<String>| {
+            value
+                .as_deref()
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(str::to_lowercase)
+        };
+        let path = filter
+            .path
+            .as_deref()
+            .map(|path| normalize_git_path(path.trim()))
+            .map(|path| path.trim_start_matches("./").trim_matches('/').to_string())
+            .filter(|path| !path.is_empty())
+            .map(PathBuf::from);
+        Self {
+            author: lowercase(&filter.author),
+            grep: lowercase(&filter.grep),
+            path,
+            since: filter.since,
+            until: filter.until,
+        }
+    }
+
+    /// Graph lanes only line up when every walked commit is returned.
+    fn is_filtered(&self) -> bool {
+        self.author.is_some()
+            || self.grep.is_some()
+            || self.path.is_some()
+            || self.since.is_some()
+            || self.until.is_some()
+    }
+
+    fn matches(&self, commit: &Commit) -> Result<bool, git2::Error> {
+        let time = commit.time().seconds();
+        if self.until.is_some_and(|until| time > until) {
+            return Ok(false);
+        }
+        if self.since.is_some_and(|since| time < since) {
+            return Ok(false);
+        }
+        if let Some(author) = &self.author {
+            let signature = commit.author();
+            let name = signature.name().unwrap_or("").to_lowercase();
+            let email = signature.email().unwrap_or("").to_lowercase();
+            if !name.contains(author.as_str()) && !email.contains(author.as_str()) {
+                return Ok(false);
+            }
+        }
+        if let Some(grep) = &self.grep {
+            let message = commit.message().unwrap_or("").to_lowercase();
+            if !message.contains(grep.as_str()) {
+                return Ok(false);
+            }
+        }
+        match &self.path {
+            Some(path) => commit_touches_path(commit, path),
+            None => Ok(true),
+        }
+    }
+}
+
+/// Like `git log -- <path>`: a commit touches `path` unless the entry is
+/// unchanged against one of its parents.
+fn commit_touches_path(commit: &Commit, path: &Path) -> Result<bool, git2::Error> {
+    let entry_id = commit.tree()?.get_path(path).ok().map(|entry| entry.id());
+    if commit.parent_count() == 0 {
+        return Ok(entry_id.is_some());
+    }
+    for parent in commit.parents() {
+        let parent_id = parent.tree()?.get_path(path).ok().map(|entry| entry.id());
+        if parent_id == entry_id {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+/// The cursor is the walk frontier: the commit each graph lane expects next.
+fn encode_log_cursor(lanes: &[Option<Oid>]) -> Option<String> {
+    if lanes.iter().all(Option::is_none) {
+        return None;
+    }
+    let raw = lanes
+        .iter()
+        .map(|lane| lane.map(|oid| oid.to_string()).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join(",");
+    Some(STANDARD.encode(raw))
+}
+
+fn decode_log_cursor(cursor: &str) -> Result<Vec<Option<Oid>>, String> {
+    let invalid = || "invalid log cursor".to_string();
+    let raw = STANDARD.decode(cursor.trim()).map_err(|_| invalid())?;
+    let raw = String::from_utf8(raw).map_err(|_| invalid())?;
+    raw.split(',')
+        .map(|lane| {
+            if lane.is_empty() {
+                Ok(None)
+            } else {
+                Oid::from_str(lane).map(Some).map_err(|_| invalid())
+            }
+        })
+        .collect()
+}
+
+/// Walks history newest-first from `lanes`, assigning each commit a graph
+/// column. Stops after `limit` matches or `GIT_LOG_SCAN_BUDGET` commits, and
+/// returns the remaining frontier.
+fn walk_git_log(
+    repo: &Repository,
+    mut lanes: Vec<Option<Oid>>,
+    query: &GitLogQuery,
+    limit: usize,
+) -> Result<(Vec<GitLogEntry>, Vec<Option<Oid>>), String> {
+    let mut heads: HashMap<Oid, Commit> = HashMap::new();
+    let mut entries = Vec::new();
+    let mut scanned = 0usize;
+
+    while entries.len() < limit && scanned < GIT_LOG_SCAN_BUDGET {
+        let mut next: Option<(usize, Oid, i64)> = None;
+        for (index, oid) in lanes.iter().enumerate() {
+            let Some(oid) = *oid else {
+                continue;
+            };
+            let commit = match heads.entry(oid) {
+                Entry::Occupied(entry) => entry.into_mut(),
+                Entry::Vacant(entry) => {
+                    entry.insert(repo.find_commit(oid).map_err(|e| e.to_string())?)
+                }
+            };
+            let time = commit.time().seconds();
+            if next.is_none_or(|(_, _, newest)| time > newest) {
+                next = Some((index, oid, time));
+            }
+        }
+        let Some((_, oid, time)) = next else {
+            break;
+        };
+        if query.since.is_some_and(|since| time < since) {
+            lanes.clear();
+            break;
+        }
+        let Some(commit) = heads.remove(&oid) else {
+            break;
+        };
+        scanned += 1;
+
+        let incoming_lanes: Vec<usize> = lanes
+            .iter()
+            .enumerate()
+            .filter_map(|(index, lane)| lane.map(|_| index))
+            .collect();
+        let mut expecting = incoming_lanes
+            .iter()
+            .copied()
+            .filter(|index| lanes[*index] == Some(oid));
+        let Some(lane) = expecting.next() else {
+            break;
+        };
+        let merging_lanes: Vec<usize> = expecting.collect();
+        for index in &merging_lanes {
+            lanes[*index] = None;
+        }
+
+        let parents: Vec<Oid> = commit.parent_ids().collect();
+        lanes[lane] = None;
+        let mut parent_lanes = Vec::with_capacity(parents.len());
+        for (position, parent) in parents.iter().enumerate() {
+            if let Some(existing) = lanes.iter().position(|lane| *lane == Some(*parent)) {
+                parent_lanes.push(existing);
+                continue;
+            }
+            let slot = if position == 0 {
+                lane
+            } else if let Some(free) = lanes.iter().position(Option::is_none) {
+                free
+            } else {
+                lanes.push(None);
+                lanes.len() - 1
+            };
+            lanes[slot] = Some(*parent);
+            parent_lanes.push(slot);
+        }
+        while lanes.last().is_some_and(Option::is_none) {
+            lanes.pop();
+        }
+
+        if query.matches(&commit).map_err(|e| e.to_string())? {
+            let mut entry = commit_to_entry(commit);
+            if !query.is_filtered() {
+                entry.graph = Some(GitLogGraphRow {
+                    lane,
+                    incoming_lanes,
+                    merging_lanes,
+                    parent_lanes,
+                });
+            }
+            entries.push(entry);
+        }
+    }
+
+    Ok((entries, lanes))
+}
+
+fn git_log_page_size(limit: Option<usize>) -> usize {
+    limit
+        .unwrap_or(GIT_LOG_DEFAULT_PAGE_SIZE)
+        .clamp(1, GIT_LOG_MAX_PAGE_SIZE)
+}
+
 async fn get_git_log_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     limit: Option<usize>,
+    cursor: Option<String>,
+    filter: Option<GitLogFilter>,
 ) -> Result<GitLogResponse, String> {
     let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
     let repo_root = resolve_git_root(&entry)?;
     let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    let max_items = limit.unwrap_or(40);
-    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
-    revwalk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
-
-    let mut total = 0usize;
-    for oid_result in revwalk {
-        oid_result.map_err(|e| e.to_string())?;
-        total += 1;
-    }
-
-    let mut revwalk = repo.revwalk().map_err(|e| e.to_string())?;
-    revwalk.push_head().map_err(|e| e.to_string())?;
-    revwalk.set_sorting(Sort::TIME).map_err(|e| e.to_string())?;
-
-    let mut entries = Vec::new();
-    for oid_result in revwalk.take(max_items) {
-        let oid = oid_result.map_err(|e| e.to_string())?;
-        let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
-        entries.push(commit_to_entry(commit));
-    }
+    let max_items = git_log_page_size(limit);
+    let filter = filter.unwrap_or_default();
+
+    let lanes = match cursor.as_deref().filter(|cursor| !cursor.trim().is_empty()) {
+        Some(cursor) => decode_log_cursor(cursor)?,
+        None => {
+            let tip = match filter.rev.as_deref().map(str::trim) {
+                Some(rev) if !rev.is_empty() => repo
+                    .revparse_single(rev)
+                    .and_then(|object| object.peel_to_commit())
+                    .map_err(|e| e.to_string())?
+                    .id(),
+                _ => repo
+                    .head()
+                    .and_then(|head| head.peel_to_commit())
+                    .map_err(|e| e.to_string())?
+                    .id(),
+            };
+            vec![Some(tip)]
+        }
+    };
+    let (entries, lanes) = walk_git_log(&repo, lanes, &GitLogQuery::new(&filter), max_items)?;
+    let total = entries.len();
+    let next_cursor = encode_log_cursor(&lanes);
 
     let mut ahead = 0usize;
     let mut behind = 0usize;
@@ -1073,33 +1748,22 @@ async fn get_git_log_inner(
         ahead_entries,
         behind_entries,
         upstream,
+        next_cursor,
     })
 }
 
-async fn get_git_commit_diff_inner(
-    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
-    app_settings: &Mutex<AppSettings>,
-    workspace_id: String,
-    sha: String,
-) -> Result<Vec<GitCommitDiff>, String> {
-    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
-
-    let ignore_whitespace_changes = {
-        let settings = app_settings.lock().await;
-        settings.git_diff_ignore_whitespace_changes
-    };
-
-    let repo_root = resolve_git_root(&entry)?;
-    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
-    let oid = git2::Oid::from_str(&sha).map_err(|e| e.to_string())?;
-    let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
-    let commit_tree = commit.tree().map_err(|e| e.to_string())?;
-    let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
-
+/// Per-file diffs between two trees, with both sides' full contents for the
+/// side-by-side view. A missing `old_tree` diffs against the empty tree.
+fn tree_file_diffs(
+    repo: &Repository,
+    old_tree: Option<&git2::Tree>,
+    new_tree: Option<&git2::Tree>,
+    ignore_whitespace_changes: bool,
+) -> Result<Vec<(git2::Delta, GitFileDiff)>, String> {
     let mut options = DiffOptions::new();
     options.ignore_whitespace_change(ignore_whitespace_changes);
     let diff = repo
-        .diff_tree_to_tree(parent_tree.as_ref(), Some(&commit_tree), Some(&mut options))
+        .diff_tree_to_tree(old_tree, new_tree, Some(&mut options))
         .map_err(|e| e.to_string())?;
 
     let mut results = Vec::new();
@@ -1121,8 +1785,7 @@ async fn get_git_commit_diff_inner(
         let is_added = delta.status() == git2::Delta::Added;
 
         let old_lines = if !is_added {
-            parent_tree
-                .as_ref()
+            old_tree
                 .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
                 .and_then(|entry| repo.find_blob(entry.id()).ok())
                 .and_then(blob_to_lines)
@@ -1131,8 +1794,8 @@ async fn get_git_commit_diff_inner(
         };
 
         let new_lines = if !is_deleted {
-            new_path
-                .and_then(|path| commit_tree.get_path(path).ok())
+            new_tree
+                .and_then(|tree| new_path.and_then(|path| tree.get_path(path).ok()))
                 .and_then(|entry| repo.find_blob(entry.id()).ok())
                 .and_then(blob_to_lines)
         } else {
@@ -1141,8 +1804,7 @@ async fn get_git_commit_diff_inner(
 
         if is_image {
             let old_image_data = if !is_added && old_image_mime.is_some() {
-                parent_tree
-                    .as_ref()
+                old_tree
                     .and_then(|tree| old_path.and_then(|path| tree.get_path(path).ok()))
                     .and_then(|entry| repo.find_blob(entry.id()).ok())
                     .and_then(blob_to_base64)
@@ -1151,30 +1813,32 @@ async fn get_git_commit_diff_inner(
             };
 
             let new_image_data = if !is_deleted && new_image_mime.is_some() {
-                new_path
-                    .and_then(|path| commit_tree.get_path(path).ok())
+                new_tree
+                    .and_then(|tree| new_path.and_then(|path| tree.get_path(path).ok()))
                     .and_then(|entry| repo.find_blob(entry.id()).ok())
                     .and_then(blob_to_base64)
             } else {
                 None
             };
 
-            results.push(GitCommitDiff {
-                path: normalized_path,
-                status: status_for_delta(delta.status()).to_string(),
-                diff: String::new(),
-                old_lines: None,
-                new_lines: None,
-                is_binary: true,
-                is_image: true,
-                old_image_data,
-                new_image_data,
-                old_image_mime: old_image_mime.map(str::to_string),
-                new_image_mime: new_image_mime.map(str::to_string),
-            });
-            continue;
-        }
-
+            results.push((
+                delta.status(),
+                GitFileDiff {
+                    path: normalized_path,
+                    diff: String::new(),
+                    old_lines: None,
+                    new_lines: None,
+                    is_binary: true,
+                    is_image: true,
+                    old_image_data,
+                    new_image_data,
+                    old_image_mime: old_image_mime.map(str::to_string),
+                    new_image_mime: new_image_mime.map(str::to_string),
+                },
+            ));
+            continue;
+        }
+
         let patch = match git2::Patch::from_diff(&diff, index) {
             Ok(patch) => patch,
             Err(_) => continue,
@@ -1189,24 +1853,70 @@ async fn get_git_commit_diff_inner(
         if content.trim().is_empty() {
             continue;
         }
-        results.push(GitCommitDiff {
-            path: normalized_path,
-            status: status_for_delta(delta.status()).to_string(),
-            diff: content,
-            old_lines,
-            new_lines,
-            is_binary: false,
-            is_image: false,
-            old_image_data: None,
-            new_image_data: None,
-            old_image_mime: None,
-            new_image_mime: None,
-        });
+        results.push((
+            delta.status(),
+            GitFileDiff {
+                path: normalized_path,
+                diff: content,
+                old_lines,
+                new_lines,
+                is_binary: false,
+                is_image: false,
+                old_image_data: None,
+                new_image_data: None,
+                old_image_mime: None,
+                new_image_mime: None,
+            },
+        ));
     }
 
     Ok(results)
 }
 
+async fn get_git_commit_diff_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    sha: String,
+) -> Result<Vec<GitCommitDiff>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+
+    let ignore_whitespace_changes = {
+        let settings = app_settings.lock().await;
+        settings.git_diff_ignore_whitespace_changes
+    };
+
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    let oid = git2::Oid::from_str(&sha).map_err(|e| e.to_string())?;
+    let commit = repo.find_commit(oid).map_err(|e| e.to_string())?;
+    let commit_tree = commit.tree().map_err(|e| e.to_string())?;
+    let parent_tree = commit.parent(0).ok().and_then(|parent| parent.tree().ok());
+
+    let diffs = tree_file_diffs(
+        &repo,
+        parent_tree.as_ref(),
+        Some(&commit_tree),
+        ignore_whitespace_changes,
+    )?;
+    Ok(diffs
+        .into_iter()
+        .map(|(status, diff)| GitCommitDiff {
+            path: diff.path,
+            status: status_for_delta(status).to_string(),
+            diff: diff.diff,
+            old_lines: diff.old_lines,
+            new_lines: diff.new_lines,
+            is_binary: diff.is_binary,
+            is_image: diff.is_image,
+            old_image_data: diff.old_image_data,
+            new_image_data: diff.new_image_data,
+            old_image_mime: diff.old_image_mime,
+            new_image_mime: diff.new_image_mime,
+        })
+        .collect())
+}
+
 async fn get_git_remote_inner(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -1498,6 +2208,385 @@ async fn create_git_branch_inner(
     Ok(())
 }
 
+fn list_stash_entries(repo: &mut Repository) -> Result<Vec<GitStashEntry>, String> {
+    let mut stashes = Vec::new();
+    repo.stash_foreach(|index, message, oid| {
+        stashes.push((index, message.to_string(), *oid));
+        true
+    })
+    .map_err(|e| e.to_string())?;
+    Ok(stashes
+        .into_iter()
+        .map(|(index, message, oid)| GitStashEntry {
+            index,
+            sha: oid.to_string(),
+            message,
+            timestamp: repo
+                .find_commit(oid)
+                .map(|commit| commit.time().seconds())
+                .unwrap_or(0),
+        })
+        .collect())
+}
+
+/// Resolves a stash commit to its current `stash@{n}` name, which is what
+/// `git stash pop` and `git stash drop` require.
+fn stash_ref_for_sha(repo: &mut Repository, sha: &str) -> Result<String, String> {
+    let oid = Oid::from_str(sha.trim()).map_err(|e| e.to_string())?;
+    list_stash_entries(repo)?
+        .into_iter()
+        .find(|stash| stash.sha == oid.to_string())
+        .map(|stash| format!("stash@{{{}}}", stash.index))
+        .ok_or_else(|| format!("Stash {sha} not found."))
+}
+
+/// Changes a stash records against the commit it was made on: tracked
+/// changes from the stash's own tree plus untracked files from its third
+/// parent, when the stash includes them.
+fn stash_file_diffs(
+    repo: &Repository,
+    stash: &Commit,
+    ignore_whitespace_changes: bool,
+) -> Result<Vec<GitFileDiff>, String> {
+    let base_tree = stash
+        .parent(0)
+        .and_then(|base| base.tree())
+        .map_err(|e| e.to_string())?;
+    let stash_tree = stash.tree().map_err(|e| e.to_string())?;
+    let mut diffs = tree_file_diffs(
+        repo,
+        Some(&base_tree),
+        Some(&stash_tree),
+        ignore_whitespace_changes,
+    )?;
+    if let Ok(untracked) = stash.parent(2) {
+        let untracked_tree = untracked.tree().map_err(|e| e.to_string())?;
+        diffs.extend(tree_file_diffs(
+            repo,
+            None,
+            Some(&untracked_tree),
+            ignore_whitespace_changes,
+        )?);
+    }
+    Ok(diffs.into_iter().map(|(_, diff)| diff).collect())
+}
+
+async fn list_git_stashes_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitStashEntry>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let mut repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    list_stash_entries(&mut repo)
+}
+
+async fn create_git_stash_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: bool,
+) -> Result<GitStashEntry, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let stash_tip = |repo_root: &Path| {
+        Repository::open(repo_root)
+            .ok()
+            .and_then(|repo| repo.refname_to_id("refs/stash").ok())
+    };
+    let previous_tip = stash_tip(&repo_root);
+
+    let mut args = vec!["stash", "push"];
+    if include_untracked {
+        args.push("--include-untracked");
+    }
+    let message = message.as_deref().map(str::trim).unwrap_or("");
+    if !message.is_empty() {
+        args.extend(["-m", message]);
+    }
+    run_git_command(&repo_root, &args).await?;
+    invalidate_cached_git_status(&workspace_id);
+
+    // `git stash push` succeeds without stashing anything on a clean tree.
+    if stash_tip(&repo_root) == previous_tip {
+        return Err("No local changes to stash.".to_string());
+    }
+    let mut repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    list_stash_entries(&mut repo)?
+        .into_iter()
+        .next()
+        .ok_or_else(|| "Stash was not created.".to_string())
+}
+
+async fn run_git_stash_command_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    command: &str,
+    sha: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let stash_ref = {
+        let mut repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        stash_ref_for_sha(&mut repo, &sha)?
+    };
+    let result = run_git_command(&repo_root, &["stash", command, &stash_ref]).await;
+    // A conflicted apply still changes the working tree.
+    invalidate_cached_git_status(&workspace_id);
+    result.map_err(|err| with_conflict_summary(&repo_root, err))
+}
+
+async fn get_git_stash_diff_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    sha: String,
+) -> Result<Vec<GitFileDiff>, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let ignore_whitespace_changes = {
+        let settings = app_settings.lock().await;
+        settings.git_diff_ignore_whitespace_changes
+    };
+    let repo_root = resolve_git_root(&entry)?;
+    let mut repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    stash_ref_for_sha(&mut repo, &sha)?;
+    let oid = Oid::from_str(sha.trim()).map_err(|e| e.to_string())?;
+    let stash = repo.find_commit(oid).map_err(|e| e.to_string())?;
+    stash_file_diffs(&repo, &stash, ignore_whitespace_changes)
+}
+
+const MAX_LISTED_CONFLICTS: usize = 5;
+
+fn conflict_operation(state: RepositoryState) -> Option<&'static str> {
+    match state {
+        RepositoryState::Merge => Some("merge"),
+        RepositoryState::Rebase
+        | RepositoryState::RebaseInteractive
+        | RepositoryState::RebaseMerge => Some("rebase"),
+        RepositoryState::CherryPick | RepositoryState::CherryPickSequence => Some("cherry-pick"),
+        RepositoryState::Revert | RepositoryState::RevertSequence => Some("revert"),
+        RepositoryState::ApplyMailbox | RepositoryState::ApplyMailboxOrRebase => Some("am"),
+        RepositoryState::Clean | RepositoryState::Bisect => None,
+    }
+}
+
+fn conflict_text(bytes: &[u8]) -> Option<String> {
+    if bytes.len() > MAX_TEXT_DIFF_BYTES || bytes_look_binary(bytes) {
+        return None;
+    }
+    String::from_utf8(bytes.to_vec()).ok()
+}
+
+fn conflict_entry_path(entry: &IndexEntry) -> String {
+    normalize_git_path(&String::from_utf8_lossy(&entry.path))
+}
+
+fn conflicted_paths(repo: &Repository) -> Result<Vec<String>, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let mut paths = Vec::new();
+    for conflict in index.conflicts().map_err(|e| e.to_string())? {
+        let conflict = conflict.map_err(|e| e.to_string())?;
+        if let Some(entry) = conflict
+            .our
+            .as_ref()
+            .or(conflict.their.as_ref())
+            .or(conflict.ancestor.as_ref())
+        {
+            paths.push(conflict_entry_path(entry));
+        }
+    }
+    Ok(paths)
+}
+
+/// Reads every conflicted path from the index: stage 1 (base), 2 (ours) and
+/// 3 (theirs), plus the working tree file git left behind.
+fn read_git_conflicts(repo: &Repository, repo_root: &Path) -> Result<GitConflictStatus, String> {
+    let index = repo.index().map_err(|e| e.to_string())?;
+    let mut files = Vec::new();
+    for conflict in index.conflicts().map_err(|e| e.to_string())? {
+        let conflict = conflict.map_err(|e| e.to_string())?;
+        let Some(path) = conflict
+            .our
+            .as_ref()
+            .or(conflict.their.as_ref())
+            .or(conflict.ancestor.as_ref())
+            .map(conflict_entry_path)
+        else {
+            continue;
+        };
+
+        let mut is_binary = false;
+        let mut side = |entry: Option<&IndexEntry>| -> Result<Option<String>, String> {
+            let Some(entry) = entry else {
+                return Ok(None);
+            };
+            let blob = repo.find_blob(entry.id).map_err(|e| e.to_string())?;
+            let text = conflict_text(blob.content());
+            is_binary |= text.is_none();
+            Ok(text)
+        };
+        let base = side(conflict.ancestor.as_ref())?;
+        let ours = side(conflict.our.as_ref())?;
+        let theirs = side(conflict.their.as_ref())?;
+        let merged = fs::read(repo_root.join(&path))
+            .ok()
+            .and_then(|bytes| conflict_text(&bytes));
+
+        files.push(GitConflictFile {
+            path,
+            base,
+            ours,
+            theirs,
+            merged,
+            is_binary,
+        });
+    }
+    Ok(GitConflictStatus {
+        operation: conflict_operation(repo.state()).map(str::to_string),
+        files,
+    })
+}
+
+/// Appends the conflicted paths to a failed command's stderr when the
+/// command stopped on conflicts, so callers can point at the conflict API.
+pub(crate) fn with_conflict_summary(repo_root: &Path, err: String) -> String {
+    let paths = Repository::open(repo_root)
+        .ok()
+        .and_then(|repo| conflicted_paths(&repo).ok())
+        .unwrap_or_default();
+    if paths.is_empty() {
+        return err;
+    }
+    let mut listed = paths
+        .iter()
+        .take(MAX_LISTED_CONFLICTS)
+        .cloned()
+        .collect::<Vec<_>>()
+        .join(", ");
+    if paths.len() > MAX_LISTED_CONFLICTS {
+        listed.push_str(&format!(" and {} more", paths.len() - MAX_LISTED_CONFLICTS));
+    }
+    let summary = format!("Merge conflicts in {listed}. Resolve them or abort before continuing.");
+    let err = err.trim_end();
+    if err.is_empty() {
+        summary
+    } else {
+        format!("{err}\n{summary}")
+    }
+}
+
+async fn get_git_conflicts_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitConflictStatus, String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+    read_git_conflicts(&repo, &repo_root)
+}
+
+async fn resolve_git_conflict_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    resolution: GitConflictResolution,
+    content: Option<String>,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    let path = normalize_git_path(path.trim());
+    let (has_ours, has_theirs) = {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        let index = repo.index().map_err(|e| e.to_string())?;
+        let mut sides = None;
+        for conflict in index.conflicts().map_err(|e| e.to_string())? {
+            let conflict = conflict.map_err(|e| e.to_string())?;
+            let matches = [&conflict.ancestor, &conflict.our, &conflict.their]
+                .into_iter()
+                .flatten()
+                .any(|entry| conflict_entry_path(entry) == path);
+            if matches {
+                sides = Some((conflict.our.is_some(), conflict.their.is_some()));
+                break;
+            }
+        }
+        sides.ok_or_else(|| format!("{path} has no merge conflict."))?
+    };
+
+    let keep_side = match resolution {
+        GitConflictResolution::Ours => Some(("--ours", has_ours)),
+        GitConflictResolution::Theirs => Some(("--theirs", has_theirs)),
+        GitConflictResolution::Edited => {
+            let content = content.ok_or("Edited resolution requires `content`.")?;
+            let full_path = repo_root.join(&path);
+            if let Some(parent) = full_path.parent() {
+                fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+            }
+            fs::write(&full_path, content).map_err(|e| e.to_string())?;
+            None
+        }
+    };
+    match keep_side {
+        // The chosen side deleted the file, so resolve by deleting it.
+        Some((_, false)) => {
+            run_git_command(&repo_root, &["rm", "--quiet", "--", &path]).await?;
+        }
+        Some((flag, true)) => {
+            run_git_command(&repo_root, &["checkout", flag, "--", &path]).await?;
+            run_git_command(&repo_root, &["add", "--", &path]).await?;
+        }
+        None => {
+            run_git_command(&repo_root, &["add", "--", &path]).await?;
+        }
+    }
+    invalidate_cached_git_status(&workspace_id);
+    Ok(())
+}
+
+async fn abort_merge_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["merge", "--abort"]).await?;
+    invalidate_cached_git_status(&workspace_id);
+    Ok(())
+}
+
+async fn continue_merge_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    {
+        let repo = Repository::open(&repo_root).map_err(|e| e.to_string())?;
+        if repo.state() != RepositoryState::Merge {
+            return Err("No merge in progress.".to_string());
+        }
+        if !conflicted_paths(&repo)?.is_empty() {
+            return Err(with_conflict_summary(&repo_root, String::new()));
+        }
+    }
+    // Commits with the prepared MERGE_MSG, like `git merge --continue`
+    // without opening an editor.
+    run_git_command(&repo_root, &["commit", "--no-edit"]).await?;
+    invalidate_cached_git_status(&workspace_id);
+    Ok(())
+}
+
+async fn abort_rebase_inner(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    let entry = workspace_entry_for_id(workspaces, &workspace_id).await?;
+    let repo_root = resolve_git_root(&entry)?;
+    run_git_command(&repo_root, &["rebase", "--abort"]).await?;
+    invalidate_cached_git_status(&workspace_id);
+    Ok(())
+}
+
 pub(crate) async fn resolve_repo_root_for_workspace_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -1536,8 +2625,10 @@ pub(crate) async fn get_git_log_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
     limit: Option<usize>,
+    cursor: Option<String>,
+    filter: Option<GitLogFilter>,
 ) -> Result<GitLogResponse, String> {
-    get_git_log_inner(workspaces, workspace_id, limit).await
+    get_git_log_inner(workspaces, workspace_id, limit, cursor, filter).await
 }
 
 pub(crate) async fn get_git_commit_diff_core(
@@ -1594,6 +2685,47 @@ pub(crate) async fn revert_git_all_core(
     revert_git_all_inner(workspaces, workspace_id).await
 }
 
+pub(crate) async fn stage_git_hunks_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitHunkSelection,
+) -> Result<(), String> {
+    apply_hunk_selection_inner(workspaces, workspace_id, path, selection, HunkAction::Stage).await
+}
+
+pub(crate) async fn unstage_git_hunks_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitHunkSelection,
+) -> Result<(), String> {
+    apply_hunk_selection_inner(
+        workspaces,
+        workspace_id,
+        path,
+        selection,
+        HunkAction::Unstage,
+    )
+    .await
+}
+
+pub(crate) async fn revert_git_hunks_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    selection: GitHunkSelection,
+) -> Result<(), String> {
+    apply_hunk_selection_inner(
+        workspaces,
+        workspace_id,
+        path,
+        selection,
+        HunkAction::Revert,
+    )
+    .await
+}
+
 pub(crate) async fn commit_git_core(
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
     workspace_id: String,
@@ -1683,6 +2815,93 @@ pub(crate) async fn create_git_branch_core(
     create_git_branch_inner(workspaces, workspace_id, name).await
 }
 
+pub(crate) async fn list_git_stashes_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<Vec<GitStashEntry>, String> {
+    list_git_stashes_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn create_git_stash_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    message: Option<String>,
+    include_untracked: bool,
+) -> Result<GitStashEntry, String> {
+    create_git_stash_inner(workspaces, workspace_id, message, include_untracked).await
+}
+
+pub(crate) async fn apply_git_stash_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    sha: String,
+) -> Result<(), String> {
+    run_git_stash_command_inner(workspaces, workspace_id, "apply", sha).await
+}
+
+pub(crate) async fn pop_git_stash_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    sha: String,
+) -> Result<(), String> {
+    run_git_stash_command_inner(workspaces, workspace_id, "pop", sha).await
+}
+
+pub(crate) async fn drop_git_stash_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    sha: String,
+) -> Result<(), String> {
+    run_git_stash_command_inner(workspaces, workspace_id, "drop", sha).await
+}
+
+pub(crate) async fn get_git_stash_diff_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    app_settings: &Mutex<AppSettings>,
+    workspace_id: String,
+    sha: String,
+) -> Result<Vec<GitFileDiff>, String> {
+    get_git_stash_diff_inner(workspaces, app_settings, workspace_id, sha).await
+}
+
+pub(crate) async fn get_git_conflicts_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<GitConflictStatus, String> {
+    get_git_conflicts_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn resolve_git_conflict_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+    path: String,
+    resolution: GitConflictResolution,
+    content: Option<String>,
+) -> Result<(), String> {
+    resolve_git_conflict_inner(workspaces, workspace_id, path, resolution, content).await
+}
+
+pub(crate) async fn abort_merge_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    abort_merge_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn continue_merge_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    continue_merge_inner(workspaces, workspace_id).await
+}
+
+pub(crate) async fn abort_rebase_core(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    workspace_id: String,
+) -> Result<(), String> {
+    abort_rebase_inner(workspaces, workspace_id).await
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -1747,4 +2966,407 @@ mod tests {
         let paths = action_paths_for_file(&root, "b.txt");
         assert_eq!(paths, vec!["a.txt".to_string(), "b.txt".to_string()]);
     }
+    fn numbered_lines(changed: &[(usize, &str)]) -> String {
+        (1..=20)
+            .map(|line| {
+                changed
+                    .iter()
+                    .find(|(number, _)| *number == line)
+                    .map(|(_, text)| format!("{text}\n"))
+                    .unwrap_or_else(|| format!("line {line}\n"))
+            })
+            .collect()
+    }
+
+    fn index_content(repo: &Repository, path: &str) -> String {
+        let mut index = repo.index().expect("repo index");
+        index.read(true).expect("read index");
+        let entry = index.get_path(Path::new(path), 0).expect("index entry");
+        let blob = repo.find_blob(entry.id).expect("index blob");
+        String::from_utf8(blob.content().to_vec()).expect("utf8 blob")
+    }
+
+    #[test]
+    fn watched_git_status_cache_still_expires_after_the_backstop_ttl() {
+        let store_aged = |workspace_id: &str, age: Duration| {
+            git_status_cache().lock().expect("cache lock").insert(
+                workspace_id.to_string(),
+                GitStatusCacheEntry {
+                    value: json!({ "branchName": "main" }),
+                    captured_at: Instant::now() - age,
+                },
+            );
+        };
+        set_git_status_watched("ttl-watched", true);
+
+        store_aged("ttl-watched", Duration::from_secs(5));
+        assert!(load_cached_git_status("ttl-watched").is_some());
+        store_aged("ttl-unwatched", Duration::from_secs(5));
+        assert!(load_cached_git_status("ttl-unwatched").is_none());
+
+        store_aged(
+            "ttl-watched",
+            WATCHED_GIT_STATUS_CACHE_TTL + Duration::from_secs(1),
+        );
+        assert!(load_cached_git_status("ttl-watched").is_none());
+        set_git_status_watched("ttl-watched", false);
+    }
+
+    #[test]
+    fn hunk_header_ranges_cover_both_sides() {
+        let ranges = hunk_header_ranges("@@ -3,4 +3,6 @@ fn main()").expect("ranges");
+        assert_eq!(
+            ranges,
+            vec![
+                GitLineRange {
+                    side: GitDiffSide::Old,
+                    start: 3,
+                    end: 6,
+                },
+                GitLineRange {
+                    side: GitDiffSide::New,
+                    start: 3,
+                    end: 8,
+                },
+            ]
+        );
+        let added = hunk_header_ranges("@@ -0,0 +1 @@").expect("ranges");
+        assert_eq!(
+            added,
+            vec![GitLineRange {
+                side: GitDiffSide::New,
+                start: 1,
+                end: 1,
+            }]
+        );
+        assert!(hunk_header_ranges("@@ nope").is_err());
+    }
+
+    fn commit_numbered_lines(root: &Path, repo: &Repository) {
+        fs::write(root.join("lines.txt"), numbered_lines(&[])).expect("write file");
+        let mut index = repo.index().expect("repo index");
+        index.add_path(Path::new("lines.txt")).expect("add path");
+        index.write().expect("write index");
+        let tree_id = index.write_tree().expect("write tree");
+        let tree = repo.find_tree(tree_id).expect("find tree");
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .expect("commit");
+    }
+
+    #[test]
+    fn hunk_selection_stages_unstages_and_reverts_single_lines() {
+        let (root, repo) = create_temp_repo();
+        commit_numbered_lines(&root, &repo);
+
+        let edited = numbered_lines(&[(2, "line two"), (18, "line eighteen")]);
+        fs::write(root.join("lines.txt"), &edited).expect("edit file");
+        // A modified line is a deletion on the old side plus an addition on
+        // the new side; select both to move the change as a whole.
+        let line = |number| GitHunkSelection {
+            hunks: Vec::new(),
+            lines: [GitDiffSide::Old, GitDiffSide::New]
+                .into_iter()
+                .map(|side| GitLineRange {
+                    side,
+                    start: number,
+                    end: number,
+                })
+                .collect(),
+        };
+
+        apply_hunk_selection(&root, "lines.txt", &line(2), HunkAction::Stage)
+            .expect("stage line 2");
+        assert_eq!(
+            index_content(&repo, "lines.txt"),
+            numbered_lines(&[(2, "line two")])
+        );
+
+        apply_hunk_selection(&root, "lines.txt", &line(2), HunkAction::Unstage)
+            .expect("unstage line 2");
+        assert_eq!(index_content(&repo, "lines.txt"), numbered_lines(&[]));
+
+        apply_hunk_selection(&root, "lines.txt", &line(18), HunkAction::Revert)
+            .expect("revert line 18");
+        assert_eq!(
+            fs::read_to_string(root.join("lines.txt")).expect("read file"),
+            numbered_lines(&[(2, "line two")])
+        );
+
+        let err = apply_hunk_selection(&root, "lines.txt", &line(10), HunkAction::Stage)
+            .expect_err("unchanged line");
+        assert!(err.contains("does not match"));
+    }
+
+    #[test]
+    fn hunk_selection_uses_diff_view_lines_after_an_earlier_hunk_is_staged() {
+        let (root, repo) = create_temp_repo();
+        commit_numbered_lines(&root, &repo);
+        let with_extra_lines = |changed: &[(usize, &str)]| {
+            let mut lines: Vec<String> = numbered_lines(changed)
+                .lines()
+                .map(|line| format!("{line}\n"))
+                .collect();
+            lines.insert(2, "extra a\nextra b\n".to_string());
+            lines.concat()
+        };
+        fs::write(
+            root.join("lines.txt"),
+            with_extra_lines(&[(18, "line eighteen")]),
+        )
+        .expect("edit file");
+
+        // HEAD→working-tree numbering: two lines inserted after line 2, so
+        // HEAD line 18 is working-tree line 20.
+        let first_hunk = GitHunkSelection {
+            hunks: vec!["@@ -1,5 +1,7 @@".to_string()],
+            lines: Vec::new(),
+        };
+        let second_hunk = GitHunkSelection {
+            hunks: Vec::new(),
+            lines: vec![
+                GitLineRange {
+                    side: GitDiffSide::Old,
+                    start: 18,
+                    end: 18,
+                },
+                GitLineRange {
+                    side: GitDiffSide::New,
+                    start: 20,
+                    end: 20,
+                },
+            ],
+        };
+
+        apply_hunk_selection(&root, "lines.txt", &first_hunk, HunkAction::Stage)
+            .expect("stage first hunk");
+        assert_eq!(index_content(&repo, "lines.txt"), with_extra_lines(&[]));
+
+        apply_hunk_selection(&root, "lines.txt", &second_hunk, HunkAction::Stage)
+            .expect("stage second hunk");
+        assert_eq!(
+            index_content(&repo, "lines.txt"),
+            with_extra_lines(&[(18, "line eighteen")])
+        );
+
+        apply_hunk_selection(&root, "lines.txt", &second_hunk, HunkAction::Unstage)
+            .expect("unstage second hunk");
+        assert_eq!(index_content(&repo, "lines.txt"), with_extra_lines(&[]));
+
+        apply_hunk_selection(&root, "lines.txt", &second_hunk, HunkAction::Revert)
+            .expect("revert second hunk");
+        assert_eq!(
+            fs::read_to_string(root.join("lines.txt")).expect("read file"),
+            with_extra_lines(&[])
+        );
+        assert_eq!(index_content(&repo, "lines.txt"), with_extra_lines(&[]));
+    }
+
+    fn commit_tree(
+        repo: &Repository,
+        files: &[(&str, &str)],
+        parents: &[Oid],
+        author: &str,
+        time: i64,
+    ) -> Oid {
+        let mut builder = repo.treebuilder(None).expect("tree builder");
+        for (name, content) in files {
+            let blob = repo.blob(content.as_bytes()).expect("blob");
+            builder.insert(name, blob, 0o100644).expect("insert blob");
+        }
+        let tree = repo
+            .find_tree(builder.write().expect("write tree"))
+            .expect("tree");
+        let sig = git2::Signature::new(author, "test@example.com", &git2::Time::new(time, 0))
+            .expect("signature");
+        let parents: Vec<_> = parents
+            .iter()
+            .map(|oid| repo.find_commit(*oid).expect("parent"))
+            .collect();
+        let parent_refs: Vec<_> = parents.iter().collect();
+        repo.commit(None, &sig, &sig, author, &tree, &parent_refs)
+            .expect("commit")
+    }
+
+    #[test]
+    fn git_log_page_size_defaults_and_clamps() {
+        assert_eq!(git_log_page_size(None), GIT_LOG_DEFAULT_PAGE_SIZE);
+        assert_eq!(git_log_page_size(Some(0)), 1);
+        assert_eq!(git_log_page_size(Some(120)), 120);
+        assert_eq!(git_log_page_size(Some(10_000)), GIT_LOG_MAX_PAGE_SIZE);
+    }
+
+    #[test]
+    fn git_log_pages_through_a_merge_with_graph_lanes_and_filters() {
+        let (_root, repo) = create_temp_repo();
+        let base = commit_tree(&repo, &[("a.txt", "a\n")], &[], "Test", 100);
+        let main = commit_tree(&repo, &[("a.txt", "a2\n")], &[base], "Alice", 200);
+        let side = commit_tree(
+            &repo,
+            &[("a.txt", "a\n"), ("b.txt", "b\n")],
+            &[base],
+            "Test",
+            300,
+        );
+        let merge = commit_tree(
+            &repo,
+            &[("a.txt", "a2\n"), ("b.txt", "b\n")],
+            &[main, side],
+            "Test",
+            400,
+        );
+        let unfiltered = GitLogQuery::new(&GitLogFilter::default());
+        let shas = |entries: &[GitLogEntry]| -> Vec<String> {
+            entries.iter().map(|entry| entry.sha.clone()).collect()
+        };
+
+        let (first, lanes) =
+            walk_git_log(&repo, vec![Some(merge)], &unfiltered, 2).expect("first page");
+        assert_eq!(shas(&first), vec![merge.to_string(), side.to_string()]);
+        assert_eq!(first[0].parents, vec![main.to_string(), side.to_string()]);
+        assert_eq!(
+            first[0].graph,
+            Some(GitLogGraphRow {
+                lane: 0,
+                incoming_lanes: vec![0],
+                merging_lanes: vec![],
+                parent_lanes: vec![0, 1],
+            })
+        );
+        let cursor = encode_log_cursor(&lanes).expect("more history");
+        assert_eq!(
+            decode_log_cursor(&cursor).expect("cursor"),
+            vec![Some(main), Some(base)]
+        );
+
+        let (second, lanes) = walk_git_log(
+            &repo,
+            decode_log_cursor(&cursor).expect("cursor"),
+            &unfiltered,
+            2,
+        )
+        .expect("second page");
+        assert_eq!(shas(&second), vec![main.to_string(), base.to_string()]);
+        assert_eq!(
+            second[0].graph,
+            Some(GitLogGraphRow {
+                lane: 0,
+                incoming_lanes: vec![0, 1],
+                merging_lanes: vec![],
+                parent_lanes: vec![1],
+            })
+        );
+        assert_eq!(second[1].graph.as_ref().map(|row| row.lane), Some(1));
+        assert!(encode_log_cursor(&lanes).is_none());
+
+        let by_path = GitLogQuery::new(&GitLogFilter {
+            path: Some("./b.txt".to_string()),
+            ..GitLogFilter::default()
+        });
+        let (entries, _) = walk_git_log(&repo, vec![Some(merge)], &by_path, 10).expect("path");
+        assert_eq!(shas(&entries), vec![side.to_string()]);
+        assert!(entries[0].graph.is_none());
+
+        let by_author = GitLogQuery::new(&GitLogFilter {
+            author: Some("alice".to_string()),
+            ..GitLogFilter::default()
+        });
+        let (entries, _) = walk_git_log(&repo, vec![Some(merge)], &by_author, 10).expect("author");
+        assert_eq!(shas(&entries), vec![main.to_string()]);
+
+        let since = GitLogQuery::new(&GitLogFilter {
+            since: Some(250),
+            ..GitLogFilter::default()
+        });
+        let (entries, lanes) = walk_git_log(&repo, vec![Some(merge)], &since, 10).expect("since");
+        assert_eq!(shas(&entries), vec![merge.to_string(), side.to_string()]);
+        assert!(encode_log_cursor(&lanes).is_none());
+
+        assert!(decode_log_cursor("not a cursor").is_err());
+    }
+
+    #[test]
+    fn stash_diffs_include_tracked_and_untracked_changes() {
+        let (root, mut repo) = create_temp_repo();
+        fs::write(root.join("tracked.txt"), "one\n").expect("write tracked");
+        let mut index = repo.index().expect("index");
+        index.add_path(Path::new("tracked.txt")).expect("add path");
+        index.write().expect("write index");
+        let tree_id = index.write_tree().expect("write tree");
+        let tree = repo.find_tree(tree_id).expect("tree");
+        let sig = git2::Signature::now("Test", "test@example.com").expect("signature");
+        repo.commit(Some("HEAD"), &sig, &sig, "init", &tree, &[])
+            .expect("commit");
+        drop(tree);
+
+        fs::write(root.join("tracked.txt"), "two\n").expect("modify tracked");
+        fs::write(root.join("new.txt"), "new\n").expect("write untracked");
+        let stash_id = repo
+            .stash_save(
+                &sig,
+                "park agent changes",
+                Some(git2::StashFlags::INCLUDE_UNTRACKED),
+            )
+            .expect("stash");
+
+        let stashes = list_stash_entries(&mut repo).expect("list stashes");
+        assert_eq!(stashes.len(), 1);
+        assert_eq!(stashes[0].index, 0);
+        assert_eq!(stashes[0].sha, stash_id.to_string());
+        assert!(stashes[0].message.contains("park agent changes"));
+        assert_eq!(
+            stash_ref_for_sha(&mut repo, &stash_id.to_string()).expect("stash ref"),
+            "stash@{0}"
+        );
+        assert!(stash_ref_for_sha(&mut repo, &Oid::zero().to_string()).is_err());
+
+        let stash = repo.find_commit(stash_id).expect("stash commit");
+        let diffs = stash_file_diffs(&repo, &stash, false).expect("stash diff");
+        let paths: Vec<&str> = diffs.iter().map(|diff| diff.path.as_str()).collect();
+        assert_eq!(paths, vec!["tracked.txt", "new.txt"]);
+        assert!(diffs[0].diff.contains("+two"));
+        assert_eq!(diffs[1].new_lines, Some(vec!["new\n".to_string()]));
+        assert_eq!(diffs[1].old_lines, None);
+    }
+
+    #[test]
+    fn read_git_conflicts_reports_each_side_of_a_merge() {
+        let (root, repo) = create_temp_repo();
+        let base = commit_tree(&repo, &[("a.txt", "base\n")], &[], "Test", 100);
+        let theirs = commit_tree(&repo, &[("a.txt", "theirs\n")], &[base], "Test", 200);
+        let ours = commit_tree(&repo, &[("a.txt", "ours\n")], &[base], "Test", 300);
+        repo.reference("refs/heads/main", ours, true, "test")
+            .expect("main ref");
+        repo.set_head("refs/heads/main").expect("set head");
+        let mut checkout = git2::build::CheckoutBuilder::new();
+        checkout.force();
+        repo.checkout_head(Some(&mut checkout)).expect("checkout");
+
+        let clean = read_git_conflicts(&repo, &root).expect("clean status");
+        assert!(clean.files.is_empty());
+        assert_eq!(clean.operation, None);
+        assert_eq!(with_conflict_summary(&root, "stderr".to_string()), "stderr");
+
+        let incoming = repo.find_annotated_commit(theirs).expect("annotated");
+        repo.merge(&[&incoming], None, None).expect("merge");
+
+        let status = read_git_conflicts(&repo, &root).expect("conflicts");
+        assert_eq!(status.operation.as_deref(), Some("merge"));
+        assert_eq!(status.files.len(), 1);
+        let file = &status.files[0];
+        assert_eq!(file.path, "a.txt");
+        assert_eq!(file.base.as_deref(), Some("base\n"));
+        assert_eq!(file.ours.as_deref(), Some("ours\n"));
+        assert_eq!(file.theirs.as_deref(), Some("theirs\n"));
+        assert!(file.merged.as_deref().unwrap_or("").contains("<<<<<<<"));
+        assert!(!file.is_binary);
+        assert_eq!(
+            with_conflict_summary(&root, "CONFLICT (content)\n".to_string()),
+            "CONFLICT (content)\nMerge conflicts in a.txt. Resolve them or abort before continuing."
+        );
+        assert_eq!(
+            with_conflict_summary(&root, String::new()),
+            "Merge conflicts in a.txt. Resolve them or abort before continuing."
+        );
+    }
 }
@@ -93,26 +93,45 @@ fn now_millis() -> u128 {
 }
 
 fn current_log_file_path(logs_dir: &Path) -> PathBuf {
-    logs_dir.join(format!("{LOG_FILE_BASENAME}.{LOG_FILE_EXTENSION}"))
+    jsonl_file_path(logs_dir, LOG_FILE_BASENAME, None)
 }
 
-fn rotated_log_file_path(logs_dir: &Path, index: usize) -> PathBuf {
-    logs_dir.join(format!("{LOG_FILE_BASENAME}.{index}.{LOG_FILE_EXTENSION}"))
+fn rotate_log_file_if_needed(logs_dir: &Path) -> Result<(), String> {
+    rotate_jsonl_file_if_needed(
+        logs_dir,
+        LOG_FILE_BASENAME,
+        MAX_LOG_FILE_BYTES,
+        MAX_LOG_FILES_TOTAL,
+    )
 }
 
-fn rotate_log_file_if_needed(logs_dir: &Path) -> Result<(), String> {
-    let current_path = current_log_file_path(logs_dir);
+/// `<dir>/<basename>.jsonl` for the live file, `<dir>/<basename>.<index>.jsonl`
+/// for rotated ones (1 is the most recent).
+pub(crate) fn jsonl_file_path(dir: &Path, basename: &str, index: Option<usize>) -> PathBuf {
+    match index {
+        Some(index) => dir.join(format!("{basename}.{index}.{LOG_FILE_EXTENSION}")),
+        None => dir.join(format!("{basename}.{LOG_FILE_EXTENSION}")),
+    }
+}
+
+pub(crate) fn rotate_jsonl_file_if_needed(
+    dir: &Path,
+    basename: &str,
+    max_file_bytes: u64,
+    max_files_total: usize,
+) -> Result<(), String> {
+    let current_path = jsonl_file_path(dir, basename, None);
     let current_size = match fs::metadata(&current_path) {
         Ok(metadata) => metadata.len(),
         Err(_) => return Ok(()),
     };
-    if current_size < MAX_LOG_FILE_BYTES {
+    if current_size < max_file_bytes {
         return Ok(());
     }
 
-    let max_rotated_files = MAX_LOG_FILES_TOTAL.saturating_sub(1);
+    let max_rotated_files = max_files_total.saturating_sub(1);
     for index in (1..=max_rotated_files).rev() {
-        let source_path = rotated_log_file_path(logs_dir, index);
+        let source_path = jsonl_file_path(dir, basename, Some(index));
         if !source_path.exists() {
             continue;
         }
@@ -121,12 +140,17 @@ fn rotate_log_file_if_needed(logs_dir: &Path) -> Result<(), String> {
                 .map_err(|err| format!("failed to remove old rotated log file: {err}"))?;
             continue;
         }
-        let destination_path = rotated_log_file_path(logs_dir, index + 1);
+        let destination_path = jsonl_file_path(dir, basename, Some(index + 1));
         fs::rename(&source_path, &destination_path)
             .map_err(|err| format!("failed to rotate structured log file: {err}"))?;
     }
 
-    fs::rename(&current_path, rotated_log_file_path(logs_dir, 1))
+    if max_rotated_files == 0 {
+        fs::remove_file(&current_path)
+            .map_err(|err| format!("failed to truncate structured log file: {err}"))?;
+        return Ok(());
+    }
+    fs::rename(&current_path, jsonl_file_path(dir, basename, Some(1)))
         .map_err(|err| format!("failed to rotate current structured log file: {err}"))?;
     Ok(())
 }
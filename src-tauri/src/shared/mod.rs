@@ -4,15 +4,22 @@ pub(crate) mod codex_aux_core;
 pub(crate) mod codex_core;
 pub(crate) mod codex_update_core;
 pub(crate) mod config_toml_core;
+pub(crate) mod event_journal_core;
 pub(crate) mod files_core;
 pub(crate) mod git_core;
+pub(crate) mod git_status_watch_core;
 pub(crate) mod git_ui_core;
 pub(crate) mod local_usage_core;
 #[allow(dead_code)]
 pub(crate) mod logging_core;
 pub(crate) mod orbit_core;
 pub(crate) mod process_core;
+pub(crate) mod process_stats_core;
 pub(crate) mod prompts_core;
 pub(crate) mod settings_core;
+#[cfg(not(any(target_os = "android", target_os = "ios")))]
+pub(crate) mod terminal_core;
+pub(crate) mod thread_id_core;
+pub(crate) mod tls_core;
 pub(crate) mod workspaces_core;
 pub(crate) mod worktree_core;
@@ -8,6 +8,8 @@ use std::process::Stdio;
 
 use tokio::process::{Child, Command};
 
+use super::process_stats_core::descendant_pids;
+
 /// On Windows, spawning a console app from a GUI subsystem app will open a new
 /// console window unless we explicitly disable it.
 fn hide_console_on_windows(_command: &mut std::process::Command) {
@@ -26,6 +28,14 @@ pub(crate) fn tokio_command(program: impl AsRef<OsStr>) -> Command {
 }
 
 pub(crate) async fn kill_child_process_tree(child: &mut Child) {
+    let descendants = child.id().map(descendant_pids).unwrap_or_default();
+    kill_child_process_tree_with(child, &descendants).await;
+}
+
+/// Like [`kill_child_process_tree`], but with descendants captured earlier.
+/// Once `child` has exited its orphans are reparented, so callers that let it
+/// exit on its own must list them while it is still running.
+pub(crate) async fn kill_child_process_tree_with(child: &mut Child, descendants: &[u32]) {
     #[cfg(windows)]
     {
         if let Some(pid) = child.id() {
@@ -42,6 +52,15 @@ pub(crate) async fn kill_child_process_tree(child: &mut Child) {
     }
 
     let _ = child.kill().await;
+
+    #[cfg(unix)]
+    for pid in descendants {
+        unsafe {
+            libc::kill(*pid as i32, libc::SIGKILL);
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = descendants;
 }
 
 #[cfg(target_os = "windows")]
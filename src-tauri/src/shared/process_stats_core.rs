@@ -0,0 +1,547 @@
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::{HashMap, HashSet};
+use std::sync::{Arc, Mutex as StdMutex, OnceLock};
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::backend::app_server::WorkspaceSession;
+use crate::backend::events::{AppServerEvent, EventSink};
+use crate::types::{WorkspaceEntry, WorkspaceSettings};
+
+const PROCESS_LIMIT_EVENT: &str = "codex/processLimitExceeded";
+/// How often connected workspaces are sampled for soft-limit crossings when
+/// nobody is polling `workspace_process_stats`.
+pub(crate) const PROCESS_LIMIT_POLL_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Workspace id -> limits it was over at the last sample.
+static WARNED_LIMITS: OnceLock<StdMutex<HashMap<String, HashSet<&'static str>>>> = OnceLock::new();
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProcessStats {
+    pub(crate) pid: u32,
+    pub(crate) parent_pid: u32,
+    pub(crate) command_line: String,
+    pub(crate) cpu_time_ms: u64,
+    pub(crate) rss_bytes: u64,
+    /// `None` when the fd table is not readable (e.g. setuid children).
+    pub(crate) open_fds: Option<u64>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct ProcessTreeTotals {
+    pub(crate) process_count: u64,
+    pub(crate) cpu_time_ms: u64,
+    pub(crate) rss_bytes: u64,
+    pub(crate) open_fds: u64,
+}
+
+impl ProcessTreeTotals {
+    fn from_processes(processes: &[ProcessStats]) -> Self {
+        processes
+            .iter()
+            .fold(Self::default(), |mut totals, process| {
+                totals.process_count += 1;
+                totals.cpu_time_ms += process.cpu_time_ms;
+                totals.rss_bytes += process.rss_bytes;
+                totals.open_fds += process.open_fds.unwrap_or(0);
+                totals
+            })
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub(crate) struct SoftLimitExceeded {
+    pub(crate) limit: &'static str,
+    pub(crate) value: u64,
+    pub(crate) soft_limit: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+struct WorkspaceProcessStats {
+    workspace_id: String,
+    root_pid: Option<u32>,
+    /// The app-server is pooled and may serve other workspaces too.
+    shared: bool,
+    /// Since the session was first spawned; supervised restarts keep counting.
+    uptime_seconds: u64,
+    totals: ProcessTreeTotals,
+    processes: Vec<ProcessStats>,
+    exceeded: Vec<SoftLimitExceeded>,
+}
+
+/// Compare a process tree against the workspace's soft limits. Limits are
+/// advisory: exceeding one only produces a warning event.
+pub(crate) fn check_soft_limits(
+    totals: &ProcessTreeTotals,
+    settings: &WorkspaceSettings,
+) -> Vec<SoftLimitExceeded> {
+    let checks = [
+        (
+            "rssMb",
+            totals.rss_bytes / (1024 * 1024),
+            settings.process_rss_soft_limit_mb,
+        ),
+        (
+            "cpuSecs",
+            totals.cpu_time_ms / 1000,
+            settings.process_cpu_soft_limit_secs,
+        ),
+        ("openFds", totals.open_fds, settings.process_fd_soft_limit),
+    ];
+    checks
+        .into_iter()
+        .filter_map(|(limit, value, soft_limit)| {
+            let soft_limit = soft_limit.filter(|soft_limit| *soft_limit > 0)?;
+            (value > soft_limit).then_some(SoftLimitExceeded {
+                limit,
+                value,
+                soft_limit,
+            })
+        })
+        .collect()
+}
+
+/// Limits in `exceeded` that `workspace_id` was not already over at its
+/// previous sample. A limit warns again only after usage drops back under it.
+fn newly_exceeded(
+    warned: &mut HashMap<String, HashSet<&'static str>>,
+    workspace_id: &str,
+    exceeded: &[SoftLimitExceeded],
+) -> Vec<SoftLimitExceeded> {
+    let current: HashSet<&'static str> = exceeded.iter().map(|entry| entry.limit).collect();
+    let previous = warned
+        .insert(workspace_id.to_string(), current)
+        .unwrap_or_default();
+    exceeded
+        .iter()
+        .filter(|entry| !previous.contains(entry.limit))
+        .cloned()
+        .collect()
+}
+
+/// Emit one warning event per soft-limit crossing.
+fn emit_limit_crossings<E: EventSink>(
+    stats: &[WorkspaceProcessStats],
+    forget_missing: bool,
+    event_sink: &E,
+) {
+    let crossings: Vec<(&WorkspaceProcessStats, Vec<SoftLimitExceeded>)> = {
+        let mut warned = match WARNED_LIMITS.get_or_init(Default::default).lock() {
+            Ok(warned) => warned,
+            Err(poisoned) => poisoned.into_inner(),
+        };
+        if forget_missing {
+            warned.retain(|workspace_id, _| {
+                stats
+                    .iter()
+                    .any(|entry| &entry.workspace_id == workspace_id)
+            });
+        }
+        stats
+            .iter()
+            .map(|entry| {
+                let crossed = newly_exceeded(&mut warned, &entry.workspace_id, &entry.exceeded);
+                (entry, crossed)
+            })
+            .filter(|(_, crossed)| !crossed.is_empty())
+            .collect()
+    };
+    for (entry, crossed) in crossings {
+        event_sink.emit_app_server_event(AppServerEvent {
+            workspace_id: entry.workspace_id.clone(),
+            message: json!({
+                "method": PROCESS_LIMIT_EVENT,
+                "params": {
+                    "workspaceId": entry.workspace_id.clone(),
+                    "rootPid": entry.root_pid,
+                    "totals": entry.totals,
+                    "exceeded": crossed,
+                },
+            }),
+            seq: None,
+        });
+    }
+}
+
+async fn collect_workspace_stats(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: Option<String>,
+) -> Result<Vec<WorkspaceProcessStats>, String> {
+    let mut targets: Vec<(String, Arc<WorkspaceSession>)> = {
+        let sessions = sessions.lock().await;
+        match workspace_id {
+            Some(workspace_id) => {
+                let session = sessions
+                    .get(&workspace_id)
+                    .cloned()
+                    .ok_or_else(|| "workspace not connected".to_string())?;
+                vec![(workspace_id, session)]
+            }
+            None => sessions
+                .iter()
+                .map(|(id, session)| (id.clone(), Arc::clone(session)))
+                .collect(),
+        }
+    };
+    targets.sort_by(|(a, _), (b, _)| a.cmp(b));
+
+    let settings_by_id: HashMap<String, WorkspaceSettings> = {
+        let workspaces = workspaces.lock().await;
+        targets
+            .iter()
+            .filter_map(|(id, _)| {
+                workspaces
+                    .get(id)
+                    .map(|entry| (id.clone(), entry.settings.clone()))
+            })
+            .collect()
+    };
+
+    let mut roots = Vec::with_capacity(targets.len());
+    for (id, session) in &targets {
+        let root_pid = session.child.lock().await.id();
+        roots.push((
+            id.clone(),
+            root_pid,
+            session.pool_key().is_some(),
+            session.started_at.elapsed().as_secs(),
+        ));
+    }
+    let root_pids: Vec<u32> = roots.iter().filter_map(|(_, pid, _, _)| *pid).collect();
+    let trees = tokio::task::spawn_blocking(move || collect_process_trees(&root_pids))
+        .await
+        .map_err(|err| format!("failed to join process stats task: {err}"))?;
+
+    let mut results = Vec::with_capacity(roots.len());
+    for (workspace_id, root_pid, shared, uptime_seconds) in roots {
+        // Pooled workspaces share a root pid, so each one gets its own copy.
+        let processes = root_pid
+            .and_then(|pid| trees.get(&pid).cloned())
+            .unwrap_or_default();
+        let totals = ProcessTreeTotals::from_processes(&processes);
+        let exceeded = settings_by_id
+            .get(&workspace_id)
+            .map(|settings| check_soft_limits(&totals, settings))
+            .unwrap_or_default();
+        results.push(WorkspaceProcessStats {
+            workspace_id,
+            root_pid,
+            shared,
+            uptime_seconds,
+            totals,
+            processes,
+            exceeded,
+        });
+    }
+    Ok(results)
+}
+
+/// CPU, RSS and fd usage of each connected workspace's app-server and its
+/// descendants. Only Linux exposes this (via /proc); other platforms report
+/// `supported: false` with empty trees.
+pub(crate) async fn workspace_process_stats_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: Option<String>,
+    event_sink: &E,
+) -> Result<Value, String> {
+    let forget_missing = workspace_id.is_none();
+    let results = collect_workspace_stats(workspaces, sessions, workspace_id).await?;
+    emit_limit_crossings(&results, forget_missing, event_sink);
+
+    Ok(json!({
+        "supported": cfg!(target_os = "linux"),
+        "workspaces": results,
+    }))
+}
+
+/// One background pass over every connected workspace, so soft-limit
+/// warnings fire even when no client is polling. Skips the /proc scan when
+/// no workspace has a limit configured.
+pub(crate) async fn check_process_limits_core<E: EventSink>(
+    workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    event_sink: &E,
+) {
+    let has_limits = workspaces.lock().await.values().any(|entry| {
+        let settings = &entry.settings;
+        [
+            settings.process_rss_soft_limit_mb,
+            settings.process_cpu_soft_limit_secs,
+            settings.process_fd_soft_limit,
+        ]
+        .into_iter()
+        .any(|limit| limit.is_some_and(|limit| limit > 0))
+    });
+    if !has_limits {
+        return;
+    }
+    match collect_workspace_stats(workspaces, sessions, None).await {
+        Ok(results) => emit_limit_crossings(&results, true, event_sink),
+        Err(err) => eprintln!("failed to sample workspace processes: {err}"),
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn collect_process_trees(root_pids: &[u32]) -> HashMap<u32, Vec<ProcessStats>> {
+    procfs::collect_trees(
+        std::path::Path::new("/proc"),
+        root_pids,
+        procfs::clock_ticks(),
+    )
+}
+
+#[cfg(not(target_os = "linux"))]
+fn collect_process_trees(_root_pids: &[u32]) -> HashMap<u32, Vec<ProcessStats>> {
+    HashMap::new()
+}
+
+/// Pids of every process below `root_pid`. Empty where the process table
+/// cannot be walked cheaply.
+pub(crate) fn descendant_pids(root_pid: u32) -> Vec<u32> {
+    collect_process_trees(&[root_pid])
+        .remove(&root_pid)
+        .unwrap_or_default()
+        .into_iter()
+        .skip(1)
+        .map(|stats| stats.pid)
+        .collect()
+}
+
+#[cfg(target_os = "linux")]
+mod procfs {
+    use super::ProcessStats;
+    use std::collections::{HashMap, HashSet, VecDeque};
+    use std::fs;
+    use std::path::Path;
+
+    pub(super) struct StatFields {
+        pub(super) comm: String,
+        pub(super) parent_pid: u32,
+        pub(super) cpu_ticks: u64,
+    }
+
+    pub(super) fn clock_ticks() -> u64 {
+        let ticks = unsafe { libc::sysconf(libc::_SC_CLK_TCK) };
+        if ticks > 0 {
+            ticks as u64
+        } else {
+            100
+        }
+    }
+
+    /// `pid (comm) state ppid ... utime stime ...`; comm may itself contain
+    /// spaces and parentheses, so split on the last `)`.
+    pub(super) fn parse_stat(contents: &str) -> Option<StatFields> {
+        let open = contents.find('(')?;
+        let close = contents.rfind(')')?;
+        let comm = contents.get(open + 1..close)?.to_string();
+        let fields: Vec<&str> = contents.get(close + 1..)?.split_whitespace().collect();
+        let parent_pid = fields.get(1)?.parse().ok()?;
+        let utime: u64 = fields.get(11)?.parse().ok()?;
+        let stime: u64 = fields.get(12)?.parse().ok()?;
+        Some(StatFields {
+            comm,
+            parent_pid,
+            cpu_ticks: utime + stime,
+        })
+    }
+
+    pub(super) fn parse_status_rss_bytes(contents: &str) -> u64 {
+        contents
+            .lines()
+            .find_map(|line| line.strip_prefix("VmRSS:"))
+            .and_then(|rest| rest.split_whitespace().next())
+            .and_then(|kb| kb.parse::<u64>().ok())
+            .map(|kb| kb * 1024)
+            .unwrap_or(0)
+    }
+
+    fn read_command_line(proc_root: &Path, pid: u32, comm: &str) -> String {
+        let raw = fs::read(proc_root.join(pid.to_string()).join("cmdline")).unwrap_or_default();
+        let command_line = raw
+            .split(|byte| *byte == 0)
+            .filter(|part| !part.is_empty())
+            .map(|part| String::from_utf8_lossy(part).into_owned())
+            .collect::<Vec<_>>()
+            .join(" ");
+        if command_line.is_empty() {
+            format!("[{comm}]")
+        } else {
+            command_line
+        }
+    }
+
+    /// Walk the process table once and return the subtree under each root,
+    /// root first. Processes that exit mid-walk are skipped.
+    pub(super) fn collect_trees(
+        proc_root: &Path,
+        root_pids: &[u32],
+        clock_ticks: u64,
+    ) -> HashMap<u32, Vec<ProcessStats>> {
+        let mut stats_by_pid: HashMap<u32, StatFields> = HashMap::new();
+        let mut children: HashMap<u32, Vec<u32>> = HashMap::new();
+        if let Ok(entries) = fs::read_dir(proc_root) {
+            for entry in entries.flatten() {
+                let Some(pid) = entry
+                    .file_name()
+                    .to_str()
+                    .and_then(|name| name.parse().ok())
+                else {
+                    continue;
+                };
+                let Ok(contents) = fs::read_to_string(entry.path().join("stat")) else {
+                    continue;
+                };
+                if let Some(stat) = parse_stat(&contents) {
+                    children.entry(stat.parent_pid).or_default().push(pid);
+                    stats_by_pid.insert(pid, stat);
+                }
+            }
+        }
+
+        let mut trees = HashMap::new();
+        for &root_pid in root_pids {
+            let mut processes = Vec::new();
+            let mut seen = HashSet::new();
+            let mut queue = VecDeque::from([root_pid]);
+            while let Some(pid) = queue.pop_front() {
+                if !seen.insert(pid) {
+                    continue;
+                }
+                let Some(stat) = stats_by_pid.get(&pid) else {
+                    continue;
+                };
+                let process_dir = proc_root.join(pid.to_string());
+                let rss_bytes = fs::read_to_string(process_dir.join("status"))
+                    .map(|status| parse_status_rss_bytes(&status))
+                    .unwrap_or(0);
+                let open_fds = fs::read_dir(process_dir.join("fd"))
+                    .ok()
+                    .map(|entries| entries.count() as u64);
+                processes.push(ProcessStats {
+                    pid,
+                    parent_pid: stat.parent_pid,
+                    command_line: read_command_line(proc_root, pid, &stat.comm),
+                    cpu_time_ms: stat.cpu_ticks * 1000 / clock_ticks.max(1),
+                    rss_bytes,
+                    open_fds,
+                });
+                if let Some(child_pids) = children.get(&pid) {
+                    let mut child_pids = child_pids.clone();
+                    child_pids.sort_unstable();
+                    queue.extend(child_pids);
+                }
+            }
+            trees.insert(root_pid, processes);
+        }
+        trees
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{check_soft_limits, newly_exceeded, ProcessTreeTotals, SoftLimitExceeded};
+    use crate::types::WorkspaceSettings;
+    use std::collections::HashMap;
+
+    #[test]
+    fn soft_limits_report_only_configured_and_exceeded_values() {
+        let totals = ProcessTreeTotals {
+            process_count: 3,
+            cpu_time_ms: 90_500,
+            rss_bytes: 600 * 1024 * 1024,
+            open_fds: 40,
+        };
+        let settings = WorkspaceSettings {
+            process_rss_soft_limit_mb: Some(512),
+            process_cpu_soft_limit_secs: Some(120),
+            process_fd_soft_limit: Some(0),
+            ..WorkspaceSettings::default()
+        };
+        let exceeded = check_soft_limits(&totals, &settings);
+        assert_eq!(exceeded.len(), 1);
+        assert_eq!(exceeded[0].limit, "rssMb");
+        assert_eq!(exceeded[0].value, 600);
+        assert_eq!(exceeded[0].soft_limit, 512);
+        assert!(check_soft_limits(&totals, &WorkspaceSettings::default()).is_empty());
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn procfs_walk_collects_descendants_only() {
+        use super::procfs::{collect_trees, parse_stat};
+        use std::fs;
+
+        let root =
+            std::env::temp_dir().join(format!("codex-monitor-proc-{}", uuid::Uuid::new_v4()));
+        let write_proc = |pid: u32, ppid: u32, comm: &str, cmdline: &[u8], rss_kb: u64| {
+            let dir = root.join(pid.to_string());
+            fs::create_dir_all(dir.join("fd")).expect("create proc dir");
+            fs::write(
+                dir.join("stat"),
+                format!("{pid} ({comm}) S {ppid} 1 1 0 -1 0 0 0 0 0 250 50 0 0 20 0 1 0"),
+            )
+            .expect("write stat");
+            fs::write(dir.join("cmdline"), cmdline).expect("write cmdline");
+            fs::write(
+                dir.join("status"),
+                format!("Name:\t{comm}\nVmRSS:\t{rss_kb} kB\n"),
+            )
+            .expect("write status");
+        };
+        write_proc(100, 1, "node", b"node\0codex\0app-server\0", 2048);
+        write_proc(101, 100, "rg (worker)", b"", 1024);
+        write_proc(200, 1, "unrelated", b"bash\0", 4096);
+        fs::write(root.join(100.to_string()).join("fd").join("0"), "").expect("write fd");
+
+        let trees = collect_trees(&root, &[100], 100);
+        let tree = &trees[&100];
+        assert_eq!(tree.len(), 2);
+        assert_eq!(tree[0].command_line, "node codex app-server");
+        assert_eq!(tree[0].cpu_time_ms, 3000);
+        assert_eq!(tree[0].rss_bytes, 2048 * 1024);
+        assert_eq!(tree[0].open_fds, Some(1));
+        assert_eq!(tree[1].pid, 101);
+        assert_eq!(tree[1].command_line, "[rg (worker)]");
+
+        let stat = parse_stat("7 (a) b) R 3 0 0 0 0 0 0 0 0 0 5 6").expect("stat");
+        assert_eq!(stat.comm, "a) b");
+        assert_eq!(stat.parent_pid, 3);
+        assert_eq!(stat.cpu_ticks, 11);
+
+        let _ = fs::remove_dir_all(root);
+    }
+
+    #[test]
+    fn limit_crossings_warn_once_until_usage_drops_back_under() {
+        let rss = SoftLimitExceeded {
+            limit: "rssMb",
+            value: 600,
+            soft_limit: 512,
+        };
+        let mut warned = HashMap::new();
+
+        assert_eq!(
+            newly_exceeded(&mut warned, "ws-1", std::slice::from_ref(&rss)),
+            vec![rss.clone()]
+        );
+        assert!(newly_exceeded(&mut warned, "ws-1", std::slice::from_ref(&rss)).is_empty());
+        assert_eq!(
+            newly_exceeded(&mut warned, "ws-2", std::slice::from_ref(&rss)),
+            vec![rss.clone()]
+        );
+
+        assert!(newly_exceeded(&mut warned, "ws-1", &[]).is_empty());
+        assert_eq!(
+            newly_exceeded(&mut warned, "ws-1", std::slice::from_ref(&rss)),
+            vec![rss]
+        );
+    }
+}
@@ -2,6 +2,7 @@ use std::path::PathBuf;
 
 use tokio::sync::Mutex;
 
+use crate::backend::app_server::{configure_app_server_pooling, configure_request_timeouts};
 use crate::codex::config as codex_config;
 use crate::storage::write_settings;
 use crate::types::AppSettings;
@@ -67,6 +68,8 @@ pub(crate) async fn update_app_settings_core(
     write_settings(settings_path, &settings)?;
     // Sync to Codex config is best-effort; mobile platforms may not have CODEX_HOME.
     let _ = sync_codex_config_from_settings(&settings);
+    configure_request_timeouts(&settings);
+    configure_app_server_pooling(&settings);
     *current = settings.clone();
     Ok(settings)
 }
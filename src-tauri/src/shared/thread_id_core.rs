@@ -0,0 +1,35 @@
+//! Thread id lookup for app-server messages, shared by the backend's protocol
+//! model and the headless CLI.
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Every place a thread id can appear in params or results.
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct ThreadIdCarrier {
+    #[serde(default, alias = "thread_id")]
+    thread_id: Option<String>,
+    #[serde(default)]
+    thread: Option<ThreadIdOnly>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct ThreadIdOnly {
+    #[serde(default)]
+    id: Option<String>,
+}
+
+/// Thread id carried by a params or result object.
+pub(crate) fn thread_id_from_value(value: &Value) -> Option<String> {
+    let carrier = ThreadIdCarrier::deserialize(value).ok()?;
+    carrier
+        .thread_id
+        .or_else(|| carrier.thread.and_then(|thread| thread.id))
+        .filter(|id| !id.is_empty())
+}
+
+/// Thread id carried by a notification or request's `params`.
+pub(crate) fn thread_id_from_message(message: &Value) -> Option<String> {
+    thread_id_from_value(message.get("params")?)
+}
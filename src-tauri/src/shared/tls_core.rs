@@ -0,0 +1,12 @@
+use sha2::{Digest, Sha256};
+
+/// SHA-256 fingerprint of a DER certificate as colon-separated uppercase hex,
+/// matching `openssl x509 -noout -fingerprint -sha256`. The daemon prints it
+/// on startup and clients pin against the same format.
+pub(crate) fn certificate_fingerprint(der: &[u8]) -> String {
+    Sha256::digest(der)
+        .iter()
+        .map(|byte| format!("{byte:02X}"))
+        .collect::<Vec<_>>()
+        .join(":")
+}
@@ -1,11 +1,10 @@
 use std::collections::HashMap;
 use std::future::Future;
-#[cfg(target_os = "windows")]
-use std::path::Path;
-use std::path::{Component, PathBuf};
+use std::path::{Component, Path, PathBuf};
 use std::process::Stdio;
 use std::sync::Arc;
 
+use futures_util::future::join_all;
 use tokio::io::AsyncWriteExt;
 use tokio::sync::Mutex;
 
@@ -13,9 +12,11 @@ use crate::backend::app_server::WorkspaceSession;
 use crate::codex::args::resolve_workspace_codex_args;
 use crate::codex::home::resolve_workspace_codex_home;
 use crate::git_utils::resolve_git_root;
+use crate::shared::event_journal_core::remove_workspace_journal;
+use crate::shared::git_ui_core::with_conflict_summary;
+use crate::shared::process_core::tokio_command;
 #[cfg(target_os = "windows")]
 use crate::shared::process_core::{build_cmd_c_command, resolve_windows_executable};
-use crate::shared::process_core::{kill_child_process_tree, tokio_command};
 use crate::shared::{git_core, worktree_core};
 use crate::storage::write_workspaces;
 use crate::types::{
@@ -242,8 +243,7 @@ where
             let mut workspaces = workspaces.lock().await;
             workspaces.remove(&entry.id);
         }
-        let mut child = session.child.lock().await;
-        kill_child_process_tree(&mut child).await;
+        session.release(&entry.id).await;
         return Err(error);
     }
 
@@ -298,6 +298,23 @@ fn validate_target_folder_name(value: &str) -> Result<String, String> {
     }
 }
 
+/// Folder `add_workspace_from_git_url_core` clones into: `target_folder_name`
+/// or the repository name from `url`, inside `destination_parent`.
+pub(crate) fn git_url_clone_path(
+    url: &str,
+    destination_parent: &Path,
+    target_folder_name: Option<&str>,
+) -> Result<PathBuf, String> {
+    let folder_name = target_folder_name
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string)
+        .or_else(|| default_repo_name_from_url(url.trim()))
+        .ok_or_else(|| "Could not determine target folder name from URL.".to_string())?;
+    let folder_name = validate_target_folder_name(&folder_name)?;
+    Ok(destination_parent.join(folder_name))
+}
+
 pub(crate) async fn add_workspace_from_git_url_core<F, Fut>(
     url: String,
     destination_path: String,
@@ -326,15 +343,7 @@ where
         return Err("Destination folder must be an existing directory.".to_string());
     }
 
-    let folder_name = target_folder_name
-        .as_deref()
-        .map(str::trim)
-        .filter(|value| !value.is_empty())
-        .map(str::to_string)
-        .or_else(|| default_repo_name_from_url(&url))
-        .ok_or_else(|| "Could not determine target folder name from URL.".to_string())?;
-    let folder_name = validate_target_folder_name(&folder_name)?;
-    let clone_path = destination_parent.join(folder_name);
+    let clone_path = git_url_clone_path(&url, &destination_parent, target_folder_name.as_deref())?;
 
     if clone_path.exists() {
         let is_empty = std::fs::read_dir(&clone_path)
@@ -477,8 +486,7 @@ where
             let mut workspaces = workspaces.lock().await;
             workspaces.remove(&entry.id);
         }
-        let mut child = session.child.lock().await;
-        kill_child_process_tree(&mut child).await;
+        session.release(&entry.id).await;
         let _ = tokio::fs::remove_dir_all(&destination_path).await;
         return Err(error);
     }
@@ -621,20 +629,15 @@ async fn apply_worktree_changes_inner_core(
         return Err("Git apply failed.".to_string());
     }
 
-    if detail.contains("Applied patch to") {
-        if detail.contains("with conflicts") {
-            return Err(
-                "Applied with conflicts. Resolve conflicts in the parent repo before retrying."
-                    .to_string(),
-            );
-        }
+    if detail.contains("Applied patch to") && !detail.contains("with conflicts") {
         return Err(
             "Patch applied partially. Resolve changes in the parent repo before retrying."
                 .to_string(),
         );
     }
 
-    Err(detail.to_string())
+    // `--3way` leaves conflicted index entries; list them after git's output.
+    Err(with_conflict_summary(&parent_root, detail.to_string()))
 }
 
 pub(crate) async fn open_workspace_in_core(
@@ -1003,17 +1006,53 @@ where
     };
     let codex_home = resolve_workspace_codex_home(&entry, parent_entry.as_ref());
     let session = spawn_session(entry.clone(), default_bin, codex_args, codex_home).await?;
-    sessions.lock().await.insert(entry.id, session);
+    // A manual reconnect replaces the previous session; stop its supervisor
+    // from respawning a second app-server for the same workspace.
+    replace_session(sessions, entry.id, session).await;
     Ok(())
 }
 
+/// Point `workspace_id` at `session` and drop its reference to the previous
+/// app-server. Rejoining the same shared process keeps it running.
+async fn replace_session(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+    workspace_id: String,
+    session: Arc<WorkspaceSession>,
+) {
+    let old_session = sessions
+        .lock()
+        .await
+        .insert(workspace_id.clone(), Arc::clone(&session));
+    if let Some(old_session) = old_session {
+        if !Arc::ptr_eq(&old_session, &session) {
+            old_session.release(&workspace_id).await;
+        }
+    }
+}
+
 async fn kill_session_by_id(sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>, id: &str) {
-    if let Some(session) = sessions.lock().await.remove(id) {
-        let mut child = session.child.lock().await;
-        kill_child_process_tree(&mut child).await;
+    // Release outside the map lock; shutting down can wait on a running turn.
+    let removed = sessions.lock().await.remove(id);
+    if let Some(session) = removed {
+        session.release(id).await;
     }
 }
 
+/// Gracefully release every connected workspace, e.g. before the daemon
+/// exits. Sessions shut down concurrently so one slow turn does not delay
+/// the rest.
+pub(crate) async fn shutdown_sessions_core(
+    sessions: &Mutex<HashMap<String, Arc<WorkspaceSession>>>,
+) {
+    let drained: Vec<(String, Arc<WorkspaceSession>)> = sessions.lock().await.drain().collect();
+    join_all(
+        drained
+            .iter()
+            .map(|(workspace_id, session)| session.release(workspace_id)),
+    )
+    .await;
+}
+
 pub(crate) async fn remove_workspace_core<FRunGit, FutRunGit, FIsMissing, FRemoveDirAll>(
     id: String,
     workspaces: &Mutex<HashMap<String, WorkspaceEntry>>,
@@ -1094,6 +1133,7 @@ where
         let mut workspaces = workspaces.lock().await;
         for workspace_id in ids_to_remove {
             workspaces.remove(&workspace_id);
+            remove_workspace_journal(&workspace_id);
         }
         let list: Vec<_> = workspaces.values().cloned().collect();
         write_workspaces(storage_path, &list)?;
@@ -1178,6 +1218,7 @@ where
         let list: Vec<_> = workspaces.values().cloned().collect();
         write_workspaces(storage_path, &list)?;
     }
+    remove_workspace_journal(&entry.id);
 
     Ok(())
 }
@@ -1558,14 +1599,7 @@ where
                 return Err(error);
             }
         };
-        if let Some(old_session) = sessions
-            .lock()
-            .await
-            .insert(entry_snapshot.id.clone(), new_session)
-        {
-            let mut child = old_session.child.lock().await;
-            kill_child_process_tree(&mut child).await;
-        }
+        replace_session(sessions, entry_snapshot.id.clone(), new_session).await;
     }
     if codex_home_changed || codex_args_changed {
         let app_settings_snapshot = app_settings.lock().await.clone();
@@ -1607,10 +1641,7 @@ where
                     continue;
                 }
             };
-            if let Some(old_session) = sessions.lock().await.insert(child.id.clone(), new_session) {
-                let mut child = old_session.child.lock().await;
-                kill_child_process_tree(&mut child).await;
-            }
+            replace_session(sessions, child.id.clone(), new_session).await;
         }
     }
     if worktree_setup_script_changed && !entry_snapshot.kind.is_worktree() {
@@ -5,8 +5,11 @@ use tauri::{AppHandle, Manager};
 use tokio::process::Child;
 use tokio::sync::Mutex;
 
+use crate::backend::app_server::{configure_app_server_pooling, configure_request_timeouts};
 use crate::dictation::DictationState;
 use crate::shared::codex_core::CodexLoginCancelState;
+use crate::shared::event_journal_core::configure_event_journal;
+use crate::shared::git_status_watch_core::{GitStatusWatchSet, GitStatusWatchers};
 use crate::storage::{read_settings, read_workspaces};
 use crate::types::{
     AppSettings, OrbitRunnerState, OrbitRunnerStatus, TcpDaemonState, TcpDaemonStatus,
@@ -50,6 +53,7 @@ impl Default for TcpDaemonRuntime {
                 started_at_ms: None,
                 last_error: None,
                 listen_addr: None,
+                managed_by: None,
             },
         }
     }
@@ -60,11 +64,14 @@ pub(crate) struct AppState {
     pub(crate) sessions: Mutex<HashMap<String, Arc<crate::codex::WorkspaceSession>>>,
     pub(crate) terminal_sessions: Mutex<HashMap<String, Arc<crate::terminal::TerminalSession>>>,
     pub(crate) remote_backend: Mutex<Option<crate::remote_backend::RemoteBackend>>,
+    pub(crate) remote_watches: Mutex<crate::remote_backend::RemoteWatches>,
     pub(crate) storage_path: PathBuf,
     pub(crate) settings_path: PathBuf,
     pub(crate) app_settings: Mutex<AppSettings>,
     pub(crate) dictation: Mutex<DictationState>,
     pub(crate) codex_login_cancels: Mutex<HashMap<String, CodexLoginCancelState>>,
+    pub(crate) git_status_watchers: GitStatusWatchers,
+    pub(crate) git_status_watches: Mutex<GitStatusWatchSet>,
     pub(crate) orbit_runner: Mutex<OrbitRunnerRuntime>,
     pub(crate) tcp_daemon: Mutex<TcpDaemonRuntime>,
 }
@@ -79,16 +86,22 @@ impl AppState {
         let settings_path = data_dir.join("settings.json");
         let workspaces = read_workspaces(&storage_path).unwrap_or_default();
         let app_settings = read_settings(&settings_path).unwrap_or_default();
+        configure_request_timeouts(&app_settings);
+        configure_app_server_pooling(&app_settings);
+        configure_event_journal(&data_dir, workspaces.keys().cloned().collect());
         Self {
             workspaces: Mutex::new(workspaces),
             sessions: Mutex::new(HashMap::new()),
             terminal_sessions: Mutex::new(HashMap::new()),
             remote_backend: Mutex::new(None),
+            remote_watches: Mutex::new(Default::default()),
             storage_path,
             settings_path,
             app_settings: Mutex::new(app_settings),
             dictation: Mutex::new(DictationState::default()),
             codex_login_cancels: Mutex::new(HashMap::new()),
+            git_status_watchers: GitStatusWatchers::default(),
+            git_status_watches: Mutex::new(GitStatusWatchSet::default()),
             orbit_runner: Mutex::new(OrbitRunnerRuntime::default()),
             tcp_daemon: Mutex::new(TcpDaemonRuntime::default()),
         }
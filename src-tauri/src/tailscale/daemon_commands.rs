@@ -15,13 +15,36 @@ fn can_force_stop_daemon(auth_ok: bool, info: Option<&DaemonInfo>) -> bool {
     auth_ok && info.is_some_and(is_managed_daemon)
 }
 
+/// systemd/launchd (see `codex-monitor-daemon install-service`) owns the
+/// lifecycle of a daemon it started; the app must not restart or kill it.
+fn service_manager(info: &DaemonInfo) -> Option<&str> {
+    if !is_managed_daemon(info) {
+        return None;
+    }
+    info.managed_by.as_deref()
+}
+
+fn is_stale_daemon(info: &DaemonInfo) -> bool {
+    info.version != CURRENT_APP_VERSION || info.mode != EXPECTED_DAEMON_MODE
+}
+
 fn should_restart_daemon(info: Option<&DaemonInfo>) -> bool {
     let Some(info) = info else {
         return true;
     };
-    !is_managed_daemon(info)
-        || info.version != CURRENT_APP_VERSION
-        || info.mode != EXPECTED_DAEMON_MODE
+    if service_manager(info).is_some() {
+        return false;
+    }
+    !is_managed_daemon(info) || is_stale_daemon(info)
+}
+
+fn service_managed_warning(info: Option<&DaemonInfo>) -> Option<String> {
+    let info = info.filter(|entry| is_stale_daemon(entry))?;
+    let manager = service_manager(info)?;
+    Some(format!(
+        "{}; the daemon is managed by {manager}, so restart it through the service manager.",
+        daemon_restart_reason(Some(info))
+    ))
 }
 
 fn daemon_restart_reason(info: Option<&DaemonInfo>) -> String {
@@ -135,8 +158,11 @@ pub(super) async fn tailscale_daemon_start(
                     state: TcpDaemonState::Running,
                     pid,
                     started_at_ms: runtime.status.started_at_ms,
-                    last_error: auth_error.clone(),
+                    last_error: auth_error
+                        .clone()
+                        .or_else(|| service_managed_warning(info.as_ref())),
                     listen_addr: Some(listen_addr.clone()),
+                    managed_by: info.as_ref().and_then(service_manager).map(str::to_string),
                 };
                 runtime.status.clone()
             };
@@ -208,6 +234,7 @@ pub(super) async fn tailscale_daemon_start(
                     started_at_ms: None,
                     last_error: None,
                     listen_addr: Some(listen_addr.clone()),
+                    managed_by: None,
                 };
             }
         }
@@ -241,6 +268,7 @@ pub(super) async fn tailscale_daemon_start(
             started_at_ms: Some(now_unix_ms()),
             last_error: None,
             listen_addr: Some(listen_addr),
+            managed_by: None,
         };
         runtime.child = Some(child);
         runtime.status.clone()
@@ -272,6 +300,15 @@ pub(super) async fn tailscale_daemon_stop(
         )
         .await
         {
+            DaemonProbe::Running {
+                info: Some(ref info),
+                ..
+            } if service_manager(info).is_some() => {
+                stop_error = Some(format!(
+                    "Daemon is managed by {}; stop it with `codex-monitor-daemon uninstall-service` or the service manager.",
+                    info.managed_by.as_deref().unwrap_or_default()
+                ));
+            }
             DaemonProbe::Running { auth_ok, info, .. } => {
                 let force_kill_allowed = can_force_stop_daemon(auth_ok, info.as_ref());
                 if let Err(shutdown_error) = request_daemon_shutdown(
@@ -347,15 +384,25 @@ pub(super) async fn tailscale_daemon_stop(
     let status = {
         let mut runtime = state.tcp_daemon.lock().await;
         runtime.status = match probe_after_stop {
-            DaemonProbe::Running { auth_error, .. } => TcpDaemonStatus {
-                state: TcpDaemonState::Error,
-                pid: pid_after_stop,
-                started_at_ms: runtime.status.started_at_ms,
-                last_error: Some(stop_error.or(auth_error).unwrap_or_else(|| {
-                    "Daemon is still running after stop attempt.".to_string()
-                })),
-                listen_addr: runtime.status.listen_addr.clone(),
-            },
+            DaemonProbe::Running {
+                auth_error, info, ..
+            } => {
+                let managed_by = info.as_ref().and_then(service_manager).map(str::to_string);
+                TcpDaemonStatus {
+                    state: if managed_by.is_some() {
+                        TcpDaemonState::Running
+                    } else {
+                        TcpDaemonState::Error
+                    },
+                    pid: pid_after_stop,
+                    started_at_ms: runtime.status.started_at_ms,
+                    last_error: Some(stop_error.or(auth_error).unwrap_or_else(|| {
+                        "Daemon is still running after stop attempt.".to_string()
+                    })),
+                    listen_addr: runtime.status.listen_addr.clone(),
+                    managed_by,
+                }
+            }
             DaemonProbe::NotDaemon => TcpDaemonStatus {
                 state: TcpDaemonState::Error,
                 pid: pid_after_stop,
@@ -364,6 +411,7 @@ pub(super) async fn tailscale_daemon_stop(
                     "Configured port is now occupied by a non-daemon process.".to_string()
                 })),
                 listen_addr: runtime.status.listen_addr.clone(),
+                managed_by: None,
             },
             DaemonProbe::NotReachable => TcpDaemonStatus {
                 state: TcpDaemonState::Stopped,
@@ -371,6 +419,7 @@ pub(super) async fn tailscale_daemon_stop(
                 started_at_ms: None,
                 last_error: stop_error,
                 listen_addr: runtime.status.listen_addr.clone(),
+                managed_by: None,
             },
         };
         sync_tcp_daemon_listen_addr(&mut runtime.status, &configured_listen_addr);
@@ -409,7 +458,7 @@ pub(super) async fn tailscale_daemon_status(
             DaemonProbe::Running {
                 auth_ok: _,
                 auth_error,
-                info: _,
+                info,
             } => TcpDaemonStatus {
                 state: TcpDaemonState::Running,
                 pid,
@@ -423,6 +472,10 @@ pub(super) async fn tailscale_daemon_status(
                     .listen_addr
                     .clone()
                     .or(base_status.listen_addr.clone()),
+                managed_by: info
+                    .as_ref()
+                    .and_then(service_manager)
+                    .map(str::to_string),
             },
             DaemonProbe::NotDaemon => TcpDaemonStatus {
                 state: TcpDaemonState::Error,
@@ -439,6 +492,7 @@ pub(super) async fn tailscale_daemon_status(
                     .listen_addr
                     .clone()
                     .or(base_status.listen_addr.clone()),
+                managed_by: None,
             },
             DaemonProbe::NotReachable => TcpDaemonStatus {
                 state: runtime.status.state.clone(),
@@ -457,6 +511,7 @@ pub(super) async fn tailscale_daemon_status(
                     .listen_addr
                     .clone()
                     .or(base_status.listen_addr.clone()),
+                managed_by: None,
             },
         };
         sync_tcp_daemon_listen_addr(&mut runtime.status, &configured_listen_addr);
@@ -469,8 +524,8 @@ pub(super) async fn tailscale_daemon_status(
 #[cfg(test)]
 mod tests {
     use super::{
-        can_force_stop_daemon, should_restart_daemon, DaemonInfo, CURRENT_APP_VERSION,
-        EXPECTED_DAEMON_MODE, EXPECTED_DAEMON_NAME,
+        can_force_stop_daemon, service_managed_warning, should_restart_daemon, DaemonInfo,
+        CURRENT_APP_VERSION, EXPECTED_DAEMON_MODE, EXPECTED_DAEMON_NAME,
     };
 
     fn daemon_info(version: &str) -> DaemonInfo {
@@ -480,6 +535,7 @@ mod tests {
             pid: Some(42),
             mode: EXPECTED_DAEMON_MODE.to_string(),
             binary_path: Some("/tmp/codex-monitor-daemon".to_string()),
+            managed_by: None,
         }
     }
 
@@ -503,4 +559,19 @@ mod tests {
         assert!(!can_force_stop_daemon(false, Some(&info)));
         assert!(!can_force_stop_daemon(true, None));
     }
+
+    #[test]
+    fn service_managed_daemon_is_never_restarted() {
+        let mut info = daemon_info("0.1.0");
+        info.managed_by = Some("systemd".to_string());
+        assert!(!should_restart_daemon(Some(&info)));
+        let warning = service_managed_warning(Some(&info)).expect("stale version warning");
+        assert!(warning.contains("managed by systemd"));
+
+        info.version = CURRENT_APP_VERSION.to_string();
+        assert!(service_managed_warning(Some(&info)).is_none());
+
+        info.name = "unknown-daemon".to_string();
+        assert!(should_restart_daemon(Some(&info)));
+    }
 }
@@ -200,6 +200,7 @@ async fn refresh_tcp_daemon_runtime(runtime: &mut TcpDaemonRuntime) {
                     started_at_ms: None,
                     last_error: None,
                     listen_addr: runtime.status.listen_addr.clone(),
+                    managed_by: None,
                 };
             } else {
                 let failure_hint = if status.code() == Some(101) {
@@ -215,6 +216,7 @@ async fn refresh_tcp_daemon_runtime(runtime: &mut TcpDaemonRuntime) {
                         "Daemon exited with status: {status}.{failure_hint}"
                     )),
                     listen_addr: runtime.status.listen_addr.clone(),
+                    managed_by: None,
                 };
             }
         }
@@ -230,6 +232,7 @@ async fn refresh_tcp_daemon_runtime(runtime: &mut TcpDaemonRuntime) {
                 started_at_ms: runtime.status.started_at_ms,
                 last_error: Some(format!("Failed to inspect daemon process: {err}")),
                 listen_addr: runtime.status.listen_addr.clone(),
+                managed_by: None,
             };
         }
     }
@@ -459,6 +462,7 @@ mod tests {
             started_at_ms: None,
             last_error: None,
             listen_addr: Some("0.0.0.0:4732".to_string()),
+            managed_by: None,
         };
 
         sync_tcp_daemon_listen_addr(&mut status, "0.0.0.0:7777");
@@ -473,6 +477,7 @@ mod tests {
             started_at_ms: Some(1),
             last_error: None,
             listen_addr: Some("0.0.0.0:4732".to_string()),
+            managed_by: None,
         };
 
         sync_tcp_daemon_listen_addr(&mut status, "0.0.0.0:7777");
@@ -9,6 +9,8 @@ pub(super) struct DaemonInfo {
     pub(super) pid: Option<u32>,
     pub(super) mode: String,
     pub(super) binary_path: Option<String>,
+    /// `systemd` or `launchd` when the daemon was installed as a service.
+    pub(super) managed_by: Option<String>,
 }
 
 #[derive(Debug, Clone)]
@@ -69,6 +71,12 @@ fn parse_daemon_info(value: &Value) -> Result<DaemonInfo, String> {
         .map(str::trim)
         .filter(|value| !value.is_empty())
         .map(str::to_string);
+    let managed_by = value
+        .get("managedBy")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
 
     Ok(DaemonInfo {
         name,
@@ -76,6 +84,7 @@ fn parse_daemon_info(value: &Value) -> Result<DaemonInfo, String> {
         pid,
         mode,
         binary_path,
+        managed_by,
     })
 }
 
@@ -31,6 +31,33 @@ pub(crate) struct GitFileDiff {
     pub(crate) new_image_mime: Option<String>,
 }
 
+/// Side of a [`GitFileDiff`] a line number refers to: `old` is HEAD and `new`
+/// the working tree.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum GitDiffSide {
+    Old,
+    New,
+}
+
+/// Inclusive range of changed lines, numbered as in [`GitFileDiff::diff`].
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitLineRange {
+    pub(crate) side: GitDiffSide,
+    pub(crate) start: u32,
+    pub(crate) end: u32,
+}
+
+/// Part of one file's changes to stage, unstage or revert: whole hunks by
+/// their header (`@@ -3,4 +3,6 @@`) and/or individual line ranges.
+#[derive(Debug, Serialize, Deserialize, Clone, Default, PartialEq, Eq)]
+pub(crate) struct GitHunkSelection {
+    #[serde(default)]
+    pub(crate) hunks: Vec<String>,
+    #[serde(default)]
+    pub(crate) lines: Vec<GitLineRange>,
+}
+
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitCommitDiff {
     pub(crate) path: String,
@@ -60,10 +87,50 @@ pub(crate) struct GitLogEntry {
     pub(crate) summary: String,
     pub(crate) author: String,
     pub(crate) timestamp: i64,
+    #[serde(default)]
+    pub(crate) parents: Vec<String>,
+    /// Graph lanes for this row; only set for unfiltered history walks.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub(crate) graph: Option<GitLogGraphRow>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub(crate) struct GitLogGraphRow {
+    /// Column of this commit.
+    pub(crate) lane: usize,
+    /// Columns with an edge entering this row from the row above.
+    #[serde(rename = "incomingLanes")]
+    pub(crate) incoming_lanes: Vec<usize>,
+    /// Incoming columns other than `lane` that end at this commit.
+    #[serde(rename = "mergingLanes")]
+    pub(crate) merging_lanes: Vec<usize>,
+    /// Column each parent continues in, in `parents` order.
+    #[serde(rename = "parentLanes")]
+    pub(crate) parent_lanes: Vec<usize>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Default)]
+pub(crate) struct GitLogFilter {
+    #[serde(default)]
+    pub(crate) author: Option<String>,
+    #[serde(default)]
+    pub(crate) path: Option<String>,
+    #[serde(default)]
+    pub(crate) grep: Option<String>,
+    /// Unix seconds, inclusive.
+    #[serde(default)]
+    pub(crate) since: Option<i64>,
+    /// Unix seconds, inclusive.
+    #[serde(default)]
+    pub(crate) until: Option<i64>,
+    /// Branch, tag or revision to start from instead of HEAD.
+    #[serde(default)]
+    pub(crate) rev: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 pub(crate) struct GitLogResponse {
+    /// Number of entries in this page; history is no longer counted up front.
     pub(crate) total: usize,
     pub(crate) entries: Vec<GitLogEntry>,
     #[serde(default)]
@@ -76,6 +143,52 @@ pub(crate) struct GitLogResponse {
     pub(crate) behind_entries: Vec<GitLogEntry>,
     #[serde(default)]
     pub(crate) upstream: Option<String>,
+    /// Opaque cursor for the next page; absent once history is exhausted.
+    #[serde(default, rename = "nextCursor")]
+    pub(crate) next_cursor: Option<String>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitStashEntry {
+    /// Position in the stash list (`stash@{index}`); shifts as stashes are
+    /// pushed and dropped, so commands take `sha` instead.
+    pub(crate) index: usize,
+    pub(crate) sha: String,
+    pub(crate) message: String,
+    pub(crate) timestamp: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitConflictFile {
+    pub(crate) path: String,
+    /// Common ancestor; absent when both sides added the file.
+    pub(crate) base: Option<String>,
+    /// HEAD's side. During a rebase this is the branch being rebased onto.
+    pub(crate) ours: Option<String>,
+    /// The incoming side: the merged branch or the commit being replayed.
+    pub(crate) theirs: Option<String>,
+    /// Working tree content, usually with conflict markers.
+    pub(crate) merged: Option<String>,
+    /// Set when some side exists but is too large or not UTF-8 text; its
+    /// content is then left out.
+    #[serde(rename = "isBinary")]
+    pub(crate) is_binary: bool,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub(crate) struct GitConflictStatus {
+    /// `merge`, `rebase`, `cherry-pick`, `revert` or `am`; absent when no
+    /// operation is in progress (e.g. after `git apply --3way`).
+    pub(crate) operation: Option<String>,
+    pub(crate) files: Vec<GitConflictFile>,
+}
+
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub(crate) enum GitConflictResolution {
+    Ours,
+    Theirs,
+    Edited,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -295,6 +408,8 @@ pub(crate) struct TcpDaemonStatus {
     pub(crate) last_error: Option<String>,
     #[serde(default)]
     pub(crate) listen_addr: Option<String>,
+    #[serde(default)]
+    pub(crate) managed_by: Option<String>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -425,6 +540,14 @@ pub(crate) struct WorkspaceSettings {
     pub(crate) launch_scripts: Option<Vec<LaunchScriptEntry>>,
     #[serde(default, rename = "worktreeSetupScript")]
     pub(crate) worktree_setup_script: Option<String>,
+    #[serde(default, rename = "accessMode")]
+    pub(crate) access_mode: Option<String>,
+    #[serde(default, rename = "processRssSoftLimitMb")]
+    pub(crate) process_rss_soft_limit_mb: Option<u64>,
+    #[serde(default, rename = "processCpuSoftLimitSecs")]
+    pub(crate) process_cpu_soft_limit_secs: Option<u64>,
+    #[serde(default, rename = "processFdSoftLimit")]
+    pub(crate) process_fd_soft_limit: Option<u64>,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -468,6 +591,12 @@ pub(crate) struct AppSettings {
     pub(crate) remote_backend_provider: RemoteBackendProvider,
     #[serde(default = "default_remote_backend_host", rename = "remoteBackendHost")]
     pub(crate) remote_backend_host: String,
+    #[serde(default, rename = "remoteBackendSocketPath")]
+    pub(crate) remote_backend_socket_path: Option<String>,
+    /// SHA-256 fingerprint of the daemon's TLS certificate. When set, TCP
+    /// connections use TLS and only trust that certificate.
+    #[serde(default, rename = "remoteBackendTlsFingerprint")]
+    pub(crate) remote_backend_tls_fingerprint: Option<String>,
     // Persisted out-of-band by storage.rs into data-dir/remote_backend_token.
     // Kept here so IPC payloads still carry the runtime token value.
     #[serde(default, rename = "remoteBackendToken")]
@@ -743,6 +872,14 @@ pub(crate) struct AppSettings {
     pub(crate) open_app_targets: Vec<OpenAppTarget>,
     #[serde(default = "default_selected_open_app_id", rename = "selectedOpenAppId")]
     pub(crate) selected_open_app_id: String,
+    #[serde(default, rename = "appServerRequestTimeoutSecs")]
+    pub(crate) app_server_request_timeout_secs: Option<u64>,
+    #[serde(default, rename = "appServerMethodTimeoutSecs")]
+    pub(crate) app_server_method_timeout_secs: HashMap<String, u64>,
+    /// Let workspaces with the same codex binary, args and CODEX_HOME share
+    /// one app-server process.
+    #[serde(default, rename = "appServerPooling")]
+    pub(crate) app_server_pooling: bool,
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
@@ -763,6 +900,7 @@ impl Default for BackendMode {
 pub(crate) enum RemoteBackendProvider {
     Tcp,
     Orbit,
+    Unix,
 }
 
 impl Default for RemoteBackendProvider {
@@ -1220,6 +1358,8 @@ impl Default for AppSettings {
             backend_mode: default_backend_mode(),
             remote_backend_provider: RemoteBackendProvider::Tcp,
             remote_backend_host: default_remote_backend_host(),
+            remote_backend_socket_path: None,
+            remote_backend_tls_fingerprint: None,
             remote_backend_token: None,
             orbit_ws_url: None,
             orbit_auth_url: None,
@@ -1296,6 +1436,9 @@ impl Default for AppSettings {
             workspace_groups: default_workspace_groups(),
             open_app_targets: default_open_app_targets(),
             selected_open_app_id: default_selected_open_app_id(),
+            app_server_request_timeout_secs: None,
+            app_server_method_timeout_secs: HashMap::new(),
+            app_server_pooling: false,
         }
     }
 }
@@ -1325,6 +1468,8 @@ mod tests {
             RemoteBackendProvider::Tcp
         ));
         assert_eq!(settings.remote_backend_host, "127.0.0.1:4732");
+        assert!(settings.remote_backend_socket_path.is_none());
+        assert!(settings.remote_backend_tls_fingerprint.is_none());
         assert!(settings.remote_backend_token.is_none());
         assert!(settings.orbit_ws_url.is_none());
         assert!(settings.orbit_auth_url.is_none());
@@ -1335,6 +1480,9 @@ mod tests {
         assert!(settings.orbit_access_client_id.is_none());
         assert!(settings.orbit_access_client_secret_ref.is_none());
         assert_eq!(settings.review_delivery_mode, "inline");
+        assert!(settings.app_server_request_timeout_secs.is_none());
+        assert!(settings.app_server_method_timeout_secs.is_empty());
+        assert!(!settings.app_server_pooling);
         let expected_primary = if cfg!(target_os = "macos") {
             "cmd"
         } else {
@@ -1520,5 +1668,9 @@ mod tests {
         assert!(settings.git_root.is_none());
         assert!(settings.display_name.is_none());
         assert!(settings.thread_display_names.is_none());
+        assert!(settings.access_mode.is_none());
+        assert!(settings.process_rss_soft_limit_mb.is_none());
+        assert!(settings.process_cpu_soft_limit_secs.is_none());
+        assert!(settings.process_fd_soft_limit.is_none());
     }
 }
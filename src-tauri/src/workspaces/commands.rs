@@ -2,7 +2,7 @@ use std::path::{Path, PathBuf};
 
 use std::sync::Arc;
 
-use serde_json::json;
+use serde_json::{json, Value};
 use tauri::{AppHandle, Manager, State};
 
 use super::files::{list_workspace_files_inner, read_workspace_file_inner, WorkspaceFileResponse};
@@ -19,9 +19,10 @@ use super::worktree::{
 
 use crate::backend::app_server::WorkspaceSession;
 use crate::codex::spawn_workspace_session;
+use crate::event_sink::TauriEventSink;
 use crate::git_utils::resolve_git_root;
 use crate::remote_backend;
-use crate::shared::workspaces_core;
+use crate::shared::{process_stats_core, workspaces_core};
 use crate::state::AppState;
 use crate::types::{WorkspaceEntry, WorkspaceInfo, WorkspaceSettings, WorktreeSetupStatus};
 
@@ -770,6 +771,31 @@ pub(crate) async fn connect_workspace(
     .await
 }
 
+#[tauri::command]
+pub(crate) async fn workspace_process_stats(
+    workspace_id: Option<String>,
+    state: State<'_, AppState>,
+    app: AppHandle,
+) -> Result<Value, String> {
+    if remote_backend::is_remote_mode(&*state).await {
+        return remote_backend::call_remote(
+            &*state,
+            app,
+            "workspace_process_stats",
+            json!({ "workspaceId": workspace_id }),
+        )
+        .await;
+    }
+
+    process_stats_core::workspace_process_stats_core(
+        &state.workspaces,
+        &state.sessions,
+        workspace_id,
+        &TauriEventSink::new(app),
+    )
+    .await
+}
+
 #[tauri::command]
 pub(crate) async fn list_workspace_files(
     workspace_id: String,
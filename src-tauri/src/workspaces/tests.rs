@@ -58,6 +58,10 @@ fn workspace_with_id_and_kind(
             launch_script: None,
             launch_scripts: None,
             worktree_setup_script: None,
+            access_mode: None,
+            process_rss_soft_limit_mb: None,
+            process_cpu_soft_limit_secs: None,
+            process_fd_soft_limit: None,
         },
     }
 }
@@ -576,9 +576,11 @@ export function MainApp() {
     gitLogAheadEntries,
     gitLogBehindEntries,
     gitLogUpstream,
+    gitLogHasMore,
     gitLogLoading,
     gitLogError,
     refreshGitLog,
+    loadMoreGitLog,
     gitCommitDiffs,
     shouldLoadDiffs,
     activeDiffs,
@@ -954,6 +956,7 @@ export function MainApp() {
     onDebug: addDebugEntry,
     model: resolvedModel,
     effort: resolvedEffort,
+    accessMode,
     collaborationMode: collaborationModePayload,
     skills,
     reviewDeliveryMode: appSettings.reviewDeliveryMode,
@@ -2823,6 +2826,8 @@ export function MainApp() {
     gitLogAheadEntries,
     gitLogBehindEntries,
     gitLogUpstream,
+    gitLogHasMore,
+    onLoadMoreGitLog: loadMoreGitLog,
     gitLogError,
     gitLogLoading,
     selectedCommitSha,
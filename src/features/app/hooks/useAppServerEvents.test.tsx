@@ -466,6 +466,35 @@ describe("useAppServerEvents", () => {
     });
   });
 
+  it("accepts daemon thread/activity summaries without incompatibility toast", async () => {
+    const handlers: Handlers = {
+      onIsAlive: vi.fn(),
+    };
+    const { root } = await mount(handlers);
+
+    act(() => {
+      listener?.({
+        workspace_id: "ws-activity",
+        message: {
+          method: "thread/activity",
+          params: {
+            workspaceId: "ws-activity",
+            threadId: "thread-1",
+            lastMethod: "item/agentMessage/delta",
+            eventCount: 12,
+          },
+        },
+      });
+    });
+
+    expect(handlers.onIsAlive).toHaveBeenCalledWith("ws-activity");
+    expect(pushErrorToastMock).not.toHaveBeenCalled();
+
+    await act(async () => {
+      root.unmount();
+    });
+  });
+
   it("passes through unknown codex/event notifications without incompatibility toast", async () => {
     const handlers: Handlers = {
       onAppServerEvent: vi.fn(),
@@ -530,6 +559,83 @@ describe("useAppServerEvents", () => {
     });
   });
 
+  it("applies the daemon event stream snapshot through the regular handlers", async () => {
+    const handlers: Handlers = {
+      onWorkspaceConnected: vi.fn(),
+      onWorkspaceDisconnected: vi.fn(),
+      onTurnStarted: vi.fn(),
+      onApprovalRequest: vi.fn(),
+      onRequestUserInput: vi.fn(),
+    };
+    const { root } = await mount(handlers);
+
+    act(() => {
+      listener?.({
+        workspace_id: "__daemon__",
+        message: {
+          method: "codex/eventStreamSnapshot",
+          params: {
+            generatedAtMs: 1,
+            workspaces: [
+              { id: "ws-1", name: "One", connected: true },
+              { id: "ws-2", name: "Two", connected: false },
+            ],
+            activeTurns: [{ workspaceId: "ws-1", threadId: "thread-1", turnId: "turn-1" }],
+            pendingRequests: [
+              {
+                workspaceId: "ws-1",
+                requestId: 7,
+                method: "item/commandExecution/requestApproval",
+                threadId: "thread-1",
+                params: { threadId: "thread-1", command: "ls" },
+                receivedAtMs: 1,
+              },
+              {
+                workspaceId: "ws-1",
+                requestId: "input-1",
+                method: "item/tool/requestUserInput",
+                threadId: "thread-1",
+                params: {
+                  threadId: "thread-1",
+                  turnId: "turn-1",
+                  itemId: "item-1",
+                  questions: [{ id: "q1", header: "H", question: "Q?" }],
+                },
+                receivedAtMs: 2,
+              },
+            ],
+            terminals: [],
+          },
+        },
+      });
+    });
+
+    expect(handlers.onWorkspaceConnected).toHaveBeenCalledWith("ws-1");
+    expect(handlers.onWorkspaceDisconnected).toHaveBeenCalledWith("ws-2");
+    expect(handlers.onTurnStarted).toHaveBeenCalledWith("ws-1", "thread-1", "turn-1");
+    expect(handlers.onApprovalRequest).toHaveBeenCalledWith({
+      workspace_id: "ws-1",
+      request_id: 7,
+      method: "item/commandExecution/requestApproval",
+      params: { threadId: "thread-1", command: "ls" },
+    });
+    expect(handlers.onRequestUserInput).toHaveBeenCalledWith(
+      expect.objectContaining({
+        workspace_id: "ws-1",
+        request_id: "input-1",
+        params: expect.objectContaining({
+          thread_id: "thread-1",
+          questions: [expect.objectContaining({ id: "q1" })],
+        }),
+      }),
+    );
+    expect(pushErrorToastMock).not.toHaveBeenCalled();
+
+    await act(async () => {
+      root.unmount();
+    });
+  });
+
   it("accepts thread/compacted without incompatibility toast", async () => {
     const handlers: Handlers = {
       onAppServerEvent: vi.fn(),
@@ -150,6 +150,7 @@ export const METHODS_ROUTED_IN_USE_APP_SERVER_EVENTS = [
   "codex/backgroundThread",
   "codex/connected",
   "codex/disconnected",
+  "codex/eventStreamSnapshot",
   "error",
   "item/agentMessage/delta",
   "item/commandExecution/outputDelta",
@@ -178,6 +179,7 @@ export const METHODS_ROUTED_IN_USE_APP_SERVER_EVENTS = [
   "authStatusChange",
   "loginChatGptComplete",
   "thread/name/updated",
+  "thread/activity",
   "thread/archived",
   "thread/status/changed",
   "thread/unarchived",
@@ -323,6 +325,111 @@ function extractModelHint(
   );
 }
 
+function buildRequestUserInput(
+  workspaceId: string,
+  requestId: string | number,
+  params: Record<string, unknown>,
+): RequestUserInputRequest {
+  const questionsRaw = Array.isArray(params.questions) ? params.questions : [];
+  const questions = questionsRaw
+    .map((entry) => {
+      const question = entry as Record<string, unknown>;
+      const optionsRaw = Array.isArray(question.options) ? question.options : [];
+      const options = optionsRaw
+        .map((option) => {
+          const record = option as Record<string, unknown>;
+          const label = String(record.label ?? "").trim();
+          const description = String(record.description ?? "").trim();
+          if (!label && !description) {
+            return null;
+          }
+          return { label, description };
+        })
+        .filter((option): option is { label: string; description: string } => Boolean(option));
+      return {
+        id: String(question.id ?? "").trim(),
+        header: String(question.header ?? ""),
+        question: String(question.question ?? ""),
+        isOther: toBooleanLike(question.isOther ?? question.is_other),
+        options: options.length ? options : undefined,
+      };
+    })
+    .filter((question) => question.id);
+  return {
+    workspace_id: workspaceId,
+    request_id: requestId,
+    params: {
+      thread_id: String(params.threadId ?? params.thread_id ?? ""),
+      turn_id: String(params.turnId ?? params.turn_id ?? ""),
+      item_id: String(params.itemId ?? params.item_id ?? ""),
+      questions,
+    },
+  };
+}
+
+/**
+ * Replays the daemon's `codex/eventStreamSnapshot` (sent on connect and after
+ * the event stream lagged) through the regular handlers, so a reconnecting
+ * client recovers connection state, running turns and unanswered requests.
+ */
+function applyEventStreamSnapshot(
+  snapshot: Record<string, unknown>,
+  handlers: AppServerEventHandlers,
+) {
+  const workspaces = Array.isArray(snapshot.workspaces) ? snapshot.workspaces : [];
+  workspaces.forEach((entry) => {
+    const workspace = asRecord(entry);
+    const workspaceId = asNonEmptyString(workspace?.id);
+    if (!workspace || !workspaceId) {
+      return;
+    }
+    if (toBooleanLike(workspace.connected)) {
+      handlers.onWorkspaceConnected?.(workspaceId);
+    } else {
+      handlers.onWorkspaceDisconnected?.(workspaceId);
+    }
+  });
+
+  const activeTurns = Array.isArray(snapshot.activeTurns) ? snapshot.activeTurns : [];
+  activeTurns.forEach((entry) => {
+    const turn = asRecord(entry);
+    const workspaceId = asNonEmptyString(turn?.workspaceId);
+    const threadId = asNonEmptyString(turn?.threadId);
+    if (!workspaceId || !threadId) {
+      return;
+    }
+    handlers.onTurnStarted?.(workspaceId, threadId, asNonEmptyString(turn?.turnId) ?? "");
+  });
+
+  const pendingRequests = Array.isArray(snapshot.pendingRequests)
+    ? snapshot.pendingRequests
+    : [];
+  pendingRequests.forEach((entry) => {
+    const request = asRecord(entry);
+    const workspaceId = asNonEmptyString(request?.workspaceId);
+    const method = asNonEmptyString(request?.method);
+    const requestId = request?.requestId;
+    if (
+      !workspaceId
+      || !method
+      || (typeof requestId !== "string" && typeof requestId !== "number")
+    ) {
+      return;
+    }
+    const params = asRecord(request?.params) ?? {};
+    if (isApprovalRequestMethod(method)) {
+      handlers.onApprovalRequest?.({
+        workspace_id: workspaceId,
+        request_id: requestId,
+        method,
+        params,
+      });
+    } else if (method === "item/tool/requestUserInput") {
+      handlers.onRequestUserInput?.(buildRequestUserInput(workspaceId, requestId, params));
+    }
+  });
+}
+
 export function useAppServerEvents(handlers: AppServerEventHandlers) {
   const handlersRef = useRef(handlers);
   const pendingAgentDeltasRef = useRef<Map<string, AgentDelta>>(new Map());
@@ -402,6 +509,12 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
         return;
       }
 
+      if (method === "codex/eventStreamSnapshot") {
+        flushAgentMessageDeltas();
+        applyEventStreamSnapshot(params, currentHandlers);
+        return;
+      }
+
       const requestId = getAppServerRequestId(payload);
       const hasRequestId = requestId !== null;
 
@@ -462,41 +575,9 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
       }
 
       if (method === "item/tool/requestUserInput" && hasRequestId) {
-        const questionsRaw = Array.isArray(params.questions) ? params.questions : [];
-        const questions = questionsRaw
-          .map((entry) => {
-            const question = entry as Record<string, unknown>;
-            const optionsRaw = Array.isArray(question.options) ? question.options : [];
-            const options = optionsRaw
-              .map((option) => {
-                const record = option as Record<string, unknown>;
-                const label = String(record.label ?? "").trim();
-                const description = String(record.description ?? "").trim();
-                if (!label && !description) {
-                  return null;
-                }
-                return { label, description };
-              })
-              .filter((option): option is { label: string; description: string } => Boolean(option));
-            return {
-              id: String(question.id ?? "").trim(),
-              header: String(question.header ?? ""),
-              question: String(question.question ?? ""),
-              isOther: toBooleanLike(question.isOther ?? question.is_other),
-              options: options.length ? options : undefined,
-            };
-          })
-          .filter((question) => question.id);
-        currentHandlers.onRequestUserInput?.({
-          workspace_id,
-          request_id: requestId as string | number,
-          params: {
-            thread_id: String(params.threadId ?? params.thread_id ?? ""),
-            turn_id: String(params.turnId ?? params.turn_id ?? ""),
-            item_id: String(params.itemId ?? params.item_id ?? ""),
-            questions,
-          },
-        });
+        currentHandlers.onRequestUserInput?.(
+          buildRequestUserInput(workspace_id, requestId as string | number, params),
+        );
         return;
       }
 
@@ -588,6 +669,13 @@ export function useAppServerEvents(handlers: AppServerEventHandlers) {
         return;
       }
 
+      // Daemon summary for a thread this client is not watching live. Turn
+      // boundaries still arrive verbatim; this only proves the workspace is
+      // alive, which was recorded above.
+      if (method === "thread/activity") {
+        return;
+      }
+
       if (method === "thread/name/updated") {
         const threadId = String(params.threadId ?? params.thread_id ?? "").trim();
         const threadNameRaw = params.threadName ?? params.thread_name ?? null;
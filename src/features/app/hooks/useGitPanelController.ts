@@ -153,9 +153,11 @@ export function useGitPanelController({
     aheadEntries: gitLogAheadEntries,
     behindEntries: gitLogBehindEntries,
     upstream: gitLogUpstream,
+    hasMore: gitLogHasMore,
     isLoading: gitLogLoading,
     error: gitLogError,
     refresh: refreshGitLog,
+    loadMore: loadMoreGitLog,
   } = useGitLog(activeWorkspace, shouldLoadGitLog);
 
   const {
@@ -300,9 +302,11 @@ export function useGitPanelController({
     gitLogAheadEntries,
     gitLogBehindEntries,
     gitLogUpstream,
+    gitLogHasMore,
     gitLogLoading,
     gitLogError,
     refreshGitLog,
+    loadMoreGitLog,
     gitCommitDiffs,
     gitCommitDiffsLoading,
     gitCommitDiffsError,
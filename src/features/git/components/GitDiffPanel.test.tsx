@@ -430,6 +430,45 @@ describe("GitDiffPanel", () => {
     expect(onModeChange).toHaveBeenCalledWith("prs");
   });
 
+  it("marks a partial commit count and loads more log pages", () => {
+    const onLoadMoreLog = vi.fn();
+    const entry: GitLogEntry = {
+      sha: "abc123",
+      shortSha: "abc123",
+      subject: "subject",
+      authorName: "author",
+      authorEmail: "author@example.com",
+      authoredAt: Date.now(),
+    };
+    const { rerender } = render(
+      <GitDiffPanel
+        {...baseProps}
+        mode="log"
+        logEntries={[entry]}
+        logTotal={1}
+        logHasMore
+        onLoadMoreLog={onLoadMoreLog}
+      />,
+    );
+
+    expect(screen.getByText("1+ 个提交")).not.toBeNull();
+    fireEvent.click(screen.getByRole("button", { name: "加载更多提交" }));
+    expect(onLoadMoreLog).toHaveBeenCalledTimes(1);
+
+    rerender(
+      <GitDiffPanel
+        {...baseProps}
+        mode="log"
+        logEntries={[entry]}
+        logTotal={1}
+        logHasMore={false}
+        onLoadMoreLog={onLoadMoreLog}
+      />,
+    );
+    expect(screen.getByText("1 个提交")).not.toBeNull();
+    expect(screen.queryByRole("button", { name: "加载更多提交" })).toBeNull();
+  });
+
   it("shows sidebar error and allows dismissing it", () => {
     render(<GitDiffPanel {...baseProps} error="fatal diff error" />);
 
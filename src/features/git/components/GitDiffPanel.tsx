@@ -74,6 +74,8 @@ type GitDiffPanelProps = {
   logAheadEntries?: GitLogEntry[];
   logBehindEntries?: GitLogEntry[];
   logUpstream?: string | null;
+  logHasMore?: boolean;
+  onLoadMoreLog?: () => void;
   issues?: GitHubIssue[];
   issuesTotal?: number;
   issuesLoading?: boolean;
@@ -173,6 +175,8 @@ export function GitDiffPanel({
   logAheadEntries = [],
   logBehindEntries = [],
   logUpstream = null,
+  logHasMore = false,
+  onLoadMoreLog,
   selectedCommitSha = null,
   onSelectCommit,
   issues = [],
@@ -540,11 +544,11 @@ export function GitDiffPanel({
     ],
   );
 
-  const logCountLabel = logTotal
-    ? `${logTotal} 个提交`
-    : logEntries.length
-      ? `${logEntries.length} 个提交`
-      : "暂无提交";
+  // Only loaded commits are counted; more pages mean the history is longer.
+  const loadedLogCount = logTotal || logEntries.length;
+  const logCountLabel = loadedLogCount
+    ? `${loadedLogCount}${logHasMore ? "+" : ""} 个提交`
+    : "暂无提交";
   const logSyncLabel = logUpstream ? `↑${logAhead} ↓${logBehind}` : "未配置上游分支";
   const logSyncTitle = logUpstream
     ? `↑ 领先远程 ${logAhead} 个提交 · ↓ 落后远程 ${logBehind} 个提交`
@@ -767,6 +771,8 @@ export function GitDiffPanel({
           logError={logError}
           logLoading={logLoading}
           logEntries={logEntries}
+          logHasMore={logHasMore}
+          onLoadMoreLog={onLoadMoreLog}
           showAheadSection={showAheadSection}
           showBehindSection={showBehindSection}
           logAheadEntries={logAheadEntries}
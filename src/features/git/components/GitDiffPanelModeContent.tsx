@@ -585,6 +585,8 @@ type GitLogModeContentProps = {
   logError: string | null | undefined;
   logLoading: boolean;
   logEntries: GitLogEntry[];
+  logHasMore?: boolean;
+  onLoadMoreLog?: () => void;
   showAheadSection: boolean;
   showBehindSection: boolean;
   logAheadEntries: GitLogEntry[];
@@ -598,6 +600,8 @@ export function GitLogModeContent({
   logError,
   logLoading,
   logEntries,
+  logHasMore = false,
+  onLoadMoreLog,
   showAheadSection,
   showBehindSection,
   logAheadEntries,
@@ -671,6 +675,16 @@ export function GitLogModeContent({
               );
             })}
           </div>
+          {logHasMore && onLoadMoreLog && (
+            <button
+              type="button"
+              className="ghost git-log-load-more"
+              onClick={onLoadMoreLog}
+              disabled={logLoading}
+            >
+              {logLoading ? "正在加载..." : "加载更多提交"}
+            </button>
+          )}
         </div>
       )}
     </div>
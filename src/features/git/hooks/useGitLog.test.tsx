@@ -185,4 +185,80 @@ describe("useGitLog", () => {
 
     expect(result.current.entries).toEqual(makeEntries("secondary"));
   });
+
+  it("appends the next page and reloads every loaded page on refresh", async () => {
+    const getGitLogMock = vi.mocked(getGitLog);
+    const firstPage = Array.from({ length: 40 }, (_, index) =>
+      makeEntries(`page1-${index}`)[0],
+    );
+    getGitLogMock
+      .mockResolvedValueOnce({
+        ...makeLogResponse("unused"),
+        entries: firstPage,
+        total: 40,
+        nextCursor: "cursor-1",
+      })
+      .mockResolvedValueOnce({
+        ...makeLogResponse("page2"),
+        nextCursor: null,
+      })
+      .mockResolvedValueOnce({
+        ...makeLogResponse("unused"),
+        entries: [...firstPage, ...makeEntries("page2")],
+        total: 41,
+      });
+
+    const { result } = renderHook(() => useGitLog(workspace, true));
+
+    await act(async () => {
+      await flushMicrotaskQueue();
+    });
+
+    expect(result.current.hasMore).toBe(true);
+
+    await act(async () => {
+      await result.current.loadMore();
+    });
+
+    expect(getGitLogMock).toHaveBeenLastCalledWith(workspace.id, 40, "cursor-1");
+    expect(result.current.entries).toEqual([...firstPage, ...makeEntries("page2")]);
+    expect(result.current.total).toBe(41);
+    expect(result.current.hasMore).toBe(false);
+
+    await act(async () => {
+      await result.current.refresh();
+    });
+
+    expect(getGitLogMock).toHaveBeenLastCalledWith(workspace.id, 41);
+    expect(result.current.entries).toHaveLength(41);
+  });
+
+  it("skips automatic refreshes once more entries are loaded than one refresh returns", async () => {
+    const getGitLogMock = vi.mocked(getGitLog);
+    const entries = Array.from({ length: 501 }, (_, index) =>
+      makeEntries(`entry-${index}`)[0],
+    );
+    getGitLogMock.mockResolvedValueOnce({
+      ...makeLogResponse("unused"),
+      entries,
+      total: 501,
+    });
+
+    const { result } = renderHook(() => useGitLog(workspace, true));
+
+    await act(async () => {
+      await flushMicrotaskQueue();
+    });
+
+    expect(result.current.entries).toHaveLength(501);
+
+    await act(async () => {
+      vi.advanceTimersByTime(10000);
+      window.dispatchEvent(new Event("focus"));
+      await flushMicrotaskQueue();
+    });
+
+    expect(getGitLogMock).toHaveBeenCalledTimes(1);
+    expect(result.current.entries).toHaveLength(501);
+  });
 });
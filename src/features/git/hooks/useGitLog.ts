@@ -10,6 +10,7 @@ type GitLogState = {
   aheadEntries: GitLogEntry[];
   behindEntries: GitLogEntry[];
   upstream: string | null;
+  nextCursor: string | null;
   isLoading: boolean;
   error: string | null;
 };
@@ -22,11 +23,17 @@ const emptyState: GitLogState = {
   aheadEntries: [],
   behindEntries: [],
   upstream: null,
+  nextCursor: null,
   isLoading: false,
   error: null,
 };
 
 const REFRESH_INTERVAL_MS = 10000;
+const LOG_PAGE_SIZE = 40;
+// Matches the backend's per-request cap (`GIT_LOG_MAX_PAGE_SIZE`); refreshes
+// reload every loaded page in one request. Past it, a reload would drop the
+// pages the user scrolled through, so only manual refreshes run.
+const MAX_REFRESH_LIMIT = 500;
 
 export function useGitLog(
   activeWorkspace: WorkspaceInfo | null,
@@ -37,6 +44,7 @@ export function useGitLog(
   const workspaceIdRef = useRef<string | null>(activeWorkspace?.id ?? null);
   const inFlightRefreshRef = useRef<Promise<void> | null>(null);
   const inFlightWorkspaceIdRef = useRef<string | null>(null);
+  const loadedCountRef = useRef(0);
 
   const refresh = useCallback(async () => {
     if (!activeWorkspace) {
@@ -53,7 +61,12 @@ export function useGitLog(
     const requestId = requestIdRef.current + 1;
     requestIdRef.current = requestId;
     setState((prev) => ({ ...prev, isLoading: true, error: null }));
-    const refreshPromise = getGitLog(workspaceId)
+    const loadedCount = Math.min(loadedCountRef.current, MAX_REFRESH_LIMIT);
+    const request =
+      loadedCount > LOG_PAGE_SIZE
+        ? getGitLog(workspaceId, loadedCount)
+        : getGitLog(workspaceId);
+    const refreshPromise = request
       .then((response) => {
         if (
           requestIdRef.current !== requestId ||
@@ -61,6 +74,7 @@ export function useGitLog(
         ) {
           return;
         }
+        loadedCountRef.current = response.entries.length;
         setState({
           entries: response.entries,
           total: response.total,
@@ -69,6 +83,7 @@ export function useGitLog(
           aheadEntries: response.aheadEntries,
           behindEntries: response.behindEntries,
           upstream: response.upstream,
+          nextCursor: response.nextCursor ?? null,
           isLoading: false,
           error: null,
         });
@@ -81,6 +96,7 @@ export function useGitLog(
         ) {
           return;
         }
+        loadedCountRef.current = 0;
         setState({
           entries: [],
           total: 0,
@@ -89,6 +105,7 @@ export function useGitLog(
           aheadEntries: [],
           behindEntries: [],
           upstream: null,
+          nextCursor: null,
           isLoading: false,
           error: error instanceof Error ? error.message : String(error),
         });
@@ -104,6 +121,50 @@ export function useGitLog(
     return refreshPromise;
   }, [activeWorkspace]);
 
+  const loadMore = useCallback(async () => {
+    const cursor = state.nextCursor;
+    if (!activeWorkspace || !cursor || state.isLoading) {
+      return;
+    }
+    const workspaceId = activeWorkspace.id;
+    const requestId = requestIdRef.current + 1;
+    requestIdRef.current = requestId;
+    setState((prev) => ({ ...prev, isLoading: true, error: null }));
+    try {
+      const response = await getGitLog(workspaceId, LOG_PAGE_SIZE, cursor);
+      if (
+        requestIdRef.current !== requestId ||
+        workspaceIdRef.current !== workspaceId
+      ) {
+        return;
+      }
+      loadedCountRef.current += response.entries.length;
+      setState((prev) => {
+        const entries = [...prev.entries, ...response.entries];
+        return {
+          ...prev,
+          entries,
+          total: entries.length,
+          nextCursor: response.nextCursor ?? null,
+          isLoading: false,
+        };
+      });
+    } catch (error) {
+      console.error("Failed to load more git log", error);
+      if (
+        requestIdRef.current !== requestId ||
+        workspaceIdRef.current !== workspaceId
+      ) {
+        return;
+      }
+      setState((prev) => ({
+        ...prev,
+        isLoading: false,
+        error: error instanceof Error ? error.message : String(error),
+      }));
+    }
+  }, [activeWorkspace, state.isLoading, state.nextCursor]);
+
   useEffect(() => {
     const workspaceId = activeWorkspace?.id ?? null;
     if (workspaceIdRef.current !== workspaceId) {
@@ -111,6 +172,7 @@ export function useGitLog(
       requestIdRef.current += 1;
       inFlightRefreshRef.current = null;
       inFlightWorkspaceIdRef.current = null;
+      loadedCountRef.current = 0;
       setState(emptyState);
     }
   }, [activeWorkspace?.id]);
@@ -120,7 +182,10 @@ export function useGitLog(
       return;
     }
     const fetchLog = () => {
-      if (document.visibilityState !== "visible") {
+      if (
+        document.visibilityState !== "visible" ||
+        loadedCountRef.current > MAX_REFRESH_LIMIT
+      ) {
         return;
       }
       refresh().catch(() => {});
@@ -146,8 +211,10 @@ export function useGitLog(
     aheadEntries: state.aheadEntries,
     behindEntries: state.behindEntries,
     upstream: state.upstream,
+    hasMore: state.nextCursor !== null,
     isLoading: state.isLoading,
     error: state.error,
     refresh,
+    loadMore,
   };
 }
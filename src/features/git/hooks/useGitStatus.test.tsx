@@ -2,11 +2,22 @@
 import { act, renderHook } from "@testing-library/react";
 import { afterEach, beforeEach, describe, expect, it, vi } from "vitest";
 import type { WorkspaceInfo } from "../../../types";
-import { getGitStatus } from "../../../services/tauri";
+import { subscribeGitStatusChanged } from "../../../services/events";
+import {
+  getGitStatus,
+  unwatchGitStatus,
+  watchGitStatus,
+} from "../../../services/tauri";
 import { useGitStatus } from "./useGitStatus";
 
 vi.mock("../../../services/tauri", () => ({
   getGitStatus: vi.fn(),
+  watchGitStatus: vi.fn(() => Promise.reject(new Error("unsupported"))),
+  unwatchGitStatus: vi.fn(() => Promise.resolve()),
+}));
+
+vi.mock("../../../services/events", () => ({
+  subscribeGitStatusChanged: vi.fn(() => () => {}),
 }));
 
 const workspace: WorkspaceInfo = {
@@ -218,4 +229,51 @@ describe("useGitStatus", () => {
 
     unmount();
   });
+
+  it("applies pushed status and polls slowly while watched", async () => {
+    const getGitStatusMock = vi.mocked(getGitStatus);
+    getGitStatusMock
+      .mockResolvedValueOnce(makeStatus("main", 1, 0))
+      .mockResolvedValueOnce(makeStatus("polled", 2, 0));
+    vi.mocked(watchGitStatus).mockResolvedValueOnce(undefined);
+
+    const { result, unmount } = renderHook(
+      ({ active }: { active: WorkspaceInfo | null }) => useGitStatus(active),
+      { initialProps: { active: workspace } },
+    );
+    await act(async () => {
+      await flushMicrotaskQueue();
+    });
+
+    expect(watchGitStatus).toHaveBeenCalledWith(workspace.id);
+    expect(result.current.status.branchName).toBe("main");
+
+    const listener = vi.mocked(subscribeGitStatusChanged).mock.calls[0][0];
+    act(() => {
+      listener({ workspaceId: secondaryWorkspace.id, status: makeStatus("other") });
+    });
+    expect(result.current.status.branchName).toBe("main");
+    act(() => {
+      listener({ workspaceId: workspace.id, status: makeStatus("pushed", 7, 0) });
+    });
+    expect(result.current.status.branchName).toBe("pushed");
+    expect(result.current.status.totalAdditions).toBe(7);
+
+    await act(async () => {
+      vi.advanceTimersByTime(12000);
+    });
+    expect(getGitStatusMock).toHaveBeenCalledTimes(1);
+
+    await act(async () => {
+      vi.advanceTimersByTime(18000);
+    });
+    await act(async () => {
+      await flushMicrotaskQueue();
+    });
+    expect(getGitStatusMock).toHaveBeenCalledTimes(2);
+    expect(result.current.status.branchName).toBe("polled");
+
+    unmount();
+    expect(unwatchGitStatus).toHaveBeenCalledWith(workspace.id);
+  });
 });
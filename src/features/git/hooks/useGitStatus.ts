@@ -1,6 +1,11 @@
 import { useCallback, useEffect, useRef, useState } from "react";
 import type { GitFileStatus, WorkspaceInfo } from "../../../types";
-import { getGitStatus } from "../../../services/tauri";
+import { subscribeGitStatusChanged } from "../../../services/events";
+import {
+  getGitStatus,
+  unwatchGitStatus,
+  watchGitStatus,
+} from "../../../services/tauri";
 import { BoundedCache } from "../../../utils/boundedCache";
 
 type GitStatusState = {
@@ -27,6 +32,9 @@ const FAST_POLL_INTERVAL_MS = 3000;
 const ERROR_POLL_INTERVAL_MS = 5000;
 const IDLE_POLL_INTERVAL_MS = 12000;
 const HIDDEN_POLL_INTERVAL_MS = 20000;
+// While the backend watches the workspace it pushes changes; polling only
+// covers events lost to a reconnect.
+const WATCHED_POLL_INTERVAL_MS = 30000;
 const GIT_STATUS_CACHE_MAX_ENTRIES = 64;
 const GIT_STATUS_CACHE_TTL_MS = 60 * 1000;
 
@@ -50,6 +58,7 @@ export function useGitStatus(
   const inFlightRefreshRef = useRef<Promise<void> | null>(null);
   const inFlightWorkspaceIdRef = useRef<string | null>(null);
   const statusRef = useRef<GitStatusState>(emptyStatus);
+  const watchingRef = useRef(false);
   const workspaceId = activeWorkspace?.id ?? null;
   const preferFastPolling = options.preferFastPolling ?? false;
 
@@ -67,6 +76,21 @@ export function useGitStatus(
     [],
   );
 
+  const applyStatus = useCallback(
+    (targetWorkspaceId: string, data: Omit<GitStatusState, "error">) => {
+      const cached = cachedStatusRef.current.get(targetWorkspaceId);
+      const resolvedBranchName = resolveBranchName(data.branchName, cached);
+      const nextStatus = {
+        ...data,
+        branchName: resolvedBranchName,
+        error: null,
+      };
+      setStatus(nextStatus);
+      cachedStatusRef.current.set(targetWorkspaceId, nextStatus);
+    },
+    [resolveBranchName],
+  );
+
   const refresh = useCallback(() => {
     if (!workspaceId) {
       setStatus(emptyStatus);
@@ -88,15 +112,7 @@ export function useGitStatus(
         ) {
           return;
         }
-        const cached = cachedStatusRef.current.get(workspaceId);
-        const resolvedBranchName = resolveBranchName(data.branchName, cached);
-        const nextStatus = {
-          ...data,
-          branchName: resolvedBranchName,
-          error: null,
-        };
-        setStatus(nextStatus);
-        cachedStatusRef.current.set(workspaceId, nextStatus);
+        applyStatus(workspaceId, data);
       })
       .catch((err) => {
         console.error("Failed to load git status", err);
@@ -122,7 +138,7 @@ export function useGitStatus(
     inFlightRefreshRef.current = refreshPromise;
     inFlightWorkspaceIdRef.current = workspaceId;
     return refreshPromise;
-  }, [resolveBranchName, workspaceId]);
+  }, [applyStatus, workspaceId]);
 
   useEffect(() => {
     statusRef.current = status;
@@ -133,6 +149,9 @@ export function useGitStatus(
       return HIDDEN_POLL_INTERVAL_MS;
     }
     const current = statusRef.current;
+    if (watchingRef.current && !current.error) {
+      return WATCHED_POLL_INTERVAL_MS;
+    }
     const hasChanges =
       current.files.length > 0 ||
       current.stagedFiles.length > 0 ||
@@ -161,6 +180,44 @@ export function useGitStatus(
     }
   }, [workspaceId]);
 
+  useEffect(() => {
+    if (!workspaceId) {
+      return;
+    }
+    let disposed = false;
+    const unsubscribe = subscribeGitStatusChanged((event) => {
+      if (
+        event.workspaceId !== workspaceId ||
+        workspaceIdRef.current !== workspaceId
+      ) {
+        return;
+      }
+      // A pushed status is newer than any poll still in flight.
+      requestIdRef.current += 1;
+      applyStatus(workspaceId, event.status);
+    });
+    watchGitStatus(workspaceId)
+      .then(() => {
+        if (disposed) {
+          unwatchGitStatus(workspaceId).catch(() => {});
+          return;
+        }
+        watchingRef.current = true;
+      })
+      .catch(() => {
+        // Older backends cannot watch; keep polling.
+      });
+
+    return () => {
+      disposed = true;
+      unsubscribe();
+      if (watchingRef.current) {
+        watchingRef.current = false;
+        unwatchGitStatus(workspaceId).catch(() => {});
+      }
+    };
+  }, [applyStatus, workspaceId]);
+
   useEffect(() => {
     if (!workspaceId) {
       setStatus(emptyStatus);
@@ -103,6 +103,8 @@ function createOptions(overrides: Record<string, unknown> = {}) {
     gitLogAheadEntries: [],
     gitLogBehindEntries: [],
     gitLogUpstream: null,
+    gitLogHasMore: false,
+    onLoadMoreGitLog: vi.fn(),
     selectedCommitSha: null,
     onSelectCommit: vi.fn(),
     gitIssues: [],
@@ -116,6 +116,8 @@ export function buildGitNodes(options: LayoutNodesOptions): GitLayoutNodes {
           logAheadEntries={options.gitLogAheadEntries}
           logBehindEntries={options.gitLogBehindEntries}
           logUpstream={options.gitLogUpstream}
+          logHasMore={options.gitLogHasMore}
+          onLoadMoreLog={options.onLoadMoreGitLog}
           selectedCommitSha={options.selectedCommitSha}
           onSelectCommit={options.onSelectCommit}
           issues={options.gitIssues}
@@ -286,6 +286,8 @@ export type LayoutNodesOptions = {
   gitLogAheadEntries: GitLogEntry[];
   gitLogBehindEntries: GitLogEntry[];
   gitLogUpstream: string | null;
+  gitLogHasMore: boolean;
+  onLoadMoreGitLog: () => void;
   selectedCommitSha: string | null;
   onSelectCommit: (entry: GitLogEntry) => void;
   gitLogError: string | null;
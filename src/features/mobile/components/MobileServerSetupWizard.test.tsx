@@ -10,6 +10,7 @@ function buildProps(
   return {
     provider: "tcp",
     remoteHostDraft: "desktop.tailnet.ts.net:4732",
+    remoteSocketPathDraft: "",
     orbitWsUrlDraft: "",
     remoteTokenDraft: "seed-token",
     busy: false,
@@ -18,6 +19,7 @@ function buildProps(
     statusError: false,
     onProviderChange: vi.fn(),
     onRemoteHostChange: vi.fn(),
+    onRemoteSocketPathChange: vi.fn(),
     onOrbitWsUrlChange: vi.fn(),
     onRemoteTokenChange: vi.fn(),
     onConnectTest: vi.fn(),
@@ -156,4 +158,24 @@ describe("MobileServerSetupWizard", () => {
 
     expect(onOrbitWsUrlChange).toHaveBeenCalledWith("wss://new.example/ws");
   });
+
+  it("shows the socket path field in unix socket step", () => {
+    const onRemoteSocketPathChange = vi.fn();
+
+    render(
+      <MobileServerSetupWizard
+        {...buildProps({
+          provider: "unix",
+          onRemoteSocketPathChange,
+        })}
+      />,
+    );
+
+    expect(screen.queryByLabelText("Tailscale host")).toBeNull();
+    fireEvent.change(screen.getByLabelText("Socket path"), {
+      target: { value: "/run/codex-monitor/daemon.sock" },
+    });
+
+    expect(onRemoteSocketPathChange).toHaveBeenCalledWith("/run/codex-monitor/daemon.sock");
+  });
 });
@@ -5,6 +5,7 @@ import type { AppSettings } from "../../../types";
 export type MobileServerSetupWizardProps = {
   provider: AppSettings["remoteBackendProvider"];
   remoteHostDraft: string;
+  remoteSocketPathDraft: string;
   orbitWsUrlDraft: string;
   remoteTokenDraft: string;
   busy: boolean;
@@ -13,6 +14,7 @@ export type MobileServerSetupWizardProps = {
   statusError: boolean;
   onProviderChange: (provider: AppSettings["remoteBackendProvider"]) => void;
   onRemoteHostChange: (value: string) => void;
+  onRemoteSocketPathChange: (value: string) => void;
   onOrbitWsUrlChange: (value: string) => void;
   onRemoteTokenChange: (value: string) => void;
   onConnectTest: () => void;
@@ -21,6 +23,7 @@ export type MobileServerSetupWizardProps = {
 export function MobileServerSetupWizard({
   provider,
   remoteHostDraft,
+  remoteSocketPathDraft,
   orbitWsUrlDraft,
   remoteTokenDraft,
   busy,
@@ -29,6 +32,7 @@ export function MobileServerSetupWizard({
   statusError,
   onProviderChange,
   onRemoteHostChange,
+  onRemoteSocketPathChange,
   onOrbitWsUrlChange,
   onRemoteTokenChange,
   onConnectTest,
@@ -63,6 +67,7 @@ export function MobileServerSetupWizard({
         >
           <option value="tcp">TCP</option>
           <option value="orbit">Orbit</option>
+          <option value="unix">Unix socket</option>
         </select>
 
         {provider === "tcp" && (
@@ -85,6 +90,26 @@ export function MobileServerSetupWizard({
           </>
         )}
 
+        {provider === "unix" && (
+          <>
+            <label className="mobile-setup-wizard-label" htmlFor="mobile-setup-socket-path">
+              Socket path
+            </label>
+            <input
+              id="mobile-setup-socket-path"
+              className="mobile-setup-wizard-input"
+              value={remoteSocketPathDraft}
+              placeholder="/run/codex-monitor/daemon.sock"
+              autoComplete="off"
+              autoCorrect="off"
+              autoCapitalize="off"
+              spellCheck={false}
+              onChange={(event) => onRemoteSocketPathChange(event.target.value)}
+              disabled={busy || checking}
+            />
+          </>
+        )}
+
         {provider === "orbit" && (
           <>
             <label className="mobile-setup-wizard-label" htmlFor="mobile-setup-orbit-url">
@@ -142,7 +167,9 @@ export function MobileServerSetupWizard({
         <div className="mobile-setup-wizard-hint">
           {provider === "tcp"
             ? "Use the Tailscale host from desktop Server settings and keep the desktop daemon running."
-            : "Use the Orbit websocket URL and token from desktop Server settings."}
+            : provider === "unix"
+              ? "Use the socket path the daemon was started with (--listen-unix). The token is optional."
+              : "Use the Orbit websocket URL and token from desktop Server settings."}
         </div>
       </div>
     </ModalShell>
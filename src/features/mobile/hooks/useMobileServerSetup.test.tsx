@@ -148,6 +148,40 @@ describe("useMobileServerSetup", () => {
     expect(refreshWorkspaces).toHaveBeenCalled();
   });
 
+  it("saves the unix socket path without requiring a token", async () => {
+    const queueSaveSettings = vi.fn().mockImplementation(async (next) => next);
+    const refreshWorkspaces = vi.fn().mockResolvedValue(undefined);
+
+    const { result } = renderHook(() =>
+      useMobileServerSetup({
+        appSettings: buildSettings(),
+        appSettingsLoading: true,
+        queueSaveSettings,
+        refreshWorkspaces,
+      }),
+    );
+
+    await act(async () => {
+      result.current.mobileSetupWizardProps.onProviderChange("unix");
+      result.current.mobileSetupWizardProps.onRemoteSocketPathChange(
+        "  /run/codex-monitor/daemon.sock  ",
+      );
+    });
+    await act(async () => {
+      result.current.mobileSetupWizardProps.onConnectTest();
+    });
+
+    expect(queueSaveSettings).toHaveBeenCalledWith(
+      expect.objectContaining({
+        backendMode: "remote",
+        remoteBackendProvider: "unix",
+        remoteBackendSocketPath: "/run/codex-monitor/daemon.sock",
+        remoteBackendToken: null,
+      }),
+    );
+    expect(result.current.mobileSetupWizardProps.statusError).toBe(false);
+  });
+
   it("surfaces save/connect failures as status errors", async () => {
     const queueSaveSettings = vi.fn().mockRejectedValue(new Error("save failed"));
     const refreshWorkspaces = vi.fn().mockResolvedValue(undefined);
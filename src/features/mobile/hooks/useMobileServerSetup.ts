@@ -19,6 +19,10 @@ type UseMobileServerSetupResult = {
 };
 
 function isRemoteServerConfigured(settings: AppSettings): boolean {
+  if (settings.remoteBackendProvider === "unix") {
+    // Socket access is guarded by file permissions; the token is optional.
+    return Boolean(settings.remoteBackendSocketPath?.trim());
+  }
   const tokenConfigured = Boolean(settings.remoteBackendToken?.trim());
   if (!tokenConfigured) {
     return false;
@@ -33,6 +37,9 @@ function defaultMobileSetupMessage(provider: AppSettings["remoteBackendProvider"
   if (provider === "orbit") {
     return "Enter your Orbit websocket URL and token, then run Connect & test.";
   }
+  if (provider === "unix") {
+    return "Enter the daemon socket path, then run Connect & test.";
+  }
   return "Enter your desktop Tailscale host and token, then run Connect & test.";
 }
 
@@ -48,6 +55,9 @@ export function useMobileServerSetup({
     appSettings.remoteBackendProvider,
   );
   const [remoteHostDraft, setRemoteHostDraft] = useState(appSettings.remoteBackendHost);
+  const [remoteSocketPathDraft, setRemoteSocketPathDraft] = useState(
+    appSettings.remoteBackendSocketPath ?? "",
+  );
   const [orbitWsUrlDraft, setOrbitWsUrlDraft] = useState(appSettings.orbitWsUrl ?? "");
   const [remoteTokenDraft, setRemoteTokenDraft] = useState(appSettings.remoteBackendToken ?? "");
   const [busy, setBusy] = useState(false);
@@ -62,12 +72,14 @@ export function useMobileServerSetup({
     }
     setProviderDraft(appSettings.remoteBackendProvider);
     setRemoteHostDraft(appSettings.remoteBackendHost);
+    setRemoteSocketPathDraft(appSettings.remoteBackendSocketPath ?? "");
     setOrbitWsUrlDraft(appSettings.orbitWsUrl ?? "");
     setRemoteTokenDraft(appSettings.remoteBackendToken ?? "");
   }, [
     appSettings.orbitWsUrl,
     appSettings.remoteBackendHost,
     appSettings.remoteBackendProvider,
+    appSettings.remoteBackendSocketPath,
     appSettings.remoteBackendToken,
     isMobileRuntime,
   ]);
@@ -116,9 +128,16 @@ export function useMobileServerSetup({
       const nextHost = remoteHostDraft.trim();
       const nextToken = remoteTokenDraft.trim() ? remoteTokenDraft.trim() : null;
       const nextOrbitWsUrl = orbitWsUrlDraft.trim() ? orbitWsUrlDraft.trim() : null;
-      const missingEndpoint = nextProvider === "orbit" ? !nextOrbitWsUrl : !nextHost.trim();
+      const nextSocketPath = remoteSocketPathDraft.trim() ? remoteSocketPathDraft.trim() : null;
+      const missingEndpoint =
+        nextProvider === "orbit"
+          ? !nextOrbitWsUrl
+          : nextProvider === "unix"
+            ? !nextSocketPath
+            : !nextHost.trim();
+      const missingToken = nextProvider !== "unix" && !nextToken;
 
-      if (missingEndpoint || !nextToken) {
+      if (missingEndpoint || missingToken) {
         setMobileServerReady(false);
         setStatusError(true);
         setStatusMessage(defaultMobileSetupMessage(nextProvider));
@@ -134,6 +153,7 @@ export function useMobileServerSetup({
           backendMode: "remote",
           remoteBackendProvider: nextProvider,
           remoteBackendHost: nextHost,
+          remoteBackendSocketPath: nextSocketPath,
           remoteBackendToken: nextToken,
           orbitWsUrl: nextOrbitWsUrl,
         });
@@ -156,6 +176,7 @@ export function useMobileServerSetup({
     providerDraft,
     queueSaveSettings,
     remoteHostDraft,
+    remoteSocketPathDraft,
     remoteTokenDraft,
     runConnectivityCheck,
   ]);
@@ -216,6 +237,7 @@ export function useMobileServerSetup({
     mobileSetupWizardProps: {
       provider: providerDraft,
       remoteHostDraft,
+      remoteSocketPathDraft,
       orbitWsUrlDraft,
       remoteTokenDraft,
       busy,
@@ -224,6 +246,7 @@ export function useMobileServerSetup({
       statusError,
       onProviderChange: setProviderDraft,
       onRemoteHostChange: setRemoteHostDraft,
+      onRemoteSocketPathChange: setRemoteSocketPathDraft,
       onOrbitWsUrlChange: setOrbitWsUrlDraft,
       onRemoteTokenChange: setRemoteTokenDraft,
       onConnectTest,
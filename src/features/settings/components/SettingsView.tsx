@@ -200,6 +200,9 @@ export function SettingsView({
   const [codexPathDraft, setCodexPathDraft] = useState(appSettings.codexBin ?? "");
   const [codexArgsDraft, setCodexArgsDraft] = useState(appSettings.codexArgs ?? "");
   const [remoteHostDraft, setRemoteHostDraft] = useState(appSettings.remoteBackendHost);
+  const [remoteSocketPathDraft, setRemoteSocketPathDraft] = useState(
+    appSettings.remoteBackendSocketPath ?? "",
+  );
   const [remoteTokenDraft, setRemoteTokenDraft] = useState(appSettings.remoteBackendToken ?? "");
   const [orbitWsUrlDraft, setOrbitWsUrlDraft] = useState(appSettings.orbitWsUrl ?? "");
   const [orbitAuthUrlDraft, setOrbitAuthUrlDraft] = useState(appSettings.orbitAuthUrl ?? "");
@@ -402,6 +405,10 @@ export function SettingsView({
     setRemoteHostDraft(appSettings.remoteBackendHost);
   }, [appSettings.remoteBackendHost]);
 
+  useEffect(() => {
+    setRemoteSocketPathDraft(appSettings.remoteBackendSocketPath ?? "");
+  }, [appSettings.remoteBackendSocketPath]);
+
   useEffect(() => {
     setRemoteTokenDraft(appSettings.remoteBackendToken ?? "");
   }, [appSettings.remoteBackendToken]);
@@ -606,17 +613,21 @@ export function SettingsView({
   const updateRemoteBackendSettings = useCallback(
     async ({
       host,
+      socketPath,
       token,
       provider,
       orbitWsUrl,
     }: {
       host?: string;
+      socketPath?: string | null;
       token?: string | null;
       provider?: AppSettings["remoteBackendProvider"];
       orbitWsUrl?: string | null;
     }) => {
       const latestSettings = latestSettingsRef.current;
       const nextHost = host ?? latestSettings.remoteBackendHost;
+      const nextSocketPath =
+        socketPath === undefined ? latestSettings.remoteBackendSocketPath ?? null : socketPath;
       const nextToken =
         token === undefined ? latestSettings.remoteBackendToken : token;
       const nextProvider = provider ?? latestSettings.remoteBackendProvider;
@@ -625,6 +636,7 @@ export function SettingsView({
       const nextSettings: AppSettings = {
         ...latestSettings,
         remoteBackendHost: nextHost,
+        remoteBackendSocketPath: nextSocketPath,
         remoteBackendToken: nextToken,
         remoteBackendProvider: nextProvider,
         orbitWsUrl: nextOrbitWsUrl,
@@ -636,6 +648,7 @@ export function SettingsView({
       };
       const unchanged =
         nextSettings.remoteBackendHost === latestSettings.remoteBackendHost &&
+        nextSettings.remoteBackendSocketPath === (latestSettings.remoteBackendSocketPath ?? null) &&
         nextSettings.remoteBackendToken === latestSettings.remoteBackendToken &&
         nextSettings.orbitWsUrl === latestSettings.orbitWsUrl &&
         nextSettings.backendMode === latestSettings.backendMode &&
@@ -659,6 +672,12 @@ export function SettingsView({
     await applyRemoteHost(remoteHostDraft);
   };
 
+  const handleCommitRemoteSocketPath = async () => {
+    const nextSocketPath = normalizeOverrideValue(remoteSocketPathDraft);
+    setRemoteSocketPathDraft(nextSocketPath ?? "");
+    await updateRemoteBackendSettings({ socketPath: nextSocketPath });
+  };
+
   const handleCommitRemoteToken = async () => {
     const nextToken = remoteTokenDraft.trim() ? remoteTokenDraft.trim() : null;
     setRemoteTokenDraft(nextToken ?? "");
@@ -681,6 +700,16 @@ export function SettingsView({
             host: nextHost,
             token: nextToken,
           });
+        } else if (provider === "unix") {
+          const nextSocketPath = normalizeOverrideValue(remoteSocketPathDraft);
+          setRemoteSocketPathDraft(nextSocketPath ?? "");
+          if (!nextSocketPath) {
+            throw new Error("请填写守护进程套接字路径。");
+          }
+          await updateRemoteBackendSettings({
+            socketPath: nextSocketPath,
+            token: nextToken,
+          });
         } else {
           const nextOrbitWsUrl = normalizeOverrideValue(orbitWsUrlDraft);
           setOrbitWsUrlDraft(nextOrbitWsUrl ?? "");
@@ -720,6 +749,7 @@ export function SettingsView({
     mobilePlatform,
     orbitWsUrlDraft,
     remoteHostDraft,
+    remoteSocketPathDraft,
     remoteTokenDraft,
   ]);
 
@@ -1589,6 +1619,7 @@ export function SettingsView({
               appSettings={appSettings}
               onUpdateAppSettings={onUpdateAppSettings}
               remoteHostDraft={remoteHostDraft}
+              remoteSocketPathDraft={remoteSocketPathDraft}
               remoteTokenDraft={remoteTokenDraft}
               orbitWsUrlDraft={orbitWsUrlDraft}
               orbitAuthUrlDraft={orbitAuthUrlDraft}
@@ -1608,6 +1639,7 @@ export function SettingsView({
               tcpDaemonStatus={tcpDaemonStatus}
               tcpDaemonBusyAction={tcpDaemonBusyAction}
               onSetRemoteHostDraft={setRemoteHostDraft}
+              onSetRemoteSocketPathDraft={setRemoteSocketPathDraft}
               onSetRemoteTokenDraft={setRemoteTokenDraft}
               onSetOrbitWsUrlDraft={setOrbitWsUrlDraft}
               onSetOrbitAuthUrlDraft={setOrbitAuthUrlDraft}
@@ -1615,6 +1647,7 @@ export function SettingsView({
               onSetOrbitAccessClientIdDraft={setOrbitAccessClientIdDraft}
               onSetOrbitAccessClientSecretRefDraft={setOrbitAccessClientSecretRefDraft}
               onCommitRemoteHost={handleCommitRemoteHost}
+              onCommitRemoteSocketPath={handleCommitRemoteSocketPath}
               onCommitRemoteToken={handleCommitRemoteToken}
               onChangeRemoteProvider={handleChangeRemoteProvider}
               onRefreshTailscaleStatus={handleRefreshTailscaleStatus}
@@ -26,6 +26,7 @@ function buildProps(
     mobileConnectStatusText: null,
     mobileConnectStatusError: false,
     remoteHostDraft: "127.0.0.1:4732",
+    remoteSocketPathDraft: "",
     remoteTokenDraft: "",
     orbitWsUrlDraft: "",
     orbitAuthUrlDraft: "",
@@ -45,6 +46,7 @@ function buildProps(
     tcpDaemonStatus: null,
     tcpDaemonBusyAction: null,
     onSetRemoteHostDraft: vi.fn(),
+    onSetRemoteSocketPathDraft: vi.fn(),
     onSetRemoteTokenDraft: vi.fn(),
     onSetOrbitWsUrlDraft: vi.fn(),
     onSetOrbitAuthUrlDraft: vi.fn(),
@@ -52,6 +54,7 @@ function buildProps(
     onSetOrbitAccessClientIdDraft: vi.fn(),
     onSetOrbitAccessClientSecretRefDraft: vi.fn(),
     onCommitRemoteHost: vi.fn(async () => undefined),
+    onCommitRemoteSocketPath: vi.fn(async () => undefined),
     onCommitRemoteToken: vi.fn(async () => undefined),
     onChangeRemoteProvider: vi.fn(async () => undefined),
     onRefreshTailscaleStatus: vi.fn(),
@@ -121,6 +124,29 @@ describe("SettingsServerSection", () => {
     expect(onChangeRemoteProvider).toHaveBeenCalledWith("orbit");
   });
 
+  it("edits and commits the socket path when provider is unix", () => {
+    const onSetRemoteSocketPathDraft = vi.fn();
+    const onCommitRemoteSocketPath = vi.fn(async () => undefined);
+
+    render(
+      <SettingsServerSection
+        {...buildProps({
+          appSettings: createAppSettings({ remoteBackendProvider: "unix" }),
+          onSetRemoteSocketPathDraft,
+          onCommitRemoteSocketPath,
+        })}
+      />,
+    );
+
+    expect(screen.queryByLabelText("远程后端 host")).toBeNull();
+    const input = screen.getByLabelText("远程后端套接字路径");
+    fireEvent.change(input, { target: { value: "/run/codex-monitor/daemon.sock" } });
+    fireEvent.keyDown(input, { key: "Enter" });
+
+    expect(onSetRemoteSocketPathDraft).toHaveBeenCalledWith("/run/codex-monitor/daemon.sock");
+    expect(onCommitRemoteSocketPath).toHaveBeenCalled();
+  });
+
   it("toggles daemon keep-running switch via settings update", () => {
     const onUpdateAppSettings = vi.fn(async () => undefined);
     const appSettings = createAppSettings({ keepDaemonRunningAfterAppClose: false });
@@ -15,6 +15,7 @@ type SettingsServerSectionProps = {
   mobileConnectStatusText: string | null;
   mobileConnectStatusError: boolean;
   remoteHostDraft: string;
+  remoteSocketPathDraft: string;
   remoteTokenDraft: string;
   orbitWsUrlDraft: string;
   orbitAuthUrlDraft: string;
@@ -34,6 +35,7 @@ type SettingsServerSectionProps = {
   tcpDaemonStatus: TcpDaemonStatus | null;
   tcpDaemonBusyAction: "start" | "stop" | "status" | null;
   onSetRemoteHostDraft: Dispatch<SetStateAction<string>>;
+  onSetRemoteSocketPathDraft: Dispatch<SetStateAction<string>>;
   onSetRemoteTokenDraft: Dispatch<SetStateAction<string>>;
   onSetOrbitWsUrlDraft: Dispatch<SetStateAction<string>>;
   onSetOrbitAuthUrlDraft: Dispatch<SetStateAction<string>>;
@@ -41,6 +43,7 @@ type SettingsServerSectionProps = {
   onSetOrbitAccessClientIdDraft: Dispatch<SetStateAction<string>>;
   onSetOrbitAccessClientSecretRefDraft: Dispatch<SetStateAction<string>>;
   onCommitRemoteHost: () => Promise<void>;
+  onCommitRemoteSocketPath: () => Promise<void>;
   onCommitRemoteToken: () => Promise<void>;
   onChangeRemoteProvider: (provider: AppSettings["remoteBackendProvider"]) => Promise<void>;
   onRefreshTailscaleStatus: () => void;
@@ -71,6 +74,7 @@ export function SettingsServerSection({
   mobileConnectStatusText,
   mobileConnectStatusError,
   remoteHostDraft,
+  remoteSocketPathDraft,
   remoteTokenDraft,
   orbitWsUrlDraft,
   orbitAuthUrlDraft,
@@ -90,6 +94,7 @@ export function SettingsServerSection({
   tcpDaemonStatus,
   tcpDaemonBusyAction,
   onSetRemoteHostDraft,
+  onSetRemoteSocketPathDraft,
   onSetRemoteTokenDraft,
   onSetOrbitWsUrlDraft,
   onSetOrbitAuthUrlDraft,
@@ -97,6 +102,7 @@ export function SettingsServerSection({
   onSetOrbitAccessClientIdDraft,
   onSetOrbitAccessClientSecretRefDraft,
   onCommitRemoteHost,
+  onCommitRemoteSocketPath,
   onCommitRemoteToken,
   onChangeRemoteProvider,
   onRefreshTailscaleStatus,
@@ -205,6 +211,7 @@ export function SettingsServerSection({
           >
             <option value="tcp">{isMobileSimplified ? "TCP" : "TCP（开发中）"}</option>
             <option value="orbit">{isMobileSimplified ? "Orbit" : "Orbit（开发中）"}</option>
+            <option value="unix">{isMobileSimplified ? "Unix 套接字" : "Unix 套接字（开发中）"}</option>
           </select>
           <div className="settings-help">
             {isMobileSimplified
@@ -454,6 +461,50 @@ export function SettingsServerSection({
           </>
         )}
 
+        {appSettings.remoteBackendProvider === "unix" && (
+          <div className="settings-field">
+            <div className="settings-field-label">远程后端</div>
+            <div className="settings-field-row">
+              <input
+                className="settings-input settings-input--compact"
+                value={remoteSocketPathDraft}
+                placeholder="/run/codex-monitor/daemon.sock"
+                onChange={(event) => onSetRemoteSocketPathDraft(event.target.value)}
+                onBlur={() => {
+                  void onCommitRemoteSocketPath();
+                }}
+                onKeyDown={(event) => {
+                  if (event.key === "Enter") {
+                    event.preventDefault();
+                    void onCommitRemoteSocketPath();
+                  }
+                }}
+                aria-label="远程后端套接字路径"
+              />
+              <input
+                type="password"
+                className="settings-input settings-input--compact"
+                value={remoteTokenDraft}
+                placeholder="令牌（可选）"
+                onChange={(event) => onSetRemoteTokenDraft(event.target.value)}
+                onBlur={() => {
+                  void onCommitRemoteToken();
+                }}
+                onKeyDown={(event) => {
+                  if (event.key === "Enter") {
+                    event.preventDefault();
+                    void onCommitRemoteToken();
+                  }
+                }}
+                aria-label="远程后端 token"
+              />
+            </div>
+            <div className="settings-help">
+              填写守护进程 <code>--listen-unix</code> 使用的套接字路径。套接字仅对所属用户开放，令牌可留空。
+            </div>
+          </div>
+        )}
+
         {appSettings.remoteBackendProvider === "orbit" && (
           <>
             <div className="settings-field">
@@ -65,6 +65,26 @@ describe("useThreadCodexParams", () => {
     });
   });
 
+  it("keeps every supported access mode when loading entries", () => {
+    vi.mocked(loadThreadCodexParams).mockReturnValue({
+      "ws-1:thread-1": { accessMode: "read-only", updatedAt: 1 },
+      "ws-1:thread-2": { accessMode: "workspace-write", updatedAt: 1 },
+      "ws-1:thread-3": { accessMode: "full-access", updatedAt: 1 },
+    } as never);
+
+    const { result } = renderHook(() => useThreadCodexParams());
+
+    expect(result.current.getThreadCodexParams("ws-1", "thread-1")?.accessMode).toBe(
+      "read-only",
+    );
+    expect(result.current.getThreadCodexParams("ws-1", "thread-2")?.accessMode).toBe(
+      "workspace-write",
+    );
+    expect(result.current.getThreadCodexParams("ws-1", "thread-3")?.accessMode).toBe(
+      "full-access",
+    );
+  });
+
   it("deletes existing entries and ignores missing keys", () => {
     vi.mocked(loadThreadCodexParams).mockReturnValue({
       "ws-1:thread-1": {
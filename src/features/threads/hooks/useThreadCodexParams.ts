@@ -36,9 +36,16 @@ const DEFAULT_ENTRY: ThreadCodexParams = {
   updatedAt: 0,
 };
 
+const ACCESS_MODES: readonly AccessMode[] = [
+  "current",
+  "read-only",
+  "workspace-write",
+  "full-access",
+];
+
 function coerceAccessMode(value: unknown): AccessMode | null {
-  if (value === "current") {
-    return value;
+  if (ACCESS_MODES.includes(value as AccessMode)) {
+    return value as AccessMode;
   }
   return null;
 }
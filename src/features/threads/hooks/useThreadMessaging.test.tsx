@@ -945,6 +945,33 @@ describe("useThreadMessaging branch coverage", () => {
     );
   });
 
+  it("forwards the selected access mode and lets per-send options override it", async () => {
+    const options = createOptions({ accessMode: "read-only" });
+    const { result } = renderHook(() => useThreadMessaging(options));
+
+    await act(async () => {
+      await result.current.sendUserMessageToThread(workspace, "thread-1", "hello", []);
+    });
+    expect(sendUserMessageService).toHaveBeenLastCalledWith(
+      "ws-1",
+      "thread-1",
+      "hello",
+      expect.objectContaining({ accessMode: "read-only" }),
+    );
+
+    await act(async () => {
+      await result.current.sendUserMessageToThread(workspace, "thread-1", "again", [], {
+        accessMode: "full-access",
+      });
+    });
+    expect(sendUserMessageService).toHaveBeenLastCalledWith(
+      "ws-1",
+      "thread-1",
+      "again",
+      expect.objectContaining({ accessMode: "full-access" }),
+    );
+  });
+
   it("normalizes object collaboration mode without settings to null in send payload", async () => {
     const options = createOptions({
       collaborationMode: { mode: "default" } as NonNullable<HookOptions["collaborationMode"]>,
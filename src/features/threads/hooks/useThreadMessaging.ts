@@ -2,6 +2,7 @@ import { useCallback, useRef } from "react";
 import type { Dispatch, MutableRefObject } from "react";
 import * as Sentry from "@sentry/react";
 import type {
+  AccessMode,
   RateLimitSnapshot,
   CustomPromptOption,
   DebugEntry,
@@ -34,6 +35,7 @@ type SendMessageOptions = {
   skipPromptExpansion?: boolean;
   model?: string | null;
   effort?: string | null;
+  accessMode?: AccessMode | null;
   collaborationMode?: Record<string, unknown> | null;
   forceSteer?: boolean;
 };
@@ -43,6 +45,7 @@ type UseThreadMessagingOptions = {
   activeThreadId: string | null;
   model?: string | null;
   effort?: string | null;
+  accessMode?: AccessMode | null;
   collaborationMode?: Record<string, unknown> | null;
   reviewDeliveryMode?: "inline" | "detached";
   steerEnabled: boolean;
@@ -241,6 +244,7 @@ export function useThreadMessaging({
   activeThreadId,
   model,
   effort,
+  accessMode,
   collaborationMode,
   reviewDeliveryMode = "inline",
   steerEnabled,
@@ -293,6 +297,8 @@ export function useThreadMessaging({
         options?.model !== undefined ? options.model : model;
       const resolvedEffort =
         options?.effort !== undefined ? options.effort : effort;
+      const resolvedAccessMode =
+        options?.accessMode !== undefined ? options.accessMode : accessMode;
       const resolvedCollaborationMode =
         options?.collaborationMode !== undefined
           ? options.collaborationMode
@@ -386,6 +392,7 @@ export function useThreadMessaging({
           sendUserMessageService(workspace.id, threadId, finalText, {
             model: resolvedModel,
             effort: resolvedEffort,
+            accessMode: resolvedAccessMode,
             collaborationMode: sanitizedCollaborationMode,
             images,
             skillMentions,
@@ -550,6 +557,7 @@ export function useThreadMessaging({
       }
     },
     [
+      accessMode,
       collaborationMode,
       customPrompts,
       dispatch,
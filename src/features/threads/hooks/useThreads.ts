@@ -1,6 +1,7 @@
 import { useCallback, useEffect, useMemo, useReducer, useRef } from "react";
 import * as Sentry from "@sentry/react";
 import type {
+  AccessMode,
   CustomPromptOption,
   DebugEntry,
   ProtocolTurnStatus,
@@ -51,6 +52,7 @@ type UseThreadsOptions = {
   onDebug?: (entry: DebugEntry) => void;
   model?: string | null;
   effort?: string | null;
+  accessMode?: AccessMode | null;
   collaborationMode?: Record<string, unknown> | null;
   reviewDeliveryMode?: "inline" | "detached";
   steerEnabled?: boolean;
@@ -75,6 +77,7 @@ export function useThreads({
   onDebug,
   model,
   effort,
+  accessMode,
   collaborationMode,
   reviewDeliveryMode = "inline",
   steerEnabled = false,
@@ -922,6 +925,7 @@ export function useThreads({
     activeThreadId,
     model,
     effort,
+    accessMode,
     collaborationMode,
     reviewDeliveryMode,
     steerEnabled,
@@ -2,8 +2,10 @@ import { beforeEach, describe, expect, it, vi } from "vitest";
 import type { Event, EventCallback, UnlistenFn } from "@tauri-apps/api/event";
 import { listen } from "@tauri-apps/api/event";
 import type { AppServerEvent } from "../types";
+import type { GitStatusChangedEvent } from "./events";
 import {
   subscribeAppServerEvents,
+  subscribeGitStatusChanged,
   subscribeMenuAddWorkspace,
   subscribeMenuAddWorkspaceFromUrl,
   subscribeMenuCycleCollaborationMode,
@@ -259,4 +261,34 @@ describe("events subscriptions", () => {
 
     cleanup();
   });
+
+  it("delivers git status changes", async () => {
+    let listener: EventCallback<GitStatusChangedEvent> = () => {};
+    const unlisten = vi.fn();
+
+    vi.mocked(listen).mockImplementation((event, handler) => {
+      expect(event).toBe("git-status-changed");
+      listener = handler as EventCallback<GitStatusChangedEvent>;
+      return Promise.resolve(unlisten);
+    });
+
+    const onEvent = vi.fn();
+    const cleanup = subscribeGitStatusChanged(onEvent);
+    const payload: GitStatusChangedEvent = {
+      workspaceId: "ws-1",
+      status: {
+        branchName: "main",
+        files: [],
+        stagedFiles: [],
+        unstagedFiles: [],
+        totalAdditions: 0,
+        totalDeletions: 0,
+      },
+    };
+
+    listener({ event: "git-status-changed", id: 1, payload });
+    expect(onEvent).toHaveBeenCalledWith(payload);
+
+    cleanup();
+  });
 });
@@ -1,5 +1,10 @@
 import { listen } from "@tauri-apps/api/event";
-import type { AppServerEvent, DictationEvent, DictationModelStatus } from "../types";
+import type {
+  AppServerEvent,
+  DictationEvent,
+  DictationModelStatus,
+  GitFileStatus,
+} from "../types";
 
 export type Unsubscribe = () => void;
 
@@ -14,6 +19,18 @@ export type TerminalExitEvent = {
   terminalId: string;
 };
 
+export type GitStatusChangedEvent = {
+  workspaceId: string;
+  status: {
+    branchName: string;
+    files: GitFileStatus[];
+    stagedFiles: GitFileStatus[];
+    unstagedFiles: GitFileStatus[];
+    totalAdditions: number;
+    totalDeletions: number;
+  };
+};
+
 type SubscriptionOptions = {
   onError?: (error: unknown) => void;
 };
@@ -86,6 +103,7 @@ const dictationDownloadHub = createEventHub<DictationModelStatus>("dictation-dow
 const dictationEventHub = createEventHub<DictationEvent>("dictation-event");
 const terminalOutputHub = createEventHub<TerminalOutputEvent>("terminal-output");
 const terminalExitHub = createEventHub<TerminalExitEvent>("terminal-exit");
+const gitStatusChangedHub = createEventHub<GitStatusChangedEvent>("git-status-changed");
 const updaterCheckHub = createEventHub<void>("updater-check");
 const menuNewAgentHub = createEventHub<void>("menu-new-agent");
 const menuNewWorktreeAgentHub = createEventHub<void>("menu-new-worktree-agent");
@@ -141,6 +159,13 @@ export function subscribeTerminalExit(
   return terminalExitHub.subscribe(onEvent, options);
 }
 
+export function subscribeGitStatusChanged(
+  onEvent: (event: GitStatusChangedEvent) => void,
+  options?: SubscriptionOptions,
+): Unsubscribe {
+  return gitStatusChangedHub.subscribe(onEvent, options);
+}
+
 export function subscribeUpdaterCheck(
   onEvent: () => void,
   options?: SubscriptionOptions,
@@ -200,6 +200,15 @@ describe("tauri invoke wrappers", () => {
       workspaceId: "ws-3",
       limit: 40,
     });
+
+    await getGitLog("ws-3", 20, "cursor-1", { author: "alice", path: "src" });
+
+    expect(invokeMock).toHaveBeenLastCalledWith("get_git_log", {
+      workspaceId: "ws-3",
+      limit: 20,
+      cursor: "cursor-1",
+      filter: { author: "alice", path: "src" },
+    });
   });
 
   it("maps workspaceId and threadId for fork_thread", async () => {
@@ -2,6 +2,7 @@ import { invoke } from "@tauri-apps/api/core";
 import { open } from "@tauri-apps/plugin-dialog";
 import type { Options as NotificationOptions } from "@tauri-apps/plugin-notification";
 import type {
+  AccessMode,
   AppSettings,
   CodexUpdateResult,
   CodexDoctorResult,
@@ -26,11 +27,16 @@ import type {
   GitFileDiff,
   GitFileStatus,
   GitCommitDiff,
+  GitConflictResolution,
+  GitConflictStatus,
   GitHubIssuesResponse,
   GitHubPullRequestComment,
   GitHubPullRequestDiff,
   GitHubPullRequestsResponse,
+  GitHunkSelection,
+  GitLogFilter,
   GitLogResponse,
+  GitStashEntry,
   ReviewTarget,
 } from "../types";
 
@@ -422,6 +428,10 @@ export async function connectWorkspace(id: string): Promise<void> {
   return invoke("connect_workspace", { id });
 }
 
+export async function workspaceProcessStats(workspaceId?: string | null) {
+  return invoke<any>("workspace_process_stats", { workspaceId: workspaceId ?? null });
+}
+
 export async function startThread(workspaceId: string) {
   return invoke<any>("start_thread", { workspaceId });
 }
@@ -442,6 +452,18 @@ export async function threadLiveUnsubscribe(workspaceId: string, threadId: strin
   return invoke<any>("thread_live_unsubscribe", { workspaceId, threadId });
 }
 
+export async function replayEvents(
+  workspaceId: string,
+  sinceSeq: number,
+  limit?: number,
+) {
+  return invoke<any>("replay_events", { workspaceId, sinceSeq, limit });
+}
+
+export async function backgroundCallbackMetrics() {
+  return invoke<any>("background_callback_metrics");
+}
+
 export async function sendUserMessage(
   workspaceId: string,
   threadId: string,
@@ -449,6 +471,7 @@ export async function sendUserMessage(
   options?: {
     model?: string | null;
     effort?: string | null;
+    accessMode?: AccessMode | null;
     images?: string[];
     collaborationMode?: Record<string, unknown> | null;
     appMentions?: AppMention[];
@@ -461,7 +484,8 @@ export async function sendUserMessage(
     text,
     model: options?.model ?? null,
     effort: options?.effort ?? null,
-    accessMode: null, // Always null — let app-server use config.toml
+    // null defers to the workspace default, then config.toml.
+    accessMode: options?.accessMode ?? null,
     images: options?.images ?? null,
     appMentions: options?.appMentions ?? null,
     skillMentions: options?.skillMentions ?? null,
@@ -562,6 +586,14 @@ export async function getGitStatus(workspace_id: string): Promise<{
   return invoke("get_git_status", { workspaceId: workspace_id });
 }
 
+export async function watchGitStatus(workspaceId: string): Promise<void> {
+  return invoke("watch_git_status", { workspaceId });
+}
+
+export async function unwatchGitStatus(workspaceId: string): Promise<void> {
+  return invoke("unwatch_git_status", { workspaceId });
+}
+
 export async function listGitRoots(
   workspace_id: string,
   depth: number,
@@ -578,8 +610,15 @@ export async function getGitDiffs(
 export async function getGitLog(
   workspace_id: string,
   limit = 40,
+  cursor?: string | null,
+  filter?: GitLogFilter | null,
 ): Promise<GitLogResponse> {
-  return invoke("get_git_log", { workspaceId: workspace_id, limit });
+  return invoke("get_git_log", {
+    workspaceId: workspace_id,
+    limit,
+    cursor,
+    filter,
+  });
 }
 
 export async function getGitCommitDiff(
@@ -613,6 +652,30 @@ export async function revertGitAll(workspaceId: string) {
   return invoke("revert_git_all", { workspaceId });
 }
 
+export async function stageGitHunks(
+  workspaceId: string,
+  path: string,
+  selection: GitHunkSelection,
+): Promise<void> {
+  return invoke("stage_git_hunks", { workspaceId, path, selection });
+}
+
+export async function unstageGitHunks(
+  workspaceId: string,
+  path: string,
+  selection: GitHunkSelection,
+): Promise<void> {
+  return invoke("unstage_git_hunks", { workspaceId, path, selection });
+}
+
+export async function revertGitHunks(
+  workspaceId: string,
+  path: string,
+  selection: GitHunkSelection,
+): Promise<void> {
+  return invoke("revert_git_hunks", { workspaceId, path, selection });
+}
+
 export async function commitGit(
   workspaceId: string,
   message: string,
@@ -937,6 +1000,71 @@ export async function createGitBranch(workspaceId: string, name: string) {
   return invoke("create_git_branch", { workspaceId, name });
 }
 
+export async function listGitStashes(
+  workspaceId: string,
+): Promise<GitStashEntry[]> {
+  return invoke("list_git_stashes", { workspaceId });
+}
+
+export async function createGitStash(
+  workspaceId: string,
+  message?: string | null,
+  includeUntracked = false,
+): Promise<GitStashEntry> {
+  return invoke("create_git_stash", { workspaceId, message, includeUntracked });
+}
+
+export async function applyGitStash(workspaceId: string, sha: string) {
+  return invoke("apply_git_stash", { workspaceId, sha });
+}
+
+export async function popGitStash(workspaceId: string, sha: string) {
+  return invoke("pop_git_stash", { workspaceId, sha });
+}
+
+export async function dropGitStash(workspaceId: string, sha: string) {
+  return invoke("drop_git_stash", { workspaceId, sha });
+}
+
+export async function getGitStashDiff(
+  workspaceId: string,
+  sha: string,
+): Promise<GitFileDiff[]> {
+  return invoke("get_git_stash_diff", { workspaceId, sha });
+}
+
+export async function getGitConflicts(
+  workspaceId: string,
+): Promise<GitConflictStatus> {
+  return invoke("get_git_conflicts", { workspaceId });
+}
+
+export async function resolveGitConflict(
+  workspaceId: string,
+  path: string,
+  resolution: GitConflictResolution,
+  content?: string | null,
+) {
+  return invoke("resolve_git_conflict", {
+    workspaceId,
+    path,
+    resolution,
+    content,
+  });
+}
+
+export async function abortMerge(workspaceId: string) {
+  return invoke("abort_merge", { workspaceId });
+}
+
+export async function continueMerge(workspaceId: string) {
+  return invoke("continue_merge", { workspaceId });
+}
+
+export async function abortRebase(workspaceId: string) {
+  return invoke("abort_rebase", { workspaceId });
+}
+
 function withModelId(modelId?: string | null) {
   return modelId ? { modelId } : {};
 }
@@ -10,6 +10,10 @@ export type WorkspaceSettings = {
   launchScript?: string | null;
   launchScripts?: LaunchScriptEntry[] | null;
   worktreeSetupScript?: string | null;
+  accessMode?: AccessMode | null;
+  processRssSoftLimitMb?: number | null;
+  processCpuSoftLimitSecs?: number | null;
+  processFdSoftLimit?: number | null;
 };
 
 export type LaunchScriptIconId =
@@ -144,10 +148,10 @@ export type ReviewTarget =
   | { type: "commit"; sha: string; title?: string }
   | { type: "custom"; instructions: string };
 
-/** @deprecated Access mode is now always determined by config.toml. Kept for backward compatibility. */
-export type AccessMode = "current";
+/** "current" defers to the workspace default, then to config.toml. */
+export type AccessMode = "current" | "read-only" | "workspace-write" | "full-access";
 export type BackendMode = "local" | "remote";
-export type RemoteBackendProvider = "tcp" | "orbit";
+export type RemoteBackendProvider = "tcp" | "orbit" | "unix";
 export type ThemePreference = "system" | "light" | "dark" | "dim";
 export type PersonalityPreference = "friendly" | "pragmatic";
 export type ThreadScrollRestoreMode = "latest" | "remember";
@@ -181,6 +185,8 @@ export type AppSettings = {
   backendMode: BackendMode;
   remoteBackendProvider: RemoteBackendProvider;
   remoteBackendHost: string;
+  remoteBackendSocketPath?: string | null;
+  remoteBackendTlsFingerprint?: string | null;
   remoteBackendToken: string | null;
   orbitWsUrl: string | null;
   orbitAuthUrl: string | null;
@@ -252,6 +258,8 @@ export type AppSettings = {
   workspaceGroups: WorkspaceGroup[];
   openAppTargets: OpenAppTarget[];
   selectedOpenAppId: string;
+  appServerRequestTimeoutSecs?: number | null;
+  appServerMethodTimeoutSecs?: Record<string, number>;
 };
 
 export type OrbitConnectTestResult = {
@@ -307,6 +315,7 @@ export type TcpDaemonStatus = {
   startedAtMs: number | null;
   lastError: string | null;
   listenAddr: string | null;
+  managedBy?: string | null;
 };
 
 export type TailscaleStatus = {
@@ -415,6 +424,19 @@ export type GitFileDiff = {
   newImageMime?: string | null;
 };
 
+export type GitDiffSide = "old" | "new";
+
+export type GitLineRange = {
+  side: GitDiffSide;
+  start: number;
+  end: number;
+};
+
+export type GitHunkSelection = {
+  hunks?: string[];
+  lines?: GitLineRange[];
+};
+
 export type GitCommitDiff = {
   path: string;
   status: string;
@@ -429,11 +451,29 @@ export type GitCommitDiff = {
   newImageMime?: string | null;
 };
 
+export type GitLogGraphRow = {
+  lane: number;
+  incomingLanes: number[];
+  mergingLanes: number[];
+  parentLanes: number[];
+};
+
 export type GitLogEntry = {
   sha: string;
   summary: string;
   author: string;
   timestamp: number;
+  parents?: string[];
+  graph?: GitLogGraphRow;
+};
+
+export type GitLogFilter = {
+  author?: string;
+  path?: string;
+  grep?: string;
+  since?: number;
+  until?: number;
+  rev?: string;
 };
 
 export type GitLogResponse = {
@@ -444,8 +484,32 @@ export type GitLogResponse = {
   aheadEntries: GitLogEntry[];
   behindEntries: GitLogEntry[];
   upstream: string | null;
+  nextCursor?: string | null;
+};
+
+export type GitStashEntry = {
+  index: number;
+  sha: string;
+  message: string;
+  timestamp: number;
+};
+
+export type GitConflictFile = {
+  path: string;
+  base: string | null;
+  ours: string | null;
+  theirs: string | null;
+  merged: string | null;
+  isBinary: boolean;
 };
 
+export type GitConflictStatus = {
+  operation: "merge" | "rebase" | "cherry-pick" | "revert" | "am" | null;
+  files: GitConflictFile[];
+};
+
+export type GitConflictResolution = "ours" | "theirs" | "edited";
+
 export type GitHubIssue = {
   number: number;
   title: string;
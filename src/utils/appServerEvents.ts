@@ -8,6 +8,7 @@ export const SUPPORTED_APP_SERVER_METHODS = [
   "codex/backgroundThread",
   "codex/connected",
   "codex/disconnected",
+  "codex/eventStreamSnapshot",
   "codex/event/exec_command_end",
   "codex/event/skills_update_available",
   "codex/stderr",
@@ -29,6 +30,7 @@ export const SUPPORTED_APP_SERVER_METHODS = [
   "app/list/updated",
   "model/rerouted",
   "thread/name/updated",
+  "thread/activity",
   "thread/archived",
   "thread/unarchived",
   "thread/live_attached",